@@ -0,0 +1,319 @@
+//! Board pin maps.
+//!
+//! Each board module maps the pads wired to the board connectors, and creates the pins already
+//! routed to their board functions through the GPIO function multiplexer:
+//!
+//! - [visionfive2]: StarFive VisionFive 2
+//! - [milkv_mars]: Milk-V Mars
+//!
+//! The maps follow the pin assignments of the upstream Linux device trees, in
+//! `arch/riscv/boot/dts/starfive/`: `jh7110-common.dtsi` for the layout shared by both boards,
+//! `jh7110-starfive-visionfive-2.dtsi` and `jh7110-milkv-mars.dts` for the board differences:
+//!
+//! | Signal         | VisionFive 2 | Milk-V Mars        |
+//! |----------------|--------------|--------------------|
+//! | SD card-detect | not wired    | GPIO41, active low |
+//!
+//! Not mapped, as none of these device trees assigns them a pad:
+//!
+//! - board LEDs: there is no `gpio-leds` node.
+//! - ethernet PHY resets: the PHY nodes have no `reset-gpios` property.
+//!
+//! ```no_run
+//! use jh71xx_hal::{board::visionfive2, pac, uart};
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//! let pins = visionfive2::Pins::new(&dp.sys_pinctrl);
+//!
+//! // header pins 8 and 10, already routed to UART0
+//! let _uart0 = uart::Uart::with_pins(dp.uart0, pins.uart0.tx, pins.uart0.rx);
+//!
+//! // header pin 40, GPIO44
+//! let _gpio44 = pins.header.pin40.into_enabled_output();
+//! ```
+
+use crate::gpio::{Disabled, Enabled, Gpio, HiZ, Input, Nop, Pad};
+
+pub mod milkv_mars;
+pub mod visionfive2;
+
+/// Number of 40-pin header pins wired to GPIO pads.
+pub const HEADER_GPIO_PINS: usize = 28;
+
+/// 40-pin header pins wired to GPIO pads, as `(header pin, pad)`.
+///
+/// The remaining header pins are power and ground.
+pub const HEADER: [(u8, Pad); HEADER_GPIO_PINS] = [
+    (3, Pad::Gpio58),
+    (5, Pad::Gpio57),
+    (7, Pad::Gpio55),
+    (8, Pad::Gpio5),
+    (10, Pad::Gpio6),
+    (11, Pad::Gpio42),
+    (12, Pad::Gpio38),
+    (13, Pad::Gpio43),
+    (15, Pad::Gpio47),
+    (16, Pad::Gpio54),
+    (18, Pad::Gpio51),
+    (19, Pad::Gpio52),
+    (21, Pad::Gpio53),
+    (22, Pad::Gpio50),
+    (23, Pad::Gpio48),
+    (24, Pad::Gpio49),
+    (26, Pad::Gpio56),
+    (27, Pad::Gpio45),
+    (28, Pad::Gpio40),
+    (29, Pad::Gpio37),
+    (31, Pad::Gpio39),
+    (32, Pad::Gpio46),
+    (33, Pad::Gpio59),
+    (35, Pad::Gpio63),
+    (36, Pad::Gpio36),
+    (37, Pad::Gpio60),
+    (38, Pad::Gpio61),
+    (40, Pad::Gpio44),
+];
+
+/// Gets the [Pad] wired to a 40-pin header pin.
+///
+/// Returns `None` for power and ground pins, and pin numbers outside the header.
+pub const fn header_pad(pin: u8) -> Option<Pad> {
+    let mut i = 0;
+
+    while i < HEADER.len() {
+        if HEADER[i].0 == pin {
+            return Some(HEADER[i].1);
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Represents a disabled [Gpio] on the SYS pad `GPIO`.
+pub type DisabledPin<'g, GPIO> = Gpio<'g, GPIO, Disabled, Nop, Nop>;
+
+/// Represents a high-impedance input [Gpio] on the SYS pad `GPIO`.
+pub type EnabledInputPin<'g, GPIO> = Gpio<'g, GPIO, Enabled, Input, HiZ>;
+
+// Declares a set of pins created from the SYS pad configuration registers.
+//
+// Fields with a function are routed to it, fields marked `Input` are enabled as high-impedance
+// inputs, and fields without either are left disabled.
+macro_rules! pin_map {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident: $gpio:ident $(=> $func:ident)?,
+            )+
+        }
+    ) => {
+        paste::paste! {
+            $(#[$meta])*
+            pub struct $name<'g> {
+                $(
+                    $(#[$field_meta])*
+                    pub $field: $crate::board::pin_map!(@ty [<$gpio:camel>] $($func)?),
+                )+
+            }
+
+            impl<'g> $name<'g> {
+                #[doc = "Creates a new [" $name "] from the SYS pad configuration registers."]
+                pub fn new(padcfg: &'g $crate::pac::sys_pinctrl::Padcfg) -> Self {
+                    Self {
+                        $(
+                            $field: $crate::board::pin_map!(@pin padcfg.$gpio() $(, $func)?),
+                        )+
+                    }
+                }
+            }
+        }
+    };
+    (@ty $gpio:ident) => {
+        $crate::board::DisabledPin<'g, $crate::pac::sys_pinctrl::padcfg::$gpio>
+    };
+    (@ty $gpio:ident Input) => {
+        $crate::board::EnabledInputPin<'g, $crate::pac::sys_pinctrl::padcfg::$gpio>
+    };
+    (@ty $gpio:ident $func:ident) => {
+        $crate::gpio::AlternatePin<'g, $crate::pac::sys_pinctrl::padcfg::$gpio, $crate::gpio::$func>
+    };
+    (@pin $periph:expr) => { $crate::gpio::get_gpio($periph) };
+    (@pin $periph:expr, Input) => { $crate::gpio::get_gpio($periph).into_enabled_input() };
+    (@pin $periph:expr, $func:ident) => {
+        $crate::gpio::get_gpio($periph).into_alternate::<$crate::gpio::$func>()
+    };
+}
+
+use pin_map;
+
+pin_map!(
+    /// 40-pin header pins without a board function, left disabled.
+    ///
+    /// Fields are named after the header pin number.
+    HeaderPins {
+        /// GPIO55
+        pin7: gpio55,
+        /// GPIO42
+        pin11: gpio42,
+        /// GPIO38
+        pin12: gpio38,
+        /// GPIO43
+        pin13: gpio43,
+        /// GPIO47
+        pin15: gpio47,
+        /// GPIO54
+        pin16: gpio54,
+        /// GPIO51
+        pin18: gpio51,
+        /// GPIO50
+        pin22: gpio50,
+        /// GPIO56
+        pin26: gpio56,
+        /// GPIO45
+        pin27: gpio45,
+        /// GPIO40
+        pin28: gpio40,
+        /// GPIO37
+        pin29: gpio37,
+        /// GPIO39
+        pin31: gpio39,
+        /// GPIO63
+        pin35: gpio63,
+        /// GPIO36
+        pin36: gpio36,
+        /// GPIO60
+        pin37: gpio60,
+        /// GPIO61
+        pin38: gpio61,
+        /// GPIO44
+        pin40: gpio44,
+    }
+);
+
+pin_map!(
+    /// UART0 console on the 40-pin header.
+    Uart0Pins {
+        /// Header pin 8, GPIO5
+        tx: gpio5 => Uart0Tx,
+        /// Header pin 10, GPIO6
+        rx: gpio6 => Uart0Rx,
+    }
+);
+
+pin_map!(
+    /// I2C0 on the 40-pin header.
+    I2c0Pins {
+        /// Header pin 3, GPIO58
+        sda: gpio58 => I2c0Sda,
+        /// Header pin 5, GPIO57
+        scl: gpio57 => I2c0Scl,
+    }
+);
+
+pin_map!(
+    /// SPI0 on the 40-pin header.
+    Spi0Pins {
+        /// Header pin 23, GPIO48
+        sck: gpio48 => Spi0Clk,
+        /// Header pin 19, GPIO52
+        mosi: gpio52 => Spi0Tx,
+        /// Header pin 21, GPIO53
+        miso: gpio53 => Spi0Rx,
+        /// Header pin 24, GPIO49
+        cs: gpio49 => Spi0Cs,
+    }
+);
+
+pin_map!(
+    /// PWM channels 0 and 1 on the 40-pin header.
+    PwmPins {
+        /// Header pin 32, GPIO46
+        pwm0: gpio46 => Pwm0,
+        /// Header pin 33, GPIO59
+        pwm1: gpio59 => Pwm1,
+    }
+);
+
+pin_map!(
+    /// SD card slot, on SDIO1 in 4-bit mode, without card-detect.
+    SdCardPins {
+        /// GPIO10
+        clk: gpio10 => Sdio1Clk,
+        /// GPIO9
+        cmd: gpio9 => Sdio1Cmd,
+        /// GPIO11
+        data0: gpio11 => Sdio1Data0,
+        /// GPIO12
+        data1: gpio12 => Sdio1Data1,
+        /// GPIO7
+        data2: gpio7 => Sdio1Data2,
+        /// GPIO8
+        data3: gpio8 => Sdio1Data3,
+    }
+);
+
+pin_map!(
+    /// eMMC module, on SDIO0.
+    ///
+    /// The clock, command and data signals use the dedicated `SD0_*` pads, only the reset is
+    /// routed through the function multiplexer.
+    EmmcPins {
+        /// GPIO62
+        rst: gpio62 => Sdio0Rst,
+    }
+);
+
+// Declares the pins of a board, each member created from the SYS pad configuration registers.
+//
+// Member types are resolved at the call site.
+macro_rules! board_pins {
+    ($(#[$meta:meta])* $($field:ident: $ty:ident,)+) => {
+        $(#[$meta])*
+        pub struct Pins<'g> {
+            $(
+                #[doc = concat!("See [", stringify!($ty), "].")]
+                pub $field: $ty<'g>,
+            )+
+        }
+
+        impl<'g> Pins<'g> {
+            /// Creates a new [Pins], routing each pin to its board function.
+            pub fn new(sys_pinctrl: &'g $crate::pac::SysPinctrl) -> Self {
+                let padcfg = sys_pinctrl.padcfg();
+
+                Self {
+                    $($field: $ty::new(padcfg),)+
+                }
+            }
+        }
+    };
+}
+
+use board_pins;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header() {
+        assert_eq!(header_pad(3), Some(Pad::Gpio58));
+        assert_eq!(header_pad(8), Some(Pad::Gpio5));
+        assert_eq!(header_pad(40), Some(Pad::Gpio44));
+        assert_eq!(header_pad(1), None);
+        assert_eq!(header_pad(41), None);
+
+        for (i, (pin, pad)) in HEADER.iter().enumerate() {
+            assert!((1..=40).contains(pin));
+            assert!(u32::from(*pad) < crate::gpio::FMUX_PADS);
+
+            for (other_pin, other_pad) in HEADER[i + 1..].iter() {
+                assert!(pin < other_pin);
+                assert_ne!(pad, other_pad);
+            }
+        }
+    }
+}
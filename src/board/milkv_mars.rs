@@ -0,0 +1,47 @@
+//! Milk-V Mars pin map.
+//!
+//! The Mars follows the VisionFive 2 layout: the 40-pin header, SD card slot and eMMC connector
+//! use the same pads. The SD card slot adds a card-detect input on GPIO41, see [SdCardPins].
+//!
+//! ```no_run
+//! use jh71xx_hal::{board::milkv_mars, pac, pwm};
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//! let pins = milkv_mars::Pins::new(&dp.sys_pinctrl);
+//!
+//! // header pin 32
+//! let _pwm0 = pwm::Pwm::with_pin(dp.pwm, pins.pwm.pwm0);
+//! ```
+
+use super::{EmmcPins, HeaderPins, I2c0Pins, PwmPins, Spi0Pins, Uart0Pins};
+
+super::pin_map!(
+    /// SD card slot, on SDIO1 in 4-bit mode, with card-detect.
+    SdCardPins {
+        /// GPIO10
+        clk: gpio10 => Sdio1Clk,
+        /// GPIO9
+        cmd: gpio9 => Sdio1Cmd,
+        /// GPIO11
+        data0: gpio11 => Sdio1Data0,
+        /// GPIO12
+        data1: gpio12 => Sdio1Data1,
+        /// GPIO7
+        data2: gpio7 => Sdio1Data2,
+        /// GPIO8
+        data3: gpio8 => Sdio1Data3,
+        /// GPIO41, card-detect, low when a card is inserted
+        cd: gpio41 => Input,
+    }
+);
+
+super::board_pins!(
+    /// Milk-V Mars pins, routed to their board functions.
+    header: HeaderPins,
+    uart0: Uart0Pins,
+    i2c0: I2c0Pins,
+    spi0: Spi0Pins,
+    pwm: PwmPins,
+    sd_card: SdCardPins,
+    emmc: EmmcPins,
+);
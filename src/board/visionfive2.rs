@@ -0,0 +1,26 @@
+//! StarFive VisionFive 2 pin map.
+//!
+//! The SD card slot has no card-detect.
+//!
+//! ```no_run
+//! use jh71xx_hal::{board::visionfive2, i2c, pac};
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//! let pins = visionfive2::Pins::new(&dp.sys_pinctrl);
+//!
+//! // header pins 3 and 5
+//! let _i2c0 = i2c::I2c::with_pins(dp.i2c0, pins.i2c0.sda, pins.i2c0.scl);
+//! ```
+
+use super::{EmmcPins, HeaderPins, I2c0Pins, PwmPins, SdCardPins, Spi0Pins, Uart0Pins};
+
+super::board_pins!(
+    /// VisionFive 2 pins, routed to their board functions.
+    header: HeaderPins,
+    uart0: Uart0Pins,
+    i2c0: I2c0Pins,
+    spi0: Spi0Pins,
+    pwm: PwmPins,
+    sd_card: SdCardPins,
+    emmc: EmmcPins,
+);
@@ -2,20 +2,43 @@
 //!
 //! Some implementations derived from [`oreboot`](https://github.com/oreboot/oreboot/blob/main/src/mainboard/starfive/visionfive2/bt0/src/init.rs).
 
-use crate::pac;
+use core::fmt;
+
+use embedded_hal::delay::DelayNs;
+
+use crate::{delay, pac, pll};
 
 mod aoncrg;
+mod cpu;
+mod error;
 mod ispcrg;
+mod output;
+mod peripheral;
+mod rates;
+mod reset;
 mod stgcrg;
 mod syscrg;
+mod tree;
 mod voutcrg;
 
 pub use aoncrg::*;
+pub use cpu::*;
+pub use error::*;
 pub use ispcrg::*;
+pub use output::*;
+pub use peripheral::*;
+pub use rates::*;
+pub use reset::*;
 pub use stgcrg::*;
 pub use syscrg::*;
+pub use tree::*;
 pub use voutcrg::*;
 
+/// Main oscillator clock rate (in Hertz): 24 MHz
+pub const OSC_HZ: u64 = 24_000_000;
+/// RTC oscillator clock rate (in Hertz): 32.768 kHz
+pub const RTC_OSC_HZ: u64 = 32_768;
+
 /// Convenience alias for CPU Root clock mux selector.
 pub type ClkCpuRootMuxSel = pac::syscrg::clk_cpu::root::ClkMuxSel;
 
@@ -141,6 +164,391 @@ impl Clocks {
         self
     }
 
+    // Reads the raw register value at `index` of a CRG.
+    fn read_crg_raw(&self, crg: Crg, index: usize) -> u32 {
+        match crg {
+            Crg::Sys => self.syscrg.read_clk(index),
+            Crg::Aon => self.aoncrg.read_clk(index),
+            Crg::Stg => self.stgcrg.read_clk(index),
+            Crg::Isp => self.ispcrg.read_clk(index),
+            Crg::Vout => self.voutcrg.read_clk(index),
+        }
+    }
+
+    /// Reads the raw register value of a clock.
+    ///
+    /// **NOTE**: ISP and VOUT clocks are only accessible with their clock domain enabled,
+    /// see [Peripheral::domain].
+    pub fn read_raw(&self, id: ClockId) -> u32 {
+        self.read_crg_raw(id.crg(), id.index())
+    }
+
+    /// Gets whether a [Reset] line is asserted.
+    pub fn is_reset_asserted(&self, reset: Reset) -> bool {
+        let crg = reset.crg();
+
+        self.read_crg_raw(crg, crg.rst_status_index() + reset.register()) & reset.mask() == 0
+    }
+
+    /// Gets whether the clocks of a [Peripheral] are enabled, and its resets released.
+    ///
+    /// Peripherals in a disabled clock domain are reported as disabled.
+    pub fn is_peripheral_enabled(&self, periph: Peripheral) -> bool {
+        let domain_enabled = periph
+            .domain()
+            .is_none_or(|domain| self.is_peripheral_enabled(domain));
+
+        domain_enabled
+            && periph
+                .clocks()
+                .iter()
+                .filter(|id| id.kind().has_gate())
+                .all(|&id| self.read_raw(id) & CLK_ICG_MASK != 0)
+            && !periph
+                .resets()
+                .iter()
+                .any(|&reset| self.is_reset_asserted(reset))
+    }
+
+    /// Modifies the raw register value of a clock.
+    fn modify_raw(&mut self, id: ClockId, f: impl FnOnce(u32) -> u32) {
+        match id.crg() {
+            Crg::Sys => self.syscrg.modify_clk(id.index(), f),
+            Crg::Aon => self.aoncrg.modify_clk(id.index(), f),
+            Crg::Stg => self.stgcrg.modify_clk(id.index(), f),
+            Crg::Isp => self.ispcrg.modify_clk(id.index(), f),
+            Crg::Vout => self.voutcrg.modify_clk(id.index(), f),
+        }
+    }
+
+    /// Sets whether the gate of a clock is enabled.
+    ///
+    /// No-op for clocks without a gate.
+    pub fn set_clock_gate(&mut self, id: ClockId, enable: bool) -> &mut Self {
+        if id.kind().has_gate() {
+            self.modify_raw(id, |r| {
+                if enable {
+                    r | CLK_ICG_MASK
+                } else {
+                    r & !CLK_ICG_MASK
+                }
+            });
+        }
+
+        self
+    }
+
+    /// Gets the divider value of a clock.
+    ///
+    /// For fractional dividers, the value is scaled by 100.
+    pub fn divider(&self, id: ClockId) -> Result<u32> {
+        if id.kind().has_div() {
+            Ok(id.div_from_bits(self.read_raw(id)))
+        } else {
+            Err(Error::NoDivider(id))
+        }
+    }
+
+    /// Sets the divider value of a clock.
+    ///
+    /// For fractional dividers, the value is scaled by 100, e.g. `651` for a divisor of `6.51`.
+    ///
+    /// Returns an error if the clock has no divider, or `div` is outside of its range
+    /// (see [ClockId::min_div] and [ClockId::max_div]).
+    pub fn set_divider(&mut self, id: ClockId, div: u32) -> Result<()> {
+        let bits = id.div_to_bits(div)?;
+        let mask = id.kind().div_mask();
+
+        self.modify_raw(id, |r| (r & !mask) | bits);
+
+        Ok(())
+    }
+
+    /// Gets the index of the selected parent of a multiplexed clock.
+    pub fn mux_sel(&self, id: ClockId) -> Result<usize> {
+        if id.kind().has_mux() {
+            Ok(((self.read_raw(id) & CLK_MUX_MASK) >> CLK_MUX_SHIFT) as usize)
+        } else {
+            Err(Error::InvalidMux(id, 0))
+        }
+    }
+
+    /// Selects the parent of a multiplexed clock, `mux_sel` indexes into [ClockKind::parents].
+    pub fn set_mux_sel(&mut self, id: ClockId, mux_sel: usize) -> Result<()> {
+        if mux_sel >= id.kind().parents().len() {
+            return Err(Error::InvalidMux(id, mux_sel));
+        }
+
+        self.modify_raw(id, |r| {
+            (r & !CLK_MUX_MASK) | (((mux_sel as u32) << CLK_MUX_SHIFT) & CLK_MUX_MASK)
+        });
+
+        Ok(())
+    }
+
+    /// Sets the rate of a clock as close as possible to `target_hz`, using the clock mux and divider.
+    ///
+    /// Parent clocks are left unchanged, `plls` provides the PLL output rates.
+    ///
+    /// Returns the achieved clock rate (in Hertz).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{clocks, pac, pll};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut clocks = clocks::Clocks::new(dp.aoncrg, dp.ispcrg, dp.stgcrg, dp.syscrg, dp.voutcrg);
+    /// let pll = pll::Pll::new(dp.sys_syscon);
+    ///
+    /// let _qspi_hz = clocks
+    ///     .set_rate(clocks::ClockId::QspiRefSrc, 100_000_000, pll.rates())
+    ///     .unwrap();
+    /// ```
+    pub fn set_rate(&mut self, id: ClockId, target_hz: u64, plls: PllRates) -> Result<u64> {
+        let config = self.freeze(plls).round_rate(id, target_hz)?;
+        let kind = id.kind();
+
+        let (mut mask, mut bits) = (0, 0);
+
+        if kind.has_mux() {
+            mask |= CLK_MUX_MASK;
+            bits |= (config.mux_sel() as u32) << CLK_MUX_SHIFT;
+        }
+
+        if kind.has_div() {
+            mask |= kind.div_mask();
+            bits |= id.div_to_bits(config.div())?;
+        }
+
+        // mux and divider share the register, switch both at once
+        self.modify_raw(id, |r| (r & !mask) | bits);
+
+        Ok(config.hz())
+    }
+
+    /// Scales the U74 CPU cores to the rate `hz`, reprogramming PLL0.
+    ///
+    /// The CPU root is parked on the oscillator while PLL0 settles, so the cores never run from
+    /// an unstable clock. The delay tick rate ([`u74_mdelay`](delay::u74_mdelay)) follows each step.
+    ///
+    /// `plls` provides the PLL rates before scaling. Returns the updated [ClockRates].
+    ///
+    /// **NOTE**: other PLL0 children (peripheral root, QSPI, GMAC, USB) change rate with PLL0,
+    /// and must be reconfigured by the caller.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{clocks, pac, pll};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut clocks = clocks::Clocks::new(dp.aoncrg, dp.ispcrg, dp.stgcrg, dp.syscrg, dp.voutcrg);
+    /// let mut pll = pll::Pll::new(dp.sys_syscon);
+    ///
+    /// let plls = pll.rates();
+    /// let rates = clocks.set_cpu_frequency(&mut pll, 1_500_000_000, plls).unwrap();
+    /// assert_eq!(rates.cpu_hz(), 1_500_000_000);
+    /// ```
+    pub fn set_cpu_frequency(
+        &mut self,
+        pll: &mut pll::Pll,
+        hz: u64,
+        plls: PllRates,
+    ) -> Result<ClockRates> {
+        let config = CpuConfig::from_target_hz(hz)?;
+
+        // park the CPU root on the oscillator
+        self.set_mux_sel(ClockId::CpuRoot, 0)?;
+        self.freeze(plls);
+
+        pll.set_pll0(config.freq());
+        pll.settle(pll::PllId::Pll0)?;
+
+        self.set_divider(ClockId::CpuCore, config.core_div())?;
+        self.set_divider(ClockId::CpuBus, CPU_BUS_DIV)?;
+
+        // switch back to PLL0
+        self.set_mux_sel(ClockId::CpuRoot, 1)?;
+
+        Ok(self.freeze(PllRates::new(config.pll0_hz(), plls.pll1_hz, plls.pll2_hz)))
+    }
+
+    /// Enables the clock gates of a [Peripheral].
+    pub fn enable_clock(&mut self, periph: Peripheral) -> &mut Self {
+        periph.clocks().iter().for_each(|&id| {
+            self.set_clock_gate(id, true);
+        });
+
+        self
+    }
+
+    /// Disables the clock gates of a [Peripheral].
+    pub fn disable_clock(&mut self, periph: Peripheral) -> &mut Self {
+        periph.clocks().iter().rev().for_each(|&id| {
+            self.set_clock_gate(id, false);
+        });
+
+        self
+    }
+
+    /// Asserts or deasserts a [Reset] line, waiting for the reset status to follow.
+    pub fn set_reset(&mut self, reset: Reset, assert: bool) -> Result<()> {
+        match reset.crg() {
+            Crg::Sys => self.syscrg.set_reset(reset, assert),
+            Crg::Aon => self.aoncrg.set_reset(reset, assert),
+            Crg::Stg => self.stgcrg.set_reset(reset, assert),
+            Crg::Isp => self.ispcrg.set_reset(reset, assert),
+            Crg::Vout => self.voutcrg.set_reset(reset, assert),
+        }
+    }
+
+    /// Asserts the reset lines of a [Peripheral].
+    pub fn assert_reset(&mut self, periph: Peripheral) -> Result<()> {
+        periph
+            .resets()
+            .iter()
+            .rev()
+            .try_for_each(|&reset| self.set_reset(reset, true))
+    }
+
+    /// Deasserts the reset lines of a [Peripheral].
+    ///
+    /// **NOTE**: the peripheral clocks must be enabled, otherwise the reset status never
+    /// reports the reset as released.
+    pub fn deassert_reset(&mut self, periph: Peripheral) -> Result<()> {
+        periph
+            .resets()
+            .iter()
+            .try_for_each(|&reset| self.set_reset(reset, false))
+    }
+
+    /// Enables the clocks of a [Peripheral], and brings it out of reset.
+    ///
+    /// The [clock domain](Peripheral::domain) of the peripheral is enabled first, e.g. the VOUT
+    /// parent clocks and resets in SYSCRG before the DC8200 clocks in VOUTCRG.
+    ///
+    /// **NOTE**: the ISP and VOUT power domains must be powered on in the PMU beforehand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{clocks, pac};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut clocks = clocks::Clocks::new(dp.aoncrg, dp.ispcrg, dp.stgcrg, dp.syscrg, dp.voutcrg);
+    ///
+    /// clocks.enable_peripheral(clocks::Peripheral::I2c0).unwrap();
+    /// ```
+    pub fn enable_peripheral(&mut self, periph: Peripheral) -> Result<()> {
+        if let Some(domain) = periph.domain() {
+            self.enable_peripheral(domain)?;
+        }
+
+        self.enable_clock(periph);
+        self.deassert_reset(periph)
+    }
+
+    /// Puts a [Peripheral] into reset, and disables its clocks.
+    ///
+    /// The [clock domain](Peripheral::domain) of the peripheral is left enabled.
+    pub fn disable_peripheral(&mut self, periph: Peripheral) -> Result<()> {
+        self.assert_reset(periph)?;
+        self.disable_clock(periph);
+
+        Ok(())
+    }
+
+    /// Writes a report of the PLL settings, and of the SYSCRG, AONCRG and STGCRG clocks to `w`.
+    ///
+    /// See [ClockRates::dump] for the clock report format.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{clocks, pac, pll, uart};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let clocks = clocks::Clocks::new(dp.aoncrg, dp.ispcrg, dp.stgcrg, dp.syscrg, dp.voutcrg);
+    /// let pll = pll::Pll::new(dp.sys_syscon);
+    /// let mut uart0 = uart::Uart::new(dp.uart0);
+    ///
+    /// clocks.dump(&pll, &mut uart0).unwrap();
+    /// ```
+    pub fn dump(&self, pll: &pll::Pll, w: &mut impl fmt::Write) -> fmt::Result {
+        [pll::PllId::Pll0, pll::PllId::Pll1, pll::PllId::Pll2]
+            .into_iter()
+            .try_for_each(|id| {
+                let freq = pll.freq(id);
+
+                writeln!(
+                    w,
+                    "{id:?}: prediv {} fbdiv {} frac {} postdiv1 {} {} {} Hz",
+                    freq.prediv,
+                    freq.fbdiv,
+                    freq.frac,
+                    freq.postdiv1,
+                    if pll.is_powered_down(id) {
+                        "down"
+                    } else {
+                        "up"
+                    },
+                    pll.output_hz(id),
+                )
+            })?;
+
+        self.freeze(pll.rates()).dump(w)
+    }
+
+    /// Freezes the current clock configuration into a [ClockRates] snapshot.
+    ///
+    /// `plls` are the current output rates of the PLLs feeding the clock tree.
+    ///
+    /// ISP and VOUT clocks read as stopped while their clock domain is disabled.
+    ///
+    /// The frozen CPU core rate becomes the rate of the default U74 delays, see
+    /// [`u74_clock_hz`](crate::delay::u74_clock_hz).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{clocks, pac, pll};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let clocks = clocks::Clocks::new(dp.aoncrg, dp.ispcrg, dp.stgcrg, dp.syscrg, dp.voutcrg);
+    ///
+    /// let plls = clocks::PllRates::from_freqs(
+    ///     &pll::Freq::pll0_1ghz(),
+    ///     &pll::Freq::pll1_ddr2133_1066mhz(),
+    ///     &pll::Freq::pll2_1188mhz(),
+    /// );
+    /// let rates = clocks.freeze(plls);
+    ///
+    /// let _cpu_hz = rates.cpu_hz();
+    /// let _uart0_hz = rates.uart_core_hz(0).unwrap();
+    /// ```
+    pub fn freeze(&self, plls: PllRates) -> ClockRates {
+        let isp_enabled = self.is_peripheral_enabled(Peripheral::IspTop);
+        let vout_enabled = self.is_peripheral_enabled(Peripheral::VoutTop);
+
+        let mut regs = [0u32; ClockId::COUNT];
+
+        ClockId::ALL
+            .iter()
+            .zip(regs.iter_mut())
+            .filter(|(id, _)| match id.crg() {
+                Crg::Isp => isp_enabled,
+                Crg::Vout => vout_enabled,
+                _ => true,
+            })
+            .for_each(|(&id, reg)| *reg = self.read_raw(id));
+
+        let rates = ClockRates::from_raw(plls, regs);
+        delay::set_u74_clock_hz(rates.cpu_hz());
+
+        rates
+    }
+
     /// Releases ownership of clock configuration peripherals, conuming the [Clocks].
     ///
     /// Gives ownership of the peripherals back to the caller, before calling [Clocks] destructor.
@@ -164,3 +572,60 @@ impl Clocks {
         )
     }
 }
+
+/// Reads the clock register at `index` of the CRG block starting at `base`.
+///
+/// # Safety
+///
+/// `base` must point to a CRG block, and `index` must be a valid clock register index in that block.
+pub(crate) unsafe fn read_crg(base: *const u32, index: usize) -> u32 {
+    base.add(index).read_volatile()
+}
+
+/// Modifies the clock register at `index` of the CRG block starting at `base`.
+///
+/// # Safety
+///
+/// `base` must point to a CRG block, and `index` must be a valid register index in that block.
+pub(crate) unsafe fn modify_crg(base: *mut u32, index: usize, f: impl FnOnce(u32) -> u32) {
+    let reg = base.add(index);
+    reg.write_volatile(f(reg.read_volatile()));
+}
+
+/// Asserts or deasserts a [Reset] line of the CRG block starting at `base`.
+///
+/// Waits up to [RESET_TIMEOUT_US] for the reset status to follow the request.
+///
+/// # Safety
+///
+/// `base` must point to the CRG block containing the `reset` line.
+pub(crate) unsafe fn update_reset(base: *mut u32, reset: Reset, assert: bool) -> Result<()> {
+    let crg = reset.crg();
+    let mask = reset.mask();
+
+    modify_crg(base, crg.rst_assert_index() + reset.register(), |r| {
+        if assert {
+            r | mask
+        } else {
+            r & !mask
+        }
+    });
+
+    // the status bit reads one once the reset is released
+    let done = if assert { 0 } else { mask };
+    let status = crg.rst_status_index() + reset.register();
+
+    let mut udelay = delay::u74_mdelay();
+    let mut time = 0;
+
+    while read_crg(base, status) & mask != done {
+        if time >= RESET_TIMEOUT_US {
+            return Err(Error::ResetTimeout(reset));
+        }
+
+        udelay.delay_us(1);
+        time += 1;
+    }
+
+    Ok(())
+}
@@ -32,6 +32,30 @@ impl ClockAoncrg {
         self
     }
 
+    /// Reads the raw value of the clock register at `index`.
+    pub(crate) fn read_clk(&self, index: usize) -> u32 {
+        // SAFETY: all AONCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { read_crg(pac::Aoncrg::ptr() as *const u32, index) }
+    }
+
+    /// Modifies the raw value of the clock register at `index`.
+    pub(crate) fn modify_clk(&mut self, index: usize, f: impl FnOnce(u32) -> u32) {
+        // SAFETY: all AONCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { modify_crg(pac::Aoncrg::ptr() as *mut u32, index, f) }
+    }
+
+    /// Asserts or deasserts a AONCRG [Reset] line, waiting for the reset status to follow.
+    pub fn set_reset(&mut self, reset: Reset, assert: bool) -> Result<()> {
+        if reset.crg() != Crg::Aon {
+            return Err(Error::InvalidReset(reset));
+        }
+
+        // SAFETY: the reset line belongs to AONCRG.
+        unsafe { update_reset(pac::Aoncrg::ptr() as *mut u32, reset, assert) }
+    }
+
     /// Releases ownership of [`AONCRG`](pac::Aoncrg) clock configuration peripherals, conuming the [ClockAoncrg].
     ///
     /// Gives ownership of the peripheral back to the caller, before calling [ClockAoncrg] destructor.
@@ -0,0 +1,108 @@
+//! CPU frequency scaling on PLL0.
+
+use super::*;
+use crate::pll::Freq;
+
+/// Maximum rate of the U74 CPU cores (in Hertz): 1.5 GHz
+pub const CPU_MAX_HZ: u64 = 1_500_000_000;
+/// Minimum PLL0 rate (in Hertz) used for CPU frequency scaling: 375 MHz
+pub const PLL0_MIN_HZ: u64 = 375_000_000;
+/// Maximum PLL0 rate (in Hertz) used for CPU frequency scaling: 1.5 GHz
+pub const PLL0_MAX_HZ: u64 = CPU_MAX_HZ;
+/// CPU bus clock divider used during CPU frequency scaling.
+pub const CPU_BUS_DIV: u32 = 2;
+
+/// PLL0 and CPU divider settings for a target CPU core rate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CpuConfig {
+    freq: Freq,
+    pll0_hz: u64,
+    core_div: u32,
+}
+
+impl CpuConfig {
+    /// Creates a new [CpuConfig] for the CPU core rate `hz`.
+    ///
+    /// PLL0 is kept in its scaling range ([PLL0_MIN_HZ]..=[PLL0_MAX_HZ]), lower rates are reached
+    /// with the CPU core divider.
+    ///
+    /// PLL0 settings come from [Freq::from_target_hz], so [cpu_hz](Self::cpu_hz) may differ
+    /// slightly from `hz`.
+    pub fn from_target_hz(hz: u64) -> Result<Self> {
+        if hz == 0 || hz > CPU_MAX_HZ {
+            return Err(Error::InvalidRate(ClockId::CpuCore, hz));
+        }
+
+        (1..=ClockId::CpuCore.max_div())
+            .map(|core_div| (core_div, hz * core_div as u64))
+            .filter(|(_, pll0_hz)| (PLL0_MIN_HZ..=PLL0_MAX_HZ).contains(pll0_hz))
+            .find_map(|(core_div, pll0_hz)| {
+                Freq::from_target_hz(pll0_hz, OSC_HZ)
+                    .ok()
+                    .map(|(freq, pll0_hz)| Self {
+                        freq,
+                        pll0_hz,
+                        core_div,
+                    })
+            })
+            .ok_or(Error::InvalidRate(ClockId::CpuCore, hz))
+    }
+
+    /// Gets the PLL0 [Freq] settings.
+    pub const fn freq(&self) -> Freq {
+        self.freq
+    }
+
+    /// Gets the PLL0 rate (in Hertz).
+    pub const fn pll0_hz(&self) -> u64 {
+        self.pll0_hz
+    }
+
+    /// Gets the CPU core clock divider.
+    pub const fn core_div(&self) -> u32 {
+        self.core_div
+    }
+
+    /// Gets the CPU core rate (in Hertz).
+    pub const fn cpu_hz(&self) -> u64 {
+        self.pll0_hz / self.core_div as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_config() {
+        let cfg = CpuConfig::from_target_hz(1_000_000_000).unwrap();
+        assert_eq!(cfg.freq(), Freq::pll0_1ghz());
+        assert_eq!(cfg.core_div(), 1);
+        assert_eq!(cfg.cpu_hz(), 1_000_000_000);
+
+        let cfg = CpuConfig::from_target_hz(1_500_000_000).unwrap();
+        assert_eq!((cfg.freq().prediv, cfg.freq().fbdiv), (2, 125));
+
+        let cfg = CpuConfig::from_target_hz(1_250_000_000).unwrap();
+        assert_eq!((cfg.freq().prediv, cfg.freq().fbdiv), (12, 625));
+
+        // no exact integer setting, fractional mode gets within a Hertz
+        let cfg = CpuConfig::from_target_hz(1_234_567_890).unwrap();
+        assert!(cfg.freq().is_fractional());
+        assert!(cfg.cpu_hz().abs_diff(1_234_567_890) <= 1);
+
+        // below the PLL0 range, the core divider takes over
+        let cfg = CpuConfig::from_target_hz(250_000_000).unwrap();
+        assert_eq!((cfg.pll0_hz(), cfg.core_div()), (500_000_000, 2));
+        assert_eq!(cfg.cpu_hz(), 250_000_000);
+
+        assert_eq!(
+            CpuConfig::from_target_hz(2_000_000_000),
+            Err(Error::InvalidRate(ClockId::CpuCore, 2_000_000_000))
+        );
+        assert_eq!(
+            CpuConfig::from_target_hz(1),
+            Err(Error::InvalidRate(ClockId::CpuCore, 1))
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use core::fmt;
+
+use super::{ClockId, Reset};
+use crate::pll;
+
+/// Convenience [`Result`](core::result::Result) alias for JH71xx clocks module.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Clock configuration errors.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Peripheral instance index does not exist on the SoC, e.g. `UART6`.
+    InvalidIndex(usize),
+    /// Clock does not have a divider.
+    NoDivider(ClockId),
+    /// Divider value is outside the range of the clock divider.
+    InvalidDivider(ClockId, u32),
+    /// Clock does not have a mux, or the mux selector is out of range.
+    InvalidMux(ClockId, usize),
+    /// Clock rate cannot be reached by the clock mux and divider.
+    InvalidRate(ClockId, u64),
+    /// PLL configuration error.
+    Pll(pll::Error),
+    /// Reset line does not belong to the CRG.
+    InvalidReset(Reset),
+    /// Reset status did not follow the reset request in time.
+    ResetTimeout(Reset),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidIndex(err) => write!(f, "invalid peripheral index: {err}"),
+            Self::NoDivider(id) => write!(f, "clock {} has no divider", id.name()),
+            Self::InvalidDivider(id, div) => {
+                write!(f, "invalid divider {div} for clock {}", id.name())
+            }
+            Self::InvalidMux(id, sel) => {
+                write!(f, "invalid mux selector {sel} for clock {}", id.name())
+            }
+            Self::InvalidRate(id, hz) => write!(f, "invalid rate {hz} Hz for clock {}", id.name()),
+            Self::Pll(err) => write!(f, "{err}"),
+            Self::InvalidReset(err) => write!(
+                f,
+                "invalid reset line {} for {:?} CRG",
+                err.line(),
+                err.crg()
+            ),
+            Self::ResetTimeout(err) => write!(
+                f,
+                "reset line {} of {:?} CRG timed out",
+                err.line(),
+                err.crg()
+            ),
+        }
+    }
+}
+
+impl From<pll::Error> for Error {
+    fn from(err: pll::Error) -> Self {
+        Self::Pll(err)
+    }
+}
@@ -1,7 +1,10 @@
+use super::*;
 use crate::pac;
 
 /// Contains configuration registers for SoC [`ISPCRG`](pac::Ispcrg) clock peripheral.
-// FIXME: currently a place-holder, implement ISPCRG specific functionality
+///
+/// The ISPCRG registers are only accessible once the ISP power domain is on, and the
+/// [`Peripheral::IspTop`] clock domain is enabled.
 #[repr(C)]
 pub struct ClockIspcrg {
     ispcrg: pac::Ispcrg,
@@ -23,6 +26,30 @@ impl ClockIspcrg {
         Self { ispcrg }
     }
 
+    /// Reads the raw value of the clock register at `index`.
+    pub(crate) fn read_clk(&self, index: usize) -> u32 {
+        // SAFETY: all ISPCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { read_crg(pac::Ispcrg::ptr() as *const u32, index) }
+    }
+
+    /// Modifies the raw value of the clock register at `index`.
+    pub(crate) fn modify_clk(&mut self, index: usize, f: impl FnOnce(u32) -> u32) {
+        // SAFETY: all ISPCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { modify_crg(pac::Ispcrg::ptr() as *mut u32, index, f) }
+    }
+
+    /// Asserts or deasserts a ISPCRG [Reset] line, waiting for the reset status to follow.
+    pub fn set_reset(&mut self, reset: Reset, assert: bool) -> Result<()> {
+        if reset.crg() != Crg::Isp {
+            return Err(Error::InvalidReset(reset));
+        }
+
+        // SAFETY: the reset line belongs to ISPCRG.
+        unsafe { update_reset(pac::Ispcrg::ptr() as *mut u32, reset, assert) }
+    }
+
     /// Releases ownership of [`ISPCRG`](pac::Ispcrg) clock configuration peripherals, conuming the [ClockIspcrg].
     ///
     /// Gives ownership of the peripheral back to the caller, before calling [ClockIspcrg] destructor.
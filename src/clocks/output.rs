@@ -0,0 +1,125 @@
+use super::*;
+use crate::gpio::{self, AonGpio, AonGpoFunction, AonPad, Disabled, Nop};
+
+/// Clock signals that can be output on an AON GPIO pad.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClockOut {
+    /// General purpose clock output 0, divided from PLL0.
+    Gclk0,
+    /// General purpose clock output 1, divided from PLL1.
+    Gclk1,
+    /// General purpose clock output 2, divided from PLL2.
+    Gclk2,
+    /// AON RTC 32 kHz clock.
+    Clk32k,
+}
+
+impl ClockOut {
+    /// Gets the [ClockId] driving the clock output.
+    pub const fn clock(self) -> ClockId {
+        match self {
+            Self::Gclk0 => ClockId::Gclk0,
+            Self::Gclk1 => ClockId::Gclk1,
+            Self::Gclk2 => ClockId::Gclk2,
+            Self::Clk32k => ClockId::AonRtc32k,
+        }
+    }
+
+    /// Gets the [AonGpoFunction] index of the clock output signal.
+    pub const fn function(self) -> u8 {
+        match self {
+            Self::Gclk0 => AonGpoFunction::U0_SYS_CRG_CLK_GCLK0,
+            Self::Gclk1 => AonGpoFunction::U0_SYS_CRG_CLK_GCLK1,
+            Self::Gclk2 => AonGpoFunction::U0_SYS_CRG_CLK_GCLK2,
+            Self::Clk32k => AonGpoFunction::U0_AON_CRG_CLK_32K_OUT,
+        }
+    }
+}
+
+/// Represents a clock signal routed to an AON GPIO pad, e.g. to probe clock rates with a scope,
+/// or to feed an external codec/PHY.
+///
+/// Owns the [AonGpio] pin while the clock is routed to it, see [release](Self::release).
+pub struct ClockOutput<'g> {
+    out: ClockOut,
+    pin: AonGpio<'g, Disabled, Nop, Nop>,
+    hz: u64,
+}
+
+impl<'g> ClockOutput<'g> {
+    /// Creates a new [ClockOutput].
+    ///
+    /// Programs the clock as close as possible to `target_hz`, enables it, and routes it to `pin`.
+    ///
+    /// `plls` provides the PLL output rates.
+    ///
+    /// Returns the error and the unmodified `pin` if the clock rate cannot be programmed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{clocks, gpio, pac, pll};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut clocks = clocks::Clocks::new(dp.aoncrg, dp.ispcrg, dp.stgcrg, dp.syscrg, dp.voutcrg);
+    /// let pll = pll::Pll::new(dp.sys_syscon);
+    /// let rgpio0 = gpio::get_aon_gpio(&dp.aon_pinctrl, gpio::AonPad::Rgpio0);
+    ///
+    /// let mclk = clocks::ClockOutput::new(
+    ///     &mut clocks,
+    ///     clocks::ClockOut::Gclk0,
+    ///     rgpio0,
+    ///     12_288_000,
+    ///     pll.rates(),
+    /// )
+    /// .map_err(|(err, _rgpio0)| err)
+    /// .unwrap();
+    ///
+    /// let _mclk_hz = mclk.hz();
+    /// let _rgpio0 = mclk.release(&mut clocks);
+    /// ```
+    pub fn new<E, D, M>(
+        clocks: &mut Clocks,
+        out: ClockOut,
+        pin: AonGpio<'g, E, D, M>,
+        target_hz: u64,
+        plls: PllRates,
+    ) -> core::result::Result<Self, (Error, AonGpio<'g, E, D, M>)> {
+        let id = out.clock();
+        let hz = match clocks.set_rate(id, target_hz, plls) {
+            Ok(hz) => hz,
+            Err(err) => return Err((err, pin)),
+        };
+
+        clocks.set_clock_gate(id, true);
+
+        let pin = pin.into_disabled();
+        gpio::set_aon_output_function(pin.pad(), out.function());
+
+        Ok(Self { out, pin, hz })
+    }
+
+    /// Gets the [ClockOut] signal.
+    pub const fn out(&self) -> ClockOut {
+        self.out
+    }
+
+    /// Gets the [AonPad] the clock is routed to.
+    pub const fn pad(&self) -> AonPad {
+        self.pin.pad()
+    }
+
+    /// Gets the achieved clock rate (in Hertz).
+    pub const fn hz(&self) -> u64 {
+        self.hz
+    }
+
+    /// Releases the [AonGpio] pin, disabling the pad output and the clock.
+    pub fn release(self, clocks: &mut Clocks) -> AonGpio<'g, Disabled, Nop, Nop> {
+        gpio::disable_aon_output(self.pin.pad());
+        clocks.set_clock_gate(self.out.clock(), false);
+
+        self.pin
+    }
+}
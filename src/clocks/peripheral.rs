@@ -0,0 +1,298 @@
+use super::{ClockId, Reset};
+
+/// Represents a SoC peripheral block with clock gates and reset lines in the CRGs.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Peripheral {
+    Uart0,
+    Uart1,
+    Uart2,
+    Uart3,
+    Uart4,
+    Uart5,
+    I2c0,
+    I2c1,
+    I2c2,
+    I2c3,
+    I2c4,
+    I2c5,
+    I2c6,
+    Spi0,
+    Spi1,
+    Spi2,
+    Spi3,
+    Spi4,
+    Spi5,
+    Spi6,
+    Pwm,
+    Mmc0,
+    Mmc1,
+    Qspi,
+    Can0,
+    Can1,
+    Gmac0,
+    Gmac1,
+    Usb,
+    Pcie0,
+    Pcie1,
+    Timer,
+    Wdt,
+    Temp,
+    Dma,
+    /// ISP clock domain: parent clocks and resets in SYSCRG.
+    IspTop,
+    /// VOUT clock domain: parent clocks and resets in SYSCRG.
+    VoutTop,
+    /// DC8200 display controller.
+    Display,
+    /// HDMI transmitter.
+    Hdmi,
+    /// MIPI DSI transmitter and D-PHY.
+    Dsi,
+    /// MIPI CSI receiver D-PHY.
+    Csi,
+    /// Video input (camera) interface.
+    Vin,
+    /// Image signal processor.
+    Isp,
+}
+
+impl Peripheral {
+    /// Gets the clocks feeding the peripheral, in enable order.
+    ///
+    /// Clocks without a gate are listed for completeness, and are skipped when enabling/disabling.
+    pub const fn clocks(self) -> &'static [ClockId] {
+        match self {
+            Self::Uart0 => &[ClockId::Uart0Apb, ClockId::Uart0Core],
+            Self::Uart1 => &[ClockId::Uart1Apb, ClockId::Uart1Core],
+            Self::Uart2 => &[ClockId::Uart2Apb, ClockId::Uart2Core],
+            Self::Uart3 => &[ClockId::Uart3Apb, ClockId::Uart3Core],
+            Self::Uart4 => &[ClockId::Uart4Apb, ClockId::Uart4Core],
+            Self::Uart5 => &[ClockId::Uart5Apb, ClockId::Uart5Core],
+            Self::I2c0 => &[ClockId::I2c0Apb],
+            Self::I2c1 => &[ClockId::I2c1Apb],
+            Self::I2c2 => &[ClockId::I2c2Apb],
+            Self::I2c3 => &[ClockId::I2c3Apb],
+            Self::I2c4 => &[ClockId::I2c4Apb],
+            Self::I2c5 => &[ClockId::I2c5Apb],
+            Self::I2c6 => &[ClockId::I2c6Apb],
+            Self::Spi0 => &[ClockId::Spi0Apb],
+            Self::Spi1 => &[ClockId::Spi1Apb],
+            Self::Spi2 => &[ClockId::Spi2Apb],
+            Self::Spi3 => &[ClockId::Spi3Apb],
+            Self::Spi4 => &[ClockId::Spi4Apb],
+            Self::Spi5 => &[ClockId::Spi5Apb],
+            Self::Spi6 => &[ClockId::Spi6Apb],
+            Self::Pwm => &[ClockId::PwmApb],
+            Self::Mmc0 => &[ClockId::Sdio0Ahb, ClockId::Sdio0Sdcard],
+            Self::Mmc1 => &[ClockId::Sdio1Ahb, ClockId::Sdio1Sdcard],
+            Self::Qspi => &[ClockId::QspiAhb, ClockId::QspiApb, ClockId::QspiRef],
+            Self::Can0 => &[ClockId::Can0Apb, ClockId::Can0Timer, ClockId::Can0Can],
+            Self::Can1 => &[ClockId::Can1Apb, ClockId::Can1Timer, ClockId::Can1Can],
+            Self::Gmac0 => &[
+                ClockId::AonGmac0Ahb,
+                ClockId::AonGmac0Axi,
+                ClockId::Gmac0Gtxclk,
+                ClockId::Gmac0Ptp,
+            ],
+            Self::Gmac1 => &[ClockId::Gmac1Ahb, ClockId::Gmac1Axi, ClockId::Gmac1Ptp],
+            Self::Usb => &[
+                ClockId::StgUsb0Apb,
+                ClockId::StgUsb0UtmiApb,
+                ClockId::StgUsb0Axi,
+                ClockId::StgUsb0Lpm,
+                ClockId::StgUsb0Stb,
+                ClockId::StgUsb0App125,
+            ],
+            Self::Pcie0 => &[
+                ClockId::StgPcie0Apb,
+                ClockId::StgPcie0AxiMst0,
+                ClockId::StgPcie0Tl,
+            ],
+            Self::Pcie1 => &[
+                ClockId::StgPcie1Apb,
+                ClockId::StgPcie1AxiMst0,
+                ClockId::StgPcie1Tl,
+            ],
+            Self::Timer => &[
+                ClockId::TimerApb,
+                ClockId::Timer0,
+                ClockId::Timer1,
+                ClockId::Timer2,
+                ClockId::Timer3,
+            ],
+            Self::Wdt => &[ClockId::WdtApb, ClockId::WdtCore],
+            Self::Temp => &[ClockId::TempApb, ClockId::TempCore],
+            Self::Dma => &[ClockId::StgDma1pAxi, ClockId::StgDma1pAhb],
+            Self::IspTop => &[
+                ClockId::IspTopCore,
+                ClockId::IspTopAxi,
+                ClockId::NocBusIspAxi,
+            ],
+            Self::VoutTop => &[
+                ClockId::VoutSrc,
+                ClockId::VoutTopAhb,
+                ClockId::VoutTopAxi,
+                ClockId::NocBusDispAxi,
+                ClockId::VoutTopHdmitx0Mclk,
+            ],
+            Self::Display => &[
+                ClockId::VoutDc8200Axi,
+                ClockId::VoutDc8200Core,
+                ClockId::VoutDc8200Ahb,
+                ClockId::VoutDc8200Pix0,
+                ClockId::VoutDc8200Pix1,
+                ClockId::VoutDomVoutTopLcd,
+            ],
+            Self::Hdmi => &[
+                ClockId::VoutHdmiTxSys,
+                ClockId::VoutHdmiTxMclk,
+                ClockId::VoutHdmiTxBclk,
+            ],
+            Self::Dsi => &[
+                ClockId::VoutDsitxApb,
+                ClockId::VoutDsitxSys,
+                ClockId::VoutDsitxDpi,
+                ClockId::VoutDsitxTxesc,
+                ClockId::VoutMipitxDphyTxesc,
+            ],
+            Self::Csi => &[
+                ClockId::IspM31dphyCfgIn,
+                ClockId::IspM31dphyRefIn,
+                ClockId::IspM31dphyTxEscLan0,
+            ],
+            Self::Vin => &[
+                ClockId::IspVinApb,
+                ClockId::IspVinSys,
+                ClockId::IspVinPixelIf0,
+                ClockId::IspVinPixelIf1,
+                ClockId::IspVinPixelIf2,
+                ClockId::IspVinPixelIf3,
+                ClockId::IspVinPAxiWr,
+            ],
+            Self::Isp => &[ClockId::IspV2TopWrapperC],
+        }
+    }
+
+    /// Gets the reset lines of the peripheral, in deassert order.
+    pub const fn resets(self) -> &'static [Reset] {
+        match self {
+            Self::Uart0 => &[Reset::SYS_UART0_APB, Reset::SYS_UART0_CORE],
+            Self::Uart1 => &[Reset::SYS_UART1_APB, Reset::SYS_UART1_CORE],
+            Self::Uart2 => &[Reset::SYS_UART2_APB, Reset::SYS_UART2_CORE],
+            Self::Uart3 => &[Reset::SYS_UART3_APB, Reset::SYS_UART3_CORE],
+            Self::Uart4 => &[Reset::SYS_UART4_APB, Reset::SYS_UART4_CORE],
+            Self::Uart5 => &[Reset::SYS_UART5_APB, Reset::SYS_UART5_CORE],
+            Self::I2c0 => &[Reset::SYS_I2C0_APB],
+            Self::I2c1 => &[Reset::SYS_I2C1_APB],
+            Self::I2c2 => &[Reset::SYS_I2C2_APB],
+            Self::I2c3 => &[Reset::SYS_I2C3_APB],
+            Self::I2c4 => &[Reset::SYS_I2C4_APB],
+            Self::I2c5 => &[Reset::SYS_I2C5_APB],
+            Self::I2c6 => &[Reset::SYS_I2C6_APB],
+            Self::Spi0 => &[Reset::SYS_SPI0_APB],
+            Self::Spi1 => &[Reset::SYS_SPI1_APB],
+            Self::Spi2 => &[Reset::SYS_SPI2_APB],
+            Self::Spi3 => &[Reset::SYS_SPI3_APB],
+            Self::Spi4 => &[Reset::SYS_SPI4_APB],
+            Self::Spi5 => &[Reset::SYS_SPI5_APB],
+            Self::Spi6 => &[Reset::SYS_SPI6_APB],
+            Self::Pwm => &[Reset::SYS_PWM_APB],
+            Self::Mmc0 => &[Reset::SYS_SDIO0_AHB],
+            Self::Mmc1 => &[Reset::SYS_SDIO1_AHB],
+            Self::Qspi => &[
+                Reset::SYS_QSPI_AHB,
+                Reset::SYS_QSPI_APB,
+                Reset::SYS_QSPI_REF,
+            ],
+            Self::Can0 => &[
+                Reset::SYS_CAN0_APB,
+                Reset::SYS_CAN0_CORE,
+                Reset::SYS_CAN0_TIMER,
+            ],
+            Self::Can1 => &[
+                Reset::SYS_CAN1_APB,
+                Reset::SYS_CAN1_CORE,
+                Reset::SYS_CAN1_TIMER,
+            ],
+            Self::Gmac0 => &[Reset::AON_GMAC0_AXI, Reset::AON_GMAC0_AHB],
+            Self::Gmac1 => &[Reset::SYS_GMAC1_AXI, Reset::SYS_GMAC1_AHB],
+            Self::Usb => &[
+                Reset::STG_USB0_APB,
+                Reset::STG_USB0_UTMI_APB,
+                Reset::STG_USB0_AXI,
+                Reset::STG_USB0_PWRUP,
+            ],
+            Self::Pcie0 => &[
+                Reset::STG_PCIE0_APB,
+                Reset::STG_PCIE0_AXI_MST0,
+                Reset::STG_PCIE0_AXI_SLV0,
+                Reset::STG_PCIE0_AXI_SLV,
+                Reset::STG_PCIE0_BRG,
+                Reset::STG_PCIE0_CORE,
+            ],
+            Self::Pcie1 => &[
+                Reset::STG_PCIE1_APB,
+                Reset::STG_PCIE1_AXI_MST0,
+                Reset::STG_PCIE1_AXI_SLV0,
+                Reset::STG_PCIE1_AXI_SLV,
+                Reset::STG_PCIE1_BRG,
+                Reset::STG_PCIE1_CORE,
+            ],
+            Self::Timer => &[
+                Reset::SYS_TIMER_APB,
+                Reset::SYS_TIMER0,
+                Reset::SYS_TIMER1,
+                Reset::SYS_TIMER2,
+                Reset::SYS_TIMER3,
+            ],
+            Self::Wdt => &[Reset::SYS_WDT_APB, Reset::SYS_WDT_CORE],
+            Self::Temp => &[Reset::SYS_TEMP_APB, Reset::SYS_TEMP_CORE],
+            Self::Dma => &[Reset::STG_DMA1P_AXI, Reset::STG_DMA1P_AHB],
+            Self::IspTop => &[
+                Reset::SYS_ISP_TOP,
+                Reset::SYS_ISP_TOP_AXI,
+                Reset::SYS_NOC_BUS_ISP_AXI,
+            ],
+            Self::VoutTop => &[Reset::SYS_VOUT_TOP_SRC, Reset::SYS_NOC_BUS_DISP_AXI],
+            Self::Display => &[
+                Reset::VOUT_DC8200_AXI,
+                Reset::VOUT_DC8200_AHB,
+                Reset::VOUT_DC8200_CORE,
+            ],
+            Self::Hdmi => &[Reset::VOUT_HDMI_TX_HDMI],
+            Self::Dsi => &[
+                Reset::VOUT_DSITX_APB,
+                Reset::VOUT_DSITX_SYS,
+                Reset::VOUT_DSITX_DPI,
+                Reset::VOUT_DSITX_TXESC,
+                Reset::VOUT_DSITX_RXESC,
+                Reset::VOUT_DSITX_TXBYTEHS,
+                Reset::VOUT_MIPITX_DPHY_SYS,
+                Reset::VOUT_MIPITX_DPHY_TXBYTEHS,
+            ],
+            Self::Csi => &[Reset::ISP_M31DPHY_HW, Reset::ISP_M31DPHY_B09_AON],
+            Self::Vin => &[
+                Reset::ISP_VIN_APB,
+                Reset::ISP_VIN_SYS,
+                Reset::ISP_VIN_PIXEL_IF0,
+                Reset::ISP_VIN_PIXEL_IF1,
+                Reset::ISP_VIN_PIXEL_IF2,
+                Reset::ISP_VIN_PIXEL_IF3,
+                Reset::ISP_VIN_P_AXI_RD,
+                Reset::ISP_VIN_P_AXI_WR,
+            ],
+            Self::Isp => &[Reset::ISP_V2_TOP_WRAPPER_P, Reset::ISP_V2_TOP_WRAPPER_C],
+        }
+    }
+
+    /// Gets the clock domain that must be running before accessing the peripheral clocks and
+    /// resets, `None` for peripherals in the always-running CRGs.
+    pub const fn domain(self) -> Option<Self> {
+        match self {
+            Self::Display | Self::Hdmi | Self::Dsi => Some(Self::VoutTop),
+            Self::Csi | Self::Vin | Self::Isp => Some(Self::IspTop),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,534 @@
+use core::fmt;
+
+use super::*;
+use crate::pll::Freq;
+
+/// Represents the output frequencies (in Hertz) of the SoC PLLs.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PllRates {
+    pub pll0_hz: u64,
+    pub pll1_hz: u64,
+    pub pll2_hz: u64,
+}
+
+impl PllRates {
+    /// Creates a new [PllRates] from the provided PLL output frequencies (in Hertz).
+    pub const fn new(pll0_hz: u64, pll1_hz: u64, pll2_hz: u64) -> Self {
+        Self {
+            pll0_hz,
+            pll1_hz,
+            pll2_hz,
+        }
+    }
+
+    /// Creates a new [PllRates] from the [Freq] settings programmed into each PLL.
+    pub const fn from_freqs(pll0: &Freq, pll1: &Freq, pll2: &Freq) -> Self {
+        Self::new(
+            pll0.output_hz(OSC_HZ),
+            pll1.output_hz(OSC_HZ),
+            pll2.output_hz(OSC_HZ),
+        )
+    }
+}
+
+/// Mux and divider settings of a clock, selected to approach a target rate.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RateConfig {
+    mux_sel: usize,
+    div: u32,
+    hz: u64,
+}
+
+impl RateConfig {
+    /// Creates a new [RateConfig] from the provided parameters.
+    pub const fn new(mux_sel: usize, div: u32, hz: u64) -> Self {
+        Self { mux_sel, div, hz }
+    }
+
+    /// Gets the index of the selected parent, only meaningful for multiplexed clocks.
+    pub const fn mux_sel(&self) -> usize {
+        self.mux_sel
+    }
+
+    /// Gets the divider value, only meaningful for clocks with a divider.
+    ///
+    /// For fractional dividers, the value is scaled by 100.
+    pub const fn div(&self) -> u32 {
+        self.div
+    }
+
+    /// Gets the achieved clock rate (in Hertz).
+    pub const fn hz(&self) -> u64 {
+        self.hz
+    }
+}
+
+/// Frozen snapshot of the clock tree, with computed rates for every [ClockId].
+///
+/// Created by [`Clocks::freeze`], rates do not follow later changes to the clock registers.
+///
+/// Rates are computed through muxes and dividers, ignoring clock gates: use [ClockRates::is_enabled]
+/// to check whether a gated clock is running.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ClockRates {
+    plls: PllRates,
+    regs: [u32; ClockId::COUNT],
+}
+
+impl ClockRates {
+    /// Creates a new [ClockRates] from PLL rates, and raw clock register values indexed by [ClockId].
+    pub const fn from_raw(plls: PllRates, regs: [u32; ClockId::COUNT]) -> Self {
+        Self { plls, regs }
+    }
+
+    /// Gets the [PllRates] used to compute the clock tree.
+    pub const fn plls(&self) -> PllRates {
+        self.plls
+    }
+
+    /// Gets the raw register value for a clock.
+    pub const fn raw(&self, id: ClockId) -> u32 {
+        self.regs[id as usize]
+    }
+
+    /// Gets whether the clock gate is enabled.
+    ///
+    /// Clocks without a gate are always enabled.
+    pub const fn is_enabled(&self, id: ClockId) -> bool {
+        !id.kind().has_gate() || self.raw(id) & CLK_ICG_MASK != 0
+    }
+
+    /// Gets the index of the selected parent for multiplexed clocks.
+    pub const fn mux_sel(&self, id: ClockId) -> usize {
+        ((self.raw(id) & CLK_MUX_MASK) >> CLK_MUX_SHIFT) as usize
+    }
+
+    /// Gets the divider value for clocks with a divider.
+    ///
+    /// For fractional dividers, the value is scaled by 100.
+    pub const fn divider(&self, id: ClockId) -> u32 {
+        id.div_from_bits(self.raw(id))
+    }
+
+    /// Gets the currently selected parent of the clock.
+    pub fn parent(&self, id: ClockId) -> Option<ClockParent> {
+        let kind = id.kind();
+
+        kind.parent()
+            .or_else(|| kind.parents().get(self.mux_sel(id)).copied())
+    }
+
+    /// Gets the rate (in Hertz) of a clock input.
+    ///
+    /// External inputs are not tracked, and report zero.
+    pub fn parent_hz(&self, parent: ClockParent) -> u64 {
+        match parent {
+            ClockParent::Osc => OSC_HZ,
+            ClockParent::Rtc => RTC_OSC_HZ,
+            ClockParent::Pll0 => self.plls.pll0_hz,
+            ClockParent::Pll1 => self.plls.pll1_hz,
+            ClockParent::Pll2 => self.plls.pll2_hz,
+            ClockParent::Clk(id) => self.rate(id),
+            ClockParent::Ext => 0,
+        }
+    }
+
+    /// Gets the rate (in Hertz) of a clock.
+    ///
+    /// Returns zero for a divider programmed to zero, or a mux selecting an unknown parent.
+    pub fn rate(&self, id: ClockId) -> u64 {
+        let parent_hz = self.parent(id).map(|p| self.parent_hz(p)).unwrap_or(0);
+
+        match id.kind() {
+            ClockKind::FracDiv(_) => match self.divider(id) {
+                // divisor below 1.0 is invalid
+                div if div >= 100 => parent_hz * 100 / div as u64,
+                _ => 0,
+            },
+            kind if kind.has_div() => match self.divider(id) {
+                0 => 0,
+                div => parent_hz / div as u64,
+            },
+            _ => parent_hz,
+        }
+    }
+
+    /// Finds the mux and divider settings giving the rate closest to `target_hz`.
+    ///
+    /// Only the mux and divider of the clock itself are considered, parent rates are left unchanged.
+    /// On a tie, the first parent in mux order wins.
+    pub fn round_rate(&self, id: ClockId, target_hz: u64) -> Result<RateConfig> {
+        let kind = id.kind();
+
+        if target_hz == 0 || !(kind.has_mux() || kind.has_div()) {
+            return Err(Error::InvalidRate(id, target_hz));
+        }
+
+        // fractional dividers are scaled by 100
+        let scale = match kind {
+            ClockKind::FracDiv(_) => 100,
+            _ => 1,
+        };
+
+        let config = |(mux_sel, parent): (usize, ClockParent)| {
+            let parent_hz = self.parent_hz(parent) * scale;
+
+            if parent_hz == 0 {
+                None
+            } else if kind.has_div() {
+                let div = ((parent_hz + target_hz / 2) / target_hz)
+                    .clamp(id.min_div() as u64, id.max_div() as u64);
+
+                Some(RateConfig::new(mux_sel, div as u32, parent_hz / div))
+            } else {
+                Some(RateConfig::new(mux_sel, 1, parent_hz))
+            }
+        };
+
+        kind.parent()
+            .map(|p| (0, p))
+            .into_iter()
+            .chain(kind.parents().iter().copied().enumerate())
+            .filter_map(config)
+            .min_by_key(|c| c.hz().abs_diff(target_hz))
+            .ok_or(Error::InvalidRate(id, target_hz))
+    }
+
+    /// Writes a report of the SYSCRG, AONCRG and STGCRG clocks to `w`, one clock per line.
+    ///
+    /// Each line lists the CRG, register index, clock name, selected parent, mux selector,
+    /// divider, gate state and computed rate.
+    pub fn dump(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        ClockId::ALL
+            .iter()
+            .filter(|id| matches!(id.crg(), Crg::Sys | Crg::Aon | Crg::Stg))
+            .try_for_each(|&id| self.dump_clock(w, id))
+    }
+
+    // Writes the report line of a single clock.
+    fn dump_clock(&self, w: &mut impl fmt::Write, id: ClockId) -> fmt::Result {
+        let kind = id.kind();
+        let parent = self.parent(id).map_or("-", |p| p.name());
+
+        write!(
+            w,
+            "{:<4} {:>3} {:<20} <- {:<16}",
+            id.crg().name(),
+            id.index(),
+            id.name(),
+            parent
+        )?;
+
+        if kind.has_mux() {
+            write!(w, " mux {:<2}", self.mux_sel(id))?;
+        } else {
+            write!(w, "{:7}", "")?;
+        }
+
+        match kind {
+            ClockKind::FracDiv(_) => {
+                let div = self.divider(id);
+                write!(w, " div {:>3}.{:02}", div / 100, div % 100)?;
+            }
+            _ if kind.has_div() => write!(w, " div {:>6}", self.divider(id))?,
+            _ => write!(w, "{:11}", "")?,
+        }
+
+        if kind.has_gate() {
+            write!(w, " {:<3}", if self.is_enabled(id) { "on" } else { "off" })?;
+        } else {
+            write!(w, "{:4}", "")?;
+        }
+
+        writeln!(w, " {} Hz", self.rate(id))
+    }
+
+    /// Gets the oscillator rate (in Hertz).
+    pub const fn osc_hz(&self) -> u64 {
+        OSC_HZ
+    }
+
+    /// Gets the U74 CPU core rate (in Hertz), also the `mcycle` counter rate.
+    pub fn cpu_hz(&self) -> u64 {
+        self.rate(ClockId::CpuCore)
+    }
+
+    /// Gets the CPU bus rate (in Hertz).
+    pub fn cpu_bus_hz(&self) -> u64 {
+        self.rate(ClockId::CpuBus)
+    }
+
+    /// Gets the bus root rate (in Hertz).
+    pub fn bus_root_hz(&self) -> u64 {
+        self.rate(ClockId::BusRoot)
+    }
+
+    /// Gets the peripheral root rate (in Hertz).
+    pub fn perh_root_hz(&self) -> u64 {
+        self.rate(ClockId::PerhRoot)
+    }
+
+    /// Gets the AXI CFG0 bus rate (in Hertz).
+    pub fn axi_cfg0_hz(&self) -> u64 {
+        self.rate(ClockId::AxiCfg0)
+    }
+
+    /// Gets the STG AXI/AHB bus rate (in Hertz).
+    pub fn stg_axiahb_hz(&self) -> u64 {
+        self.rate(ClockId::StgAxiAhb)
+    }
+
+    /// Gets the AHB0 bus rate (in Hertz).
+    pub fn ahb0_hz(&self) -> u64 {
+        self.rate(ClockId::Ahb0)
+    }
+
+    /// Gets the AHB1 bus rate (in Hertz).
+    pub fn ahb1_hz(&self) -> u64 {
+        self.rate(ClockId::Ahb1)
+    }
+
+    /// Gets the APB bus rate (in Hertz).
+    pub fn apb_bus_hz(&self) -> u64 {
+        self.rate(ClockId::ApbBus)
+    }
+
+    /// Gets the APB0 bus rate (in Hertz).
+    pub fn apb0_hz(&self) -> u64 {
+        self.rate(ClockId::Apb0)
+    }
+
+    /// Gets the DDR bus rate (in Hertz).
+    pub fn ddr_bus_hz(&self) -> u64 {
+        self.rate(ClockId::DdrBus)
+    }
+
+    /// Gets the QSPI reference rate (in Hertz).
+    pub fn qspi_ref_hz(&self) -> u64 {
+        self.rate(ClockId::QspiRef)
+    }
+
+    /// Gets the AON APB bus rate (in Hertz).
+    pub fn aon_apb_hz(&self) -> u64 {
+        self.rate(ClockId::AonApbFunc)
+    }
+
+    /// Gets the core (baud) clock rate (in Hertz) of the UART instance `n`.
+    pub fn uart_core_hz(&self, n: usize) -> Result<u64> {
+        ClockId::uart_core(n).map(|id| self.rate(id))
+    }
+
+    /// Gets the APB clock rate (in Hertz) of the UART instance `n`.
+    pub fn uart_apb_hz(&self, n: usize) -> Result<u64> {
+        ClockId::uart_apb(n).map(|id| self.rate(id))
+    }
+
+    /// Gets the APB (reference) clock rate (in Hertz) of the I2C instance `n`.
+    pub fn i2c_apb_hz(&self, n: usize) -> Result<u64> {
+        ClockId::i2c_apb(n).map(|id| self.rate(id))
+    }
+
+    /// Gets the APB (reference) clock rate (in Hertz) of the SPI instance `n`.
+    pub fn spi_apb_hz(&self, n: usize) -> Result<u64> {
+        ClockId::spi_apb(n).map(|id| self.rate(id))
+    }
+
+    /// Gets the card clock rate (in Hertz) of the SDIO instance `n`.
+    pub fn sdio_hz(&self, n: usize) -> Result<u64> {
+        ClockId::sdio_sdcard(n).map(|id| self.rate(id))
+    }
+
+    /// Gets the core clock rate (in Hertz) of the CAN instance `n`.
+    pub fn can_hz(&self, n: usize) -> Result<u64> {
+        ClockId::can_core(n).map(|id| self.rate(id))
+    }
+
+    /// Gets the rate (in Hertz) of the general purpose clock output `n`.
+    pub fn gclk_hz(&self, n: usize) -> Result<u64> {
+        ClockId::gclk(n).map(|id| self.rate(id))
+    }
+
+    /// Gets the PWM APB rate (in Hertz), also the PWM counter rate.
+    pub fn pwm_apb_hz(&self) -> u64 {
+        self.rate(ClockId::PwmApb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLL0_HZ: u64 = 1_500_000_000;
+    const PLL1_HZ: u64 = 1_066_000_000;
+    const PLL2_HZ: u64 = 1_188_000_000;
+
+    fn reg(mux: u32, div: u32, enable: bool) -> u32 {
+        ((enable as u32) << 31) | (mux << CLK_MUX_SHIFT) | div
+    }
+
+    // Register values as left by the VisionFive 2 firmware before booting Linux.
+    fn vf2_rates() -> ClockRates {
+        let mut regs = [reg(0, 1, true); ClockId::COUNT];
+        let mut set = |id: ClockId, val: u32| regs[id as usize] = val;
+
+        set(ClockId::CpuRoot, reg(1, 0, false));
+        set(ClockId::CpuBus, reg(0, 2, false));
+        set(ClockId::PerhRoot, reg(0, 2, false));
+        set(ClockId::BusRoot, reg(1, 0, false));
+        set(ClockId::NocStgBus, reg(0, 3, false));
+        set(ClockId::AxiCfg0, reg(0, 3, false));
+        set(ClockId::StgAxiAhb, reg(0, 2, false));
+        set(ClockId::ApbBus, reg(0, 4, false));
+        set(ClockId::Pll1Div2, reg(0, 2, false));
+        set(ClockId::Pll1Div4, reg(0, 2, false));
+        set(ClockId::Pll1Div8, reg(0, 2, false));
+        set(ClockId::OscDiv2, reg(0, 2, false));
+        set(ClockId::DdrBus, reg(1, 0, false));
+        set(ClockId::QspiRefSrc, reg(0, 10, false));
+        set(ClockId::QspiRef, reg(1, 0, true));
+        set(ClockId::Uart3Core, reg(0, (8 << CLK_FRAC_SHIFT) | 6, true));
+        set(ClockId::I2c5Apb, reg(0, 0, false));
+        set(ClockId::AonOscDiv4, reg(0, 4, false));
+        set(ClockId::AonApbFunc, reg(0, 0, false));
+
+        ClockRates::from_raw(PllRates::new(PLL0_HZ, PLL1_HZ, PLL2_HZ), regs)
+    }
+
+    #[test]
+    fn test_roots() {
+        let rates = vf2_rates();
+
+        assert_eq!(rates.cpu_hz(), PLL0_HZ);
+        assert_eq!(rates.cpu_bus_hz(), PLL0_HZ / 2);
+        assert_eq!(rates.bus_root_hz(), PLL2_HZ);
+        assert_eq!(rates.perh_root_hz(), PLL0_HZ / 2);
+        assert_eq!(rates.axi_cfg0_hz(), PLL2_HZ / 3);
+        assert_eq!(rates.stg_axiahb_hz(), PLL2_HZ / 3 / 2);
+        assert_eq!(rates.apb_bus_hz(), 49_500_000);
+        assert_eq!(rates.apb0_hz(), 49_500_000);
+        assert_eq!(rates.ddr_bus_hz(), PLL1_HZ / 2);
+        assert_eq!(rates.qspi_ref_hz(), PLL0_HZ / 10);
+        assert_eq!(rates.aon_apb_hz(), OSC_HZ / 4);
+    }
+
+    #[test]
+    fn test_peripherals() {
+        let rates = vf2_rates();
+
+        (0..3).for_each(|n| assert_eq!(rates.uart_core_hz(n), Ok(OSC_HZ)));
+        // 750 MHz / 6.08
+        assert_eq!(rates.uart_core_hz(3), Ok(123_355_263));
+        assert_eq!(rates.uart_core_hz(6), Err(Error::InvalidIndex(6)));
+
+        assert_eq!(rates.i2c_apb_hz(0), Ok(49_500_000));
+        assert_eq!(rates.i2c_apb_hz(7), Err(Error::InvalidIndex(7)));
+
+        assert!(rates.is_enabled(ClockId::I2c0Apb));
+        assert!(!rates.is_enabled(ClockId::I2c5Apb));
+        // clocks without a gate are always running
+        assert!(rates.is_enabled(ClockId::ApbBus));
+    }
+
+    #[test]
+    fn test_invalid_dividers() {
+        let mut regs = [0u32; ClockId::COUNT];
+        regs[ClockId::CpuRoot as usize] = reg(1, 0, false);
+        let rates = ClockRates::from_raw(PllRates::new(PLL0_HZ, 0, 0), regs);
+
+        // divider of zero stops the clock
+        assert_eq!(rates.cpu_hz(), 0);
+        // fractional divisor below 1.0 is invalid
+        assert_eq!(rates.uart_core_hz(3), Ok(0));
+    }
+
+    struct Buf {
+        data: [u8; 16384],
+        len: usize,
+    }
+
+    impl fmt::Write for Buf {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.data
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut buf = Buf {
+            data: [0; 16384],
+            len: 0,
+        };
+        vf2_rates().dump(&mut buf).unwrap();
+
+        let report = core::str::from_utf8(&buf.data[..buf.len]).unwrap();
+        let count = ClockId::ALL
+            .iter()
+            .filter(|id| matches!(id.crg(), Crg::Sys | Crg::Aon | Crg::Stg))
+            .count();
+
+        assert_eq!(report.lines().count(), count);
+        assert!(report.lines().any(|l| l.starts_with("SYS    4 PerhRoot ")
+            && l.contains("<- Pll0 ")
+            && l.contains("mux 0 ")
+            && l.contains("div      2")
+            && l.ends_with(" 750000000 Hz")));
+        assert!(report.lines().any(|l| l.starts_with("SYS  152 Uart3Core ")
+            && l.contains("div   6.08")
+            && l.ends_with(" 123355263 Hz")));
+        assert!(report
+            .lines()
+            .any(|l| l.starts_with("SYS  142 I2c4Apb ") && l.contains(" on ")));
+        assert!(report
+            .lines()
+            .any(|l| l.starts_with("SYS  143 I2c5Apb ") && l.contains(" off ")));
+    }
+
+    #[test]
+    fn test_round_rate() {
+        let rates = vf2_rates();
+
+        // PLL0 / 3 is the closest to 500 MHz
+        assert_eq!(
+            rates.round_rate(ClockId::CpuCore, 500_000_000),
+            Ok(RateConfig::new(0, 3, 500_000_000))
+        );
+        // divider is clamped to its maximum
+        assert_eq!(
+            rates.round_rate(ClockId::CpuCore, 1_000_000),
+            Ok(RateConfig::new(0, 7, PLL0_HZ / 7))
+        );
+        // PLL2 / 2 is closer to 600 MHz than PLL0 / 2
+        assert_eq!(
+            rates.round_rate(ClockId::PerhRoot, 600_000_000),
+            Ok(RateConfig::new(1, 2, PLL2_HZ / 2))
+        );
+        // mux without a divider
+        assert_eq!(
+            rates.round_rate(ClockId::QspiRef, OSC_HZ),
+            Ok(RateConfig::new(0, 1, OSC_HZ))
+        );
+        // 750 MHz / 6.51 for 115200 * 16 * 625
+        assert_eq!(
+            rates.round_rate(ClockId::Uart3Core, 115_200_000),
+            Ok(RateConfig::new(0, 651, 115_207_373))
+        );
+
+        assert_eq!(
+            rates.round_rate(ClockId::Uart0Core, OSC_HZ),
+            Err(Error::InvalidRate(ClockId::Uart0Core, OSC_HZ))
+        );
+        assert_eq!(
+            rates.round_rate(ClockId::CpuCore, 0),
+            Err(Error::InvalidRate(ClockId::CpuCore, 0))
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Software reset lines of the SoC clock-reset generators.
+//!
+//! Reset line numbers follow the Linux [`starfive,jh7110-crg`](https://github.com/torvalds/linux/blob/master/include/dt-bindings/reset/starfive%2Cjh7110-crg.h) bindings.
+
+use super::Crg;
+
+/// Time to wait (in microseconds) for the reset status to follow a reset request.
+pub const RESET_TIMEOUT_US: u32 = 1000;
+
+impl Crg {
+    /// Gets the register index of the first software reset assert register.
+    pub const fn rst_assert_index(self) -> usize {
+        match self {
+            Self::Sys => 0x2f8 >> 2,
+            Self::Aon => 0x38 >> 2,
+            Self::Stg => 0x74 >> 2,
+            Self::Isp => 0x38 >> 2,
+            Self::Vout => 0x48 >> 2,
+        }
+    }
+
+    /// Gets the register index of the first reset status register.
+    pub const fn rst_status_index(self) -> usize {
+        match self {
+            Self::Sys => 0x308 >> 2,
+            Self::Aon => 0x3c >> 2,
+            Self::Stg => 0x78 >> 2,
+            Self::Isp => 0x3c >> 2,
+            Self::Vout => 0x4c >> 2,
+        }
+    }
+}
+
+/// Represents a software reset line of a clock-reset generator (CRG).
+///
+/// Writing a one to the line asserts the reset, the matching status bit reads one once the
+/// reset is released.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Reset {
+    crg: Crg,
+    line: u8,
+}
+
+impl Reset {
+    /// Creates a new [Reset] from the provided parameters.
+    pub const fn new(crg: Crg, line: u8) -> Self {
+        Self { crg, line }
+    }
+
+    /// Gets the [Crg] containing the reset line.
+    pub const fn crg(&self) -> Crg {
+        self.crg
+    }
+
+    /// Gets the reset line number.
+    pub const fn line(&self) -> u8 {
+        self.line
+    }
+
+    /// Gets the offset of the 32-bit assert/status register containing the reset line.
+    pub const fn register(&self) -> usize {
+        self.line as usize / 32
+    }
+
+    /// Gets the bit mask of the reset line in its assert/status register.
+    pub const fn mask(&self) -> u32 {
+        1 << (self.line % 32)
+    }
+
+    pub const SYS_IOMUX_APB: Self = Self::new(Crg::Sys, 2);
+    pub const SYS_GPU_APB: Self = Self::new(Crg::Sys, 21);
+    pub const SYS_GPU_DOMA: Self = Self::new(Crg::Sys, 22);
+    pub const SYS_NOC_BUS_DISP_AXI: Self = Self::new(Crg::Sys, 26);
+    pub const SYS_NOC_BUS_GPU_AXI: Self = Self::new(Crg::Sys, 27);
+    pub const SYS_NOC_BUS_ISP_AXI: Self = Self::new(Crg::Sys, 28);
+    pub const SYS_NOC_BUS_STG_AXI: Self = Self::new(Crg::Sys, 30);
+    pub const SYS_DDR_AXI: Self = Self::new(Crg::Sys, 38);
+    pub const SYS_DDR_OSC: Self = Self::new(Crg::Sys, 39);
+    pub const SYS_DDR_APB: Self = Self::new(Crg::Sys, 40);
+    pub const SYS_ISP_TOP: Self = Self::new(Crg::Sys, 41);
+    pub const SYS_ISP_TOP_AXI: Self = Self::new(Crg::Sys, 42);
+    pub const SYS_VOUT_TOP_SRC: Self = Self::new(Crg::Sys, 43);
+    pub const SYS_QSPI_AHB: Self = Self::new(Crg::Sys, 61);
+    pub const SYS_QSPI_APB: Self = Self::new(Crg::Sys, 62);
+    pub const SYS_QSPI_REF: Self = Self::new(Crg::Sys, 63);
+    pub const SYS_SDIO0_AHB: Self = Self::new(Crg::Sys, 64);
+    pub const SYS_SDIO1_AHB: Self = Self::new(Crg::Sys, 65);
+    pub const SYS_GMAC1_AXI: Self = Self::new(Crg::Sys, 66);
+    pub const SYS_GMAC1_AHB: Self = Self::new(Crg::Sys, 67);
+    pub const SYS_SPI0_APB: Self = Self::new(Crg::Sys, 69);
+    pub const SYS_SPI1_APB: Self = Self::new(Crg::Sys, 70);
+    pub const SYS_SPI2_APB: Self = Self::new(Crg::Sys, 71);
+    pub const SYS_SPI3_APB: Self = Self::new(Crg::Sys, 72);
+    pub const SYS_SPI4_APB: Self = Self::new(Crg::Sys, 73);
+    pub const SYS_SPI5_APB: Self = Self::new(Crg::Sys, 74);
+    pub const SYS_SPI6_APB: Self = Self::new(Crg::Sys, 75);
+    pub const SYS_I2C0_APB: Self = Self::new(Crg::Sys, 76);
+    pub const SYS_I2C1_APB: Self = Self::new(Crg::Sys, 77);
+    pub const SYS_I2C2_APB: Self = Self::new(Crg::Sys, 78);
+    pub const SYS_I2C3_APB: Self = Self::new(Crg::Sys, 79);
+    pub const SYS_I2C4_APB: Self = Self::new(Crg::Sys, 80);
+    pub const SYS_I2C5_APB: Self = Self::new(Crg::Sys, 81);
+    pub const SYS_I2C6_APB: Self = Self::new(Crg::Sys, 82);
+    pub const SYS_UART0_APB: Self = Self::new(Crg::Sys, 83);
+    pub const SYS_UART0_CORE: Self = Self::new(Crg::Sys, 84);
+    pub const SYS_UART1_APB: Self = Self::new(Crg::Sys, 85);
+    pub const SYS_UART1_CORE: Self = Self::new(Crg::Sys, 86);
+    pub const SYS_UART2_APB: Self = Self::new(Crg::Sys, 87);
+    pub const SYS_UART2_CORE: Self = Self::new(Crg::Sys, 88);
+    pub const SYS_UART3_APB: Self = Self::new(Crg::Sys, 89);
+    pub const SYS_UART3_CORE: Self = Self::new(Crg::Sys, 90);
+    pub const SYS_UART4_APB: Self = Self::new(Crg::Sys, 91);
+    pub const SYS_UART4_CORE: Self = Self::new(Crg::Sys, 92);
+    pub const SYS_UART5_APB: Self = Self::new(Crg::Sys, 93);
+    pub const SYS_UART5_CORE: Self = Self::new(Crg::Sys, 94);
+    pub const SYS_PWM_APB: Self = Self::new(Crg::Sys, 108);
+    pub const SYS_WDT_APB: Self = Self::new(Crg::Sys, 109);
+    pub const SYS_WDT_CORE: Self = Self::new(Crg::Sys, 110);
+    pub const SYS_CAN0_APB: Self = Self::new(Crg::Sys, 111);
+    pub const SYS_CAN0_CORE: Self = Self::new(Crg::Sys, 112);
+    pub const SYS_CAN0_TIMER: Self = Self::new(Crg::Sys, 113);
+    pub const SYS_CAN1_APB: Self = Self::new(Crg::Sys, 114);
+    pub const SYS_CAN1_CORE: Self = Self::new(Crg::Sys, 115);
+    pub const SYS_CAN1_TIMER: Self = Self::new(Crg::Sys, 116);
+    pub const SYS_TIMER_APB: Self = Self::new(Crg::Sys, 117);
+    pub const SYS_TIMER0: Self = Self::new(Crg::Sys, 118);
+    pub const SYS_TIMER1: Self = Self::new(Crg::Sys, 119);
+    pub const SYS_TIMER2: Self = Self::new(Crg::Sys, 120);
+    pub const SYS_TIMER3: Self = Self::new(Crg::Sys, 121);
+    pub const SYS_TEMP_APB: Self = Self::new(Crg::Sys, 123);
+    pub const SYS_TEMP_CORE: Self = Self::new(Crg::Sys, 124);
+
+    pub const AON_GMAC0_AXI: Self = Self::new(Crg::Aon, 0);
+    pub const AON_GMAC0_AHB: Self = Self::new(Crg::Aon, 1);
+    pub const AON_IOMUX: Self = Self::new(Crg::Aon, 2);
+    pub const AON_PMU_APB: Self = Self::new(Crg::Aon, 3);
+    pub const AON_PMU_WKUP: Self = Self::new(Crg::Aon, 4);
+    pub const AON_RTC_APB: Self = Self::new(Crg::Aon, 5);
+    pub const AON_RTC_CAL: Self = Self::new(Crg::Aon, 6);
+    pub const AON_RTC_32K: Self = Self::new(Crg::Aon, 7);
+
+    pub const STG_SEC_AHB: Self = Self::new(Crg::Stg, 3);
+    pub const STG_DMA1P_AXI: Self = Self::new(Crg::Stg, 5);
+    pub const STG_DMA1P_AHB: Self = Self::new(Crg::Stg, 6);
+    pub const STG_USB0_AXI: Self = Self::new(Crg::Stg, 7);
+    pub const STG_USB0_APB: Self = Self::new(Crg::Stg, 8);
+    pub const STG_USB0_UTMI_APB: Self = Self::new(Crg::Stg, 9);
+    pub const STG_USB0_PWRUP: Self = Self::new(Crg::Stg, 10);
+    pub const STG_PCIE0_AXI_MST0: Self = Self::new(Crg::Stg, 11);
+    pub const STG_PCIE0_AXI_SLV0: Self = Self::new(Crg::Stg, 12);
+    pub const STG_PCIE0_AXI_SLV: Self = Self::new(Crg::Stg, 13);
+    pub const STG_PCIE0_BRG: Self = Self::new(Crg::Stg, 14);
+    pub const STG_PCIE0_CORE: Self = Self::new(Crg::Stg, 15);
+    pub const STG_PCIE0_APB: Self = Self::new(Crg::Stg, 16);
+    pub const STG_PCIE1_AXI_MST0: Self = Self::new(Crg::Stg, 17);
+    pub const STG_PCIE1_AXI_SLV0: Self = Self::new(Crg::Stg, 18);
+    pub const STG_PCIE1_AXI_SLV: Self = Self::new(Crg::Stg, 19);
+    pub const STG_PCIE1_BRG: Self = Self::new(Crg::Stg, 20);
+    pub const STG_PCIE1_CORE: Self = Self::new(Crg::Stg, 21);
+    pub const STG_PCIE1_APB: Self = Self::new(Crg::Stg, 22);
+
+    pub const ISP_V2_TOP_WRAPPER_P: Self = Self::new(Crg::Isp, 0);
+    pub const ISP_V2_TOP_WRAPPER_C: Self = Self::new(Crg::Isp, 1);
+    pub const ISP_M31DPHY_HW: Self = Self::new(Crg::Isp, 2);
+    pub const ISP_M31DPHY_B09_AON: Self = Self::new(Crg::Isp, 3);
+    pub const ISP_VIN_APB: Self = Self::new(Crg::Isp, 4);
+    pub const ISP_VIN_PIXEL_IF0: Self = Self::new(Crg::Isp, 5);
+    pub const ISP_VIN_PIXEL_IF1: Self = Self::new(Crg::Isp, 6);
+    pub const ISP_VIN_PIXEL_IF2: Self = Self::new(Crg::Isp, 7);
+    pub const ISP_VIN_PIXEL_IF3: Self = Self::new(Crg::Isp, 8);
+    pub const ISP_VIN_SYS: Self = Self::new(Crg::Isp, 9);
+    pub const ISP_VIN_P_AXI_RD: Self = Self::new(Crg::Isp, 10);
+    pub const ISP_VIN_P_AXI_WR: Self = Self::new(Crg::Isp, 11);
+
+    pub const VOUT_DC8200_AXI: Self = Self::new(Crg::Vout, 0);
+    pub const VOUT_DC8200_AHB: Self = Self::new(Crg::Vout, 1);
+    pub const VOUT_DC8200_CORE: Self = Self::new(Crg::Vout, 2);
+    pub const VOUT_DSITX_DPI: Self = Self::new(Crg::Vout, 3);
+    pub const VOUT_DSITX_APB: Self = Self::new(Crg::Vout, 4);
+    pub const VOUT_DSITX_RXESC: Self = Self::new(Crg::Vout, 5);
+    pub const VOUT_DSITX_SYS: Self = Self::new(Crg::Vout, 6);
+    pub const VOUT_DSITX_TXBYTEHS: Self = Self::new(Crg::Vout, 7);
+    pub const VOUT_DSITX_TXESC: Self = Self::new(Crg::Vout, 8);
+    pub const VOUT_HDMI_TX_HDMI: Self = Self::new(Crg::Vout, 9);
+    pub const VOUT_MIPITX_DPHY_SYS: Self = Self::new(Crg::Vout, 10);
+    pub const VOUT_MIPITX_DPHY_TXBYTEHS: Self = Self::new(Crg::Vout, 11);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_mask() {
+        // matches the `rst1.u0_ddr_osc` field used by DDR initialization
+        assert_eq!(Reset::SYS_DDR_OSC.register(), 1);
+        assert_eq!(Reset::SYS_DDR_OSC.mask(), 1 << 7);
+
+        assert_eq!(Reset::SYS_UART0_APB.register(), 2);
+        assert_eq!(Reset::SYS_UART0_APB.mask(), 1 << 19);
+
+        assert_eq!(Reset::AON_GMAC0_AXI.register(), 0);
+        assert_eq!(Reset::AON_GMAC0_AXI.mask(), 1);
+    }
+}
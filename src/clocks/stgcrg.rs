@@ -1,3 +1,4 @@
+use super::*;
 use crate::pac;
 
 /// Contains configuration registers for SoC [`STGCRG`](pac::Stgcrg) clock peripheral.
@@ -23,6 +24,30 @@ impl ClockStgcrg {
         Self { stgcrg }
     }
 
+    /// Reads the raw value of the clock register at `index`.
+    pub(crate) fn read_clk(&self, index: usize) -> u32 {
+        // SAFETY: all STGCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { read_crg(pac::Stgcrg::ptr() as *const u32, index) }
+    }
+
+    /// Modifies the raw value of the clock register at `index`.
+    pub(crate) fn modify_clk(&mut self, index: usize, f: impl FnOnce(u32) -> u32) {
+        // SAFETY: all STGCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { modify_crg(pac::Stgcrg::ptr() as *mut u32, index, f) }
+    }
+
+    /// Asserts or deasserts a STGCRG [Reset] line, waiting for the reset status to follow.
+    pub fn set_reset(&mut self, reset: Reset, assert: bool) -> Result<()> {
+        if reset.crg() != Crg::Stg {
+            return Err(Error::InvalidReset(reset));
+        }
+
+        // SAFETY: the reset line belongs to STGCRG.
+        unsafe { update_reset(pac::Stgcrg::ptr() as *mut u32, reset, assert) }
+    }
+
     /// Releases ownership of [`STGCRG`](pac::Stgcrg) clock configuration peripherals, conuming the [ClockStgcrg].
     ///
     /// Gives ownership of the peripheral back to the caller, before calling [ClockStgcrg] destructor.
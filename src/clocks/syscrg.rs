@@ -107,6 +107,30 @@ impl ClockSyscrg {
         self
     }
 
+    /// Reads the raw value of the clock register at `index`.
+    pub(crate) fn read_clk(&self, index: usize) -> u32 {
+        // SAFETY: all SYSCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { read_crg(pac::Syscrg::ptr() as *const u32, index) }
+    }
+
+    /// Modifies the raw value of the clock register at `index`.
+    pub(crate) fn modify_clk(&mut self, index: usize, f: impl FnOnce(u32) -> u32) {
+        // SAFETY: all SYSCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { modify_crg(pac::Syscrg::ptr() as *mut u32, index, f) }
+    }
+
+    /// Asserts or deasserts a SYSCRG [Reset] line, waiting for the reset status to follow.
+    pub fn set_reset(&mut self, reset: Reset, assert: bool) -> Result<()> {
+        if reset.crg() != Crg::Sys {
+            return Err(Error::InvalidReset(reset));
+        }
+
+        // SAFETY: the reset line belongs to SYSCRG.
+        unsafe { update_reset(pac::Syscrg::ptr() as *mut u32, reset, assert) }
+    }
+
     /// Releases ownership of [`SYSCRG`](pac::Syscrg) clock configuration peripherals, conuming the [ClockSyscrg].
     ///
     /// Gives ownership of the peripheral back to the caller, before calling [ClockSyscrg] destructor.
@@ -0,0 +1,790 @@
+//! Static description of the SoC clock tree.
+//!
+//! Clock indices, parents and divider layouts follow the Linux
+//! [`clk-starfive-jh7110`](https://github.com/torvalds/linux/tree/master/drivers/clk/starfive) drivers.
+
+use super::{Error, Result};
+
+use ClockKind::*;
+use ClockParent::*;
+
+/// Bit enabling the clock gate (ICG) of a clock register.
+pub const CLK_ICG_MASK: u32 = 1 << 31;
+/// Bit offset of the clock mux selector of a clock register.
+pub const CLK_MUX_SHIFT: u32 = 24;
+/// Bit mask of the clock mux selector of a clock register.
+pub const CLK_MUX_MASK: u32 = 0x3f << CLK_MUX_SHIFT;
+/// Bit mask of the integer clock divider of a clock register.
+pub const CLK_DIV_MASK: u32 = 0xff_ffff;
+/// Bit offset of the fractional part of a fractional clock divider.
+pub const CLK_FRAC_SHIFT: u32 = 8;
+/// Bit mask of the fractional part of a fractional clock divider.
+pub const CLK_FRAC_MASK: u32 = 0xff << CLK_FRAC_SHIFT;
+/// Bit mask of the integer part of a fractional clock divider.
+pub const CLK_INT_MASK: u32 = 0xff;
+/// Minimum fractional divider value, scaled by 100: `1.00`.
+pub const CLK_FRAC_MIN: u32 = 100;
+/// Maximum fractional divider value, scaled by 100: `255.99`.
+pub const CLK_FRAC_MAX: u32 = 25599;
+
+/// Represents the clock-reset generator (CRG) block containing a clock register.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Crg {
+    /// System CRG: [`SYSCRG`](crate::pac::Syscrg).
+    Sys,
+    /// Always-on CRG: [`AONCRG`](crate::pac::Aoncrg).
+    Aon,
+    /// System-top-group CRG: [`STGCRG`](crate::pac::Stgcrg).
+    Stg,
+    /// Image signal processor CRG: [`ISPCRG`](crate::pac::Ispcrg).
+    Isp,
+    /// Video output CRG: [`VOUTCRG`](crate::pac::Voutcrg).
+    Vout,
+}
+
+impl Crg {
+    /// Gets the short name of the CRG.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Sys => "SYS",
+            Self::Aon => "AON",
+            Self::Stg => "STG",
+            Self::Isp => "ISP",
+            Self::Vout => "VOUT",
+        }
+    }
+}
+
+/// Represents an input to a clock in the tree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClockParent {
+    /// 24 MHz main oscillator.
+    Osc,
+    /// 32.768 kHz RTC oscillator.
+    Rtc,
+    /// PLL0 output.
+    Pll0,
+    /// PLL1 output.
+    Pll1,
+    /// PLL2 output.
+    Pll2,
+    /// Output of another CRG clock.
+    Clk(ClockId),
+    /// External clock input (e.g. HDMI TX PHY pixel clock, DVP camera clock), rate not tracked.
+    Ext,
+}
+
+impl ClockParent {
+    /// Gets the name of the clock input.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Osc => "Osc",
+            Self::Rtc => "Rtc",
+            Self::Pll0 => "Pll0",
+            Self::Pll1 => "Pll1",
+            Self::Pll2 => "Pll2",
+            Self::Clk(id) => id.name(),
+            Self::Ext => "Ext",
+        }
+    }
+}
+
+/// Describes how a clock derives its rate from its parent(s).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClockKind {
+    /// Clock gate, passes the parent rate through.
+    Gate(ClockParent),
+    /// Integer divider.
+    Div(ClockParent),
+    /// Integer divider with a clock gate.
+    GateDiv(ClockParent),
+    /// Fractional divider, the divisor is `INT + FRAC / 100`.
+    FracDiv(ClockParent),
+    /// Clock multiplexer.
+    Mux(&'static [ClockParent]),
+    /// Clock multiplexer with a clock gate.
+    GateMux(&'static [ClockParent]),
+    /// Clock multiplexer followed by an integer divider.
+    MuxDiv(&'static [ClockParent]),
+}
+
+impl ClockKind {
+    /// Gets whether the clock has a gate (ICG) bit.
+    pub const fn has_gate(&self) -> bool {
+        matches!(self, Self::Gate(_) | Self::GateDiv(_) | Self::GateMux(_))
+    }
+
+    /// Gets whether the clock has a mux selector.
+    pub const fn has_mux(&self) -> bool {
+        matches!(self, Self::Mux(_) | Self::GateMux(_) | Self::MuxDiv(_))
+    }
+
+    /// Gets whether the clock has a divider.
+    pub const fn has_div(&self) -> bool {
+        matches!(
+            self,
+            Self::Div(_) | Self::GateDiv(_) | Self::FracDiv(_) | Self::MuxDiv(_)
+        )
+    }
+
+    /// Gets the list of selectable parents of a multiplexer, empty for single-parent clocks.
+    pub const fn parents(&self) -> &'static [ClockParent] {
+        match self {
+            Self::Mux(p) | Self::GateMux(p) | Self::MuxDiv(p) => p,
+            _ => &[],
+        }
+    }
+
+    /// Gets the bit mask of the divider field in the clock register, zero for clocks without a divider.
+    pub const fn div_mask(&self) -> u32 {
+        match self {
+            Self::FracDiv(_) => CLK_INT_MASK | CLK_FRAC_MASK,
+            kind if kind.has_div() => CLK_DIV_MASK,
+            _ => 0,
+        }
+    }
+
+    /// Gets the parent of a single-parent clock, `None` for multiplexers.
+    pub const fn parent(&self) -> Option<ClockParent> {
+        match self {
+            Self::Gate(p) | Self::Div(p) | Self::GateDiv(p) | Self::FracDiv(p) => Some(*p),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! clock_tree {
+    ($(
+        $(#[$doc:meta])+
+        $id:ident: $crg:ident[$index:literal] => $kind:expr;
+    )+) => {
+        /// Identifies a clock output of one of the SoC clock-reset generators (CRG).
+        #[repr(u8)]
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub enum ClockId {
+            $(
+                $(#[$doc])+
+                $id,
+            )+
+        }
+
+        impl ClockId {
+            /// Number of clocks described by the clock tree.
+            pub const COUNT: usize = [$(Self::$id,)+].len();
+
+            /// Every clock described by the clock tree, in [ClockId] order.
+            pub const ALL: [Self; Self::COUNT] = [$(Self::$id,)+];
+
+            /// Gets the [Crg] containing the clock register.
+            pub const fn crg(self) -> Crg {
+                match self {
+                    $(Self::$id => Crg::$crg,)+
+                }
+            }
+
+            /// Gets the register index (32-bit word offset) of the clock in its [Crg].
+            pub const fn index(self) -> usize {
+                match self {
+                    $(Self::$id => $index,)+
+                }
+            }
+
+            /// Gets the [ClockKind] describing how the clock derives its rate.
+            pub const fn kind(self) -> ClockKind {
+                match self {
+                    $(Self::$id => $kind,)+
+                }
+            }
+
+            /// Gets the clock name, as used by the TRM and the Linux driver.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$id => stringify!($id),)+
+                }
+            }
+        }
+    };
+}
+
+clock_tree! {
+    /// CPU root clock mux.
+    CpuRoot: Sys[0] => Mux(&[Osc, Pll0]);
+    /// CPU core clock divider, clocks the U74 cores.
+    CpuCore: Sys[1] => Div(Clk(ClockId::CpuRoot));
+    /// CPU bus clock divider.
+    CpuBus: Sys[2] => Div(Clk(ClockId::CpuCore));
+    /// GPU root clock mux.
+    GpuRoot: Sys[3] => Mux(&[Pll2, Pll1]);
+    /// Peripheral root clock mux and divider.
+    PerhRoot: Sys[4] => MuxDiv(&[Pll0, Pll2]);
+    /// Bus root clock mux.
+    BusRoot: Sys[5] => Mux(&[Osc, Pll2]);
+    /// NOC STG bus clock divider.
+    NocStgBus: Sys[6] => Div(Clk(ClockId::BusRoot));
+    /// AXI CFG0 bus clock divider.
+    AxiCfg0: Sys[7] => Div(Clk(ClockId::BusRoot));
+    /// STG AXI/AHB bus clock divider.
+    StgAxiAhb: Sys[8] => Div(Clk(ClockId::AxiCfg0));
+    /// AHB0 bus clock gate.
+    Ahb0: Sys[9] => Gate(Clk(ClockId::StgAxiAhb));
+    /// AHB1 bus clock gate.
+    Ahb1: Sys[10] => Gate(Clk(ClockId::StgAxiAhb));
+    /// APB bus clock divider.
+    ApbBus: Sys[11] => Div(Clk(ClockId::StgAxiAhb));
+    /// APB0 bus clock gate.
+    Apb0: Sys[12] => Gate(Clk(ClockId::ApbBus));
+    /// PLL0 divide-by-two.
+    Pll0Div2: Sys[13] => Div(Pll0);
+    /// PLL1 divide-by-two.
+    Pll1Div2: Sys[14] => Div(Pll1);
+    /// PLL2 divide-by-two.
+    Pll2Div2: Sys[15] => Div(Pll2);
+    /// Audio root clock divider.
+    AudioRoot: Sys[16] => Div(Pll2);
+    /// Internal audio master clock divider.
+    MclkInner: Sys[17] => Div(Clk(ClockId::AudioRoot));
+    /// Audio master clock output gate.
+    MclkOut: Sys[19] => Gate(Clk(ClockId::MclkInner));
+    /// ISP 2x clock mux and divider.
+    Isp2x: Sys[20] => MuxDiv(&[Pll2, Pll1]);
+    /// ISP AXI clock divider.
+    IspAxi: Sys[21] => Div(Clk(ClockId::Isp2x));
+    /// General purpose clock output 0.
+    Gclk0: Sys[22] => GateDiv(Clk(ClockId::Pll0Div2));
+    /// General purpose clock output 1.
+    Gclk1: Sys[23] => GateDiv(Clk(ClockId::Pll1Div2));
+    /// General purpose clock output 2.
+    Gclk2: Sys[24] => GateDiv(Clk(ClockId::Pll2Div2));
+    /// OSC divide-by-two.
+    OscDiv2: Sys[40] => Div(Osc);
+    /// PLL1 divide-by-four.
+    Pll1Div4: Sys[41] => Div(Clk(ClockId::Pll1Div2));
+    /// PLL1 divide-by-eight.
+    Pll1Div8: Sys[42] => Div(Clk(ClockId::Pll1Div4));
+    /// DDR bus clock mux.
+    DdrBus: Sys[43] => Mux(&[
+        Clk(ClockId::OscDiv2),
+        Clk(ClockId::Pll1Div2),
+        Clk(ClockId::Pll1Div4),
+        Clk(ClockId::Pll1Div8),
+    ]);
+    /// DDR AXI clock gate.
+    DdrAxi: Sys[44] => Gate(Clk(ClockId::DdrBus));
+    /// GPU core clock divider.
+    GpuCore: Sys[45] => Div(Clk(ClockId::GpuRoot));
+    /// ISP top core clock gate.
+    IspTopCore: Sys[51] => Gate(Clk(ClockId::Isp2x));
+    /// ISP top AXI clock gate.
+    IspTopAxi: Sys[52] => Gate(Clk(ClockId::IspAxi));
+    /// NOC ISP AXI clock gate.
+    NocBusIspAxi: Sys[53] => Gate(Clk(ClockId::IspAxi));
+    /// VOUT source clock gate, feeds the VOUTCRG pixel clock dividers.
+    VoutSrc: Sys[58] => Gate(Pll2);
+    /// VOUT AXI clock divider.
+    VoutAxi: Sys[59] => Div(Pll2);
+    /// NOC display AXI clock gate.
+    NocBusDispAxi: Sys[60] => Gate(Clk(ClockId::VoutAxi));
+    /// VOUT top AHB clock gate.
+    VoutTopAhb: Sys[61] => Gate(Clk(ClockId::Ahb1));
+    /// VOUT top AXI clock gate.
+    VoutTopAxi: Sys[62] => Gate(Clk(ClockId::VoutAxi));
+    /// VOUT top HDMI TX master clock gate, assumes the internal audio master clock.
+    VoutTopHdmitx0Mclk: Sys[63] => Gate(Clk(ClockId::MclkInner));
+    /// VOUT top MIPI PHY reference clock divider.
+    VoutTopMipiphyRef: Sys[64] => Div(Osc);
+    /// QSPI AHB clock gate.
+    QspiAhb: Sys[87] => Gate(Clk(ClockId::Ahb1));
+    /// QSPI APB clock gate.
+    QspiApb: Sys[88] => Gate(Clk(ClockId::ApbBus));
+    /// QSPI reference source clock divider.
+    QspiRefSrc: Sys[89] => Div(Pll0);
+    /// QSPI reference clock mux.
+    QspiRef: Sys[90] => GateMux(&[Osc, Clk(ClockId::QspiRefSrc)]);
+    /// SDIO0 AHB clock gate.
+    Sdio0Ahb: Sys[91] => Gate(Clk(ClockId::Ahb0));
+    /// SDIO1 AHB clock gate.
+    Sdio1Ahb: Sys[92] => Gate(Clk(ClockId::Ahb0));
+    /// SDIO0 card clock divider.
+    Sdio0Sdcard: Sys[93] => GateDiv(Clk(ClockId::AxiCfg0));
+    /// SDIO1 card clock divider.
+    Sdio1Sdcard: Sys[94] => GateDiv(Clk(ClockId::AxiCfg0));
+    /// USB 125 MHz clock divider.
+    Usb125m: Sys[95] => Div(Pll0);
+    /// NOC STG AXI clock gate.
+    NocBusStgAxi: Sys[96] => Gate(Clk(ClockId::NocStgBus));
+    /// GMAC1 AHB clock gate.
+    Gmac1Ahb: Sys[97] => Gate(Clk(ClockId::StgAxiAhb));
+    /// GMAC1 AXI clock gate.
+    Gmac1Axi: Sys[98] => Gate(Clk(ClockId::StgAxiAhb));
+    /// GMAC source clock divider.
+    GmacSrc: Sys[99] => Div(Pll0);
+    /// GMAC1 GTX clock divider.
+    Gmac1Gtxclk: Sys[100] => Div(Pll0);
+    /// GMAC1 PTP clock divider.
+    Gmac1Ptp: Sys[102] => GateDiv(Clk(ClockId::GmacSrc));
+    /// GMAC0 GTX clock divider.
+    Gmac0Gtxclk: Sys[108] => GateDiv(Pll0);
+    /// GMAC0 PTP clock divider.
+    Gmac0Ptp: Sys[109] => GateDiv(Clk(ClockId::GmacSrc));
+    /// GMAC PHY reference clock divider.
+    GmacPhy: Sys[110] => GateDiv(Clk(ClockId::GmacSrc));
+    /// IOMUX APB clock gate.
+    IomuxApb: Sys[112] => Gate(Clk(ClockId::ApbBus));
+    /// CAN0 APB clock gate.
+    Can0Apb: Sys[115] => Gate(Clk(ClockId::ApbBus));
+    /// CAN0 timer clock divider.
+    Can0Timer: Sys[116] => GateDiv(Osc);
+    /// CAN0 core clock divider.
+    Can0Can: Sys[117] => GateDiv(Clk(ClockId::PerhRoot));
+    /// CAN1 APB clock gate.
+    Can1Apb: Sys[118] => Gate(Clk(ClockId::ApbBus));
+    /// CAN1 timer clock divider.
+    Can1Timer: Sys[119] => GateDiv(Osc);
+    /// CAN1 core clock divider.
+    Can1Can: Sys[120] => GateDiv(Clk(ClockId::PerhRoot));
+    /// PWM APB clock gate.
+    PwmApb: Sys[121] => Gate(Clk(ClockId::ApbBus));
+    /// Watchdog APB clock gate.
+    WdtApb: Sys[122] => Gate(Clk(ClockId::ApbBus));
+    /// Watchdog core clock gate.
+    WdtCore: Sys[123] => Gate(Osc);
+    /// Timer APB clock gate.
+    TimerApb: Sys[124] => Gate(Clk(ClockId::ApbBus));
+    /// Timer channel 0 clock gate.
+    Timer0: Sys[125] => Gate(Osc);
+    /// Timer channel 1 clock gate.
+    Timer1: Sys[126] => Gate(Osc);
+    /// Timer channel 2 clock gate.
+    Timer2: Sys[127] => Gate(Osc);
+    /// Timer channel 3 clock gate.
+    Timer3: Sys[128] => Gate(Osc);
+    /// Temperature sensor APB clock gate.
+    TempApb: Sys[129] => Gate(Clk(ClockId::ApbBus));
+    /// Temperature sensor core clock divider.
+    TempCore: Sys[130] => Div(Osc);
+    /// SPI0 APB clock gate.
+    Spi0Apb: Sys[131] => Gate(Clk(ClockId::Apb0));
+    /// SPI1 APB clock gate.
+    Spi1Apb: Sys[132] => Gate(Clk(ClockId::Apb0));
+    /// SPI2 APB clock gate.
+    Spi2Apb: Sys[133] => Gate(Clk(ClockId::Apb0));
+    /// SPI3 APB clock gate.
+    Spi3Apb: Sys[134] => Gate(Clk(ClockId::ApbBus));
+    /// SPI4 APB clock gate.
+    Spi4Apb: Sys[135] => Gate(Clk(ClockId::ApbBus));
+    /// SPI5 APB clock gate.
+    Spi5Apb: Sys[136] => Gate(Clk(ClockId::ApbBus));
+    /// SPI6 APB clock gate.
+    Spi6Apb: Sys[137] => Gate(Clk(ClockId::ApbBus));
+    /// I2C0 APB clock gate.
+    I2c0Apb: Sys[138] => Gate(Clk(ClockId::Apb0));
+    /// I2C1 APB clock gate.
+    I2c1Apb: Sys[139] => Gate(Clk(ClockId::Apb0));
+    /// I2C2 APB clock gate.
+    I2c2Apb: Sys[140] => Gate(Clk(ClockId::Apb0));
+    /// I2C3 APB clock gate.
+    I2c3Apb: Sys[141] => Gate(Clk(ClockId::ApbBus));
+    /// I2C4 APB clock gate.
+    I2c4Apb: Sys[142] => Gate(Clk(ClockId::ApbBus));
+    /// I2C5 APB clock gate.
+    I2c5Apb: Sys[143] => Gate(Clk(ClockId::ApbBus));
+    /// I2C6 APB clock gate.
+    I2c6Apb: Sys[144] => Gate(Clk(ClockId::ApbBus));
+    /// UART0 APB clock gate.
+    Uart0Apb: Sys[145] => Gate(Clk(ClockId::Apb0));
+    /// UART0 core clock gate.
+    Uart0Core: Sys[146] => Gate(Osc);
+    /// UART1 APB clock gate.
+    Uart1Apb: Sys[147] => Gate(Clk(ClockId::Apb0));
+    /// UART1 core clock gate.
+    Uart1Core: Sys[148] => Gate(Osc);
+    /// UART2 APB clock gate.
+    Uart2Apb: Sys[149] => Gate(Clk(ClockId::Apb0));
+    /// UART2 core clock gate.
+    Uart2Core: Sys[150] => Gate(Osc);
+    /// UART3 APB clock gate.
+    Uart3Apb: Sys[151] => Gate(Clk(ClockId::ApbBus));
+    /// UART3 core clock fractional divider.
+    Uart3Core: Sys[152] => FracDiv(Clk(ClockId::PerhRoot));
+    /// UART4 APB clock gate.
+    Uart4Apb: Sys[153] => Gate(Clk(ClockId::ApbBus));
+    /// UART4 core clock fractional divider.
+    Uart4Core: Sys[154] => FracDiv(Clk(ClockId::PerhRoot));
+    /// UART5 APB clock gate.
+    Uart5Apb: Sys[155] => Gate(Clk(ClockId::ApbBus));
+    /// UART5 core clock fractional divider.
+    Uart5Core: Sys[156] => FracDiv(Clk(ClockId::PerhRoot));
+    /// AON OSC divide-by-four.
+    AonOscDiv4: Aon[0] => Div(Osc);
+    /// AON APB function clock mux.
+    AonApbFunc: Aon[1] => Mux(&[Clk(ClockId::AonOscDiv4), Osc]);
+    /// AON GMAC0 AHB clock gate.
+    AonGmac0Ahb: Aon[2] => Gate(Clk(ClockId::StgAxiAhb));
+    /// AON GMAC0 AXI clock gate.
+    AonGmac0Axi: Aon[3] => Gate(Clk(ClockId::StgAxiAhb));
+    /// AON OTP controller APB clock gate.
+    AonOtpcApb: Aon[9] => Gate(Clk(ClockId::ApbBus));
+    /// AON RTC APB clock gate.
+    AonRtcApb: Aon[10] => Gate(Clk(ClockId::ApbBus));
+    /// AON RTC internal clock divider.
+    AonRtcInternal: Aon[11] => Div(Osc);
+    /// AON RTC 32 kHz clock mux.
+    AonRtc32k: Aon[12] => Mux(&[Rtc, Clk(ClockId::AonRtcInternal)]);
+    /// AON RTC calibration clock gate.
+    AonRtcCal: Aon[13] => Gate(Osc);
+    /// STG USB0 APB clock gate.
+    StgUsb0Apb: Stg[1] => Gate(Clk(ClockId::ApbBus));
+    /// STG USB0 UTMI APB clock gate.
+    StgUsb0UtmiApb: Stg[2] => Gate(Clk(ClockId::ApbBus));
+    /// STG USB0 AXI clock gate.
+    StgUsb0Axi: Stg[3] => Gate(Clk(ClockId::StgAxiAhb));
+    /// STG USB0 LPM clock divider.
+    StgUsb0Lpm: Stg[4] => GateDiv(Osc);
+    /// STG USB0 STB clock divider.
+    StgUsb0Stb: Stg[5] => GateDiv(Osc);
+    /// STG USB0 application 125 MHz clock gate.
+    StgUsb0App125: Stg[6] => Gate(Clk(ClockId::Usb125m));
+    /// STG USB0 reference clock divider.
+    StgUsb0Refclk: Stg[7] => Div(Osc);
+    /// STG PCIe0 AXI master clock gate.
+    StgPcie0AxiMst0: Stg[8] => Gate(Clk(ClockId::StgAxiAhb));
+    /// STG PCIe0 APB clock gate.
+    StgPcie0Apb: Stg[9] => Gate(Clk(ClockId::ApbBus));
+    /// STG PCIe0 TL clock gate.
+    StgPcie0Tl: Stg[10] => Gate(Clk(ClockId::StgAxiAhb));
+    /// STG PCIe1 AXI master clock gate.
+    StgPcie1AxiMst0: Stg[11] => Gate(Clk(ClockId::StgAxiAhb));
+    /// STG PCIe1 APB clock gate.
+    StgPcie1Apb: Stg[12] => Gate(Clk(ClockId::ApbBus));
+    /// STG PCIe1 TL clock gate.
+    StgPcie1Tl: Stg[13] => Gate(Clk(ClockId::StgAxiAhb));
+    /// STG security engine AHB clock gate.
+    StgSecAhb: Stg[15] => Gate(Clk(ClockId::StgAxiAhb));
+    /// STG E24 RTC clock divider.
+    StgE2Rtc: Stg[24] => GateDiv(Osc);
+    /// STG E24 core clock gate.
+    StgE2Core: Stg[25] => Gate(Clk(ClockId::StgAxiAhb));
+    /// STG DMA AXI clock gate.
+    StgDma1pAxi: Stg[27] => Gate(Clk(ClockId::StgAxiAhb));
+    /// STG DMA AHB clock gate.
+    StgDma1pAhb: Stg[28] => Gate(Clk(ClockId::StgAxiAhb));
+    /// ISP domain APB clock divider.
+    IspDom4ApbFunc: Isp[0] => Div(Clk(ClockId::IspTopAxi));
+    /// ISP MIPI RX0 pixel clock divider.
+    IspMipiRx0Pxl: Isp[1] => Div(Clk(ClockId::IspTopCore));
+    /// ISP M31 D-PHY configuration clock divider.
+    IspM31dphyCfgIn: Isp[3] => Div(Clk(ClockId::IspTopCore));
+    /// ISP M31 D-PHY reference clock divider.
+    IspM31dphyRefIn: Isp[4] => Div(Clk(ClockId::IspTopCore));
+    /// ISP M31 D-PHY lane 0 TX escape clock divider.
+    IspM31dphyTxEscLan0: Isp[5] => Div(Clk(ClockId::IspTopCore));
+    /// VIN APB clock gate.
+    IspVinApb: Isp[6] => Gate(Clk(ClockId::IspDom4ApbFunc));
+    /// VIN system clock divider.
+    IspVinSys: Isp[7] => Div(Clk(ClockId::IspTopCore));
+    /// VIN pixel interface 0 clock gate.
+    IspVinPixelIf0: Isp[8] => Gate(Clk(ClockId::IspMipiRx0Pxl));
+    /// VIN pixel interface 1 clock gate.
+    IspVinPixelIf1: Isp[9] => Gate(Clk(ClockId::IspMipiRx0Pxl));
+    /// VIN pixel interface 2 clock gate.
+    IspVinPixelIf2: Isp[10] => Gate(Clk(ClockId::IspMipiRx0Pxl));
+    /// VIN pixel interface 3 clock gate.
+    IspVinPixelIf3: Isp[11] => Gate(Clk(ClockId::IspMipiRx0Pxl));
+    /// VIN AXI write clock mux: MIPI RX0 pixel clock, or inverted DVP clock.
+    IspVinPAxiWr: Isp[12] => Mux(&[Clk(ClockId::IspMipiRx0Pxl), Ext]);
+    /// ISP core clock mux: MIPI RX0 pixel clock, or inverted DVP clock.
+    IspV2TopWrapperC: Isp[13] => GateMux(&[Clk(ClockId::IspMipiRx0Pxl), Ext]);
+    /// VOUT APB clock divider.
+    VoutApb: Vout[0] => Div(Clk(ClockId::VoutTopAhb));
+    /// DC8200 pixel clock divider.
+    VoutDc8200Pix: Vout[1] => Div(Clk(ClockId::VoutSrc));
+    /// MIPI DSI system clock divider.
+    VoutDsiSys: Vout[2] => Div(Clk(ClockId::VoutSrc));
+    /// MIPI DSI TX escape clock divider.
+    VoutTxEsc: Vout[3] => Div(Clk(ClockId::VoutTopAhb));
+    /// DC8200 AXI clock gate.
+    VoutDc8200Axi: Vout[4] => Gate(Clk(ClockId::VoutTopAxi));
+    /// DC8200 core clock gate.
+    VoutDc8200Core: Vout[5] => Gate(Clk(ClockId::VoutTopAxi));
+    /// DC8200 AHB clock gate.
+    VoutDc8200Ahb: Vout[6] => Gate(Clk(ClockId::VoutTopAhb));
+    /// DC8200 pixel 0 clock mux: DC8200 pixel clock, or HDMI TX PHY pixel clock.
+    VoutDc8200Pix0: Vout[7] => GateMux(&[Clk(ClockId::VoutDc8200Pix), Ext]);
+    /// DC8200 pixel 1 clock mux: DC8200 pixel clock, or HDMI TX PHY pixel clock.
+    VoutDc8200Pix1: Vout[8] => GateMux(&[Clk(ClockId::VoutDc8200Pix), Ext]);
+    /// LCD output clock mux: DC8200 pixel 0 or pixel 1 clock.
+    VoutDomVoutTopLcd: Vout[9] => GateMux(&[
+        Clk(ClockId::VoutDc8200Pix0),
+        Clk(ClockId::VoutDc8200Pix1),
+    ]);
+    /// MIPI DSI TX APB clock gate.
+    VoutDsitxApb: Vout[10] => Gate(Clk(ClockId::VoutDsiSys));
+    /// MIPI DSI TX system clock gate.
+    VoutDsitxSys: Vout[11] => Gate(Clk(ClockId::VoutDsiSys));
+    /// MIPI DSI TX DPI clock mux: DC8200 pixel clock, or HDMI TX PHY pixel clock.
+    VoutDsitxDpi: Vout[12] => GateMux(&[Clk(ClockId::VoutDc8200Pix), Ext]);
+    /// MIPI DSI TX escape clock gate.
+    VoutDsitxTxesc: Vout[13] => Gate(Clk(ClockId::VoutTxEsc));
+    /// MIPI TX D-PHY escape clock gate.
+    VoutMipitxDphyTxesc: Vout[14] => Gate(Clk(ClockId::VoutTxEsc));
+    /// HDMI TX master clock gate.
+    VoutHdmiTxMclk: Vout[15] => Gate(Clk(ClockId::VoutTopHdmitx0Mclk));
+    /// HDMI TX audio bit clock divider.
+    VoutHdmiTxBclk: Vout[16] => Div(Clk(ClockId::VoutTopHdmitx0Mclk));
+    /// HDMI TX system clock gate.
+    VoutHdmiTxSys: Vout[17] => Gate(Clk(ClockId::VoutApb));
+}
+
+impl ClockId {
+    /// Gets the minimum divider value of the clock.
+    ///
+    /// For fractional dividers, the value is scaled by 100.
+    pub const fn min_div(self) -> u32 {
+        match self.kind() {
+            FracDiv(_) => CLK_FRAC_MIN,
+            _ => 1,
+        }
+    }
+
+    /// Gets the maximum divider value of the clock, zero for clocks without a divider.
+    ///
+    /// For fractional dividers, the value is scaled by 100.
+    pub const fn max_div(self) -> u32 {
+        match self {
+            Self::CpuCore => 7,
+            Self::CpuBus => 2,
+            Self::PerhRoot => 2,
+            Self::NocStgBus => 3,
+            Self::AxiCfg0 => 3,
+            Self::StgAxiAhb => 2,
+            Self::ApbBus => 8,
+            Self::Pll0Div2 | Self::Pll1Div2 | Self::Pll2Div2 => 2,
+            Self::AudioRoot => 8,
+            Self::MclkInner => 64,
+            Self::Isp2x => 8,
+            Self::IspAxi => 4,
+            Self::Gclk0 | Self::Gclk1 | Self::Gclk2 => 62,
+            Self::OscDiv2 => 2,
+            Self::Pll1Div4 | Self::Pll1Div8 => 2,
+            Self::GpuCore => 7,
+            Self::VoutAxi => 7,
+            Self::VoutTopMipiphyRef => 2,
+            Self::QspiRefSrc => 16,
+            Self::Sdio0Sdcard | Self::Sdio1Sdcard => 15,
+            Self::Usb125m => 15,
+            Self::GmacSrc => 7,
+            Self::Gmac1Gtxclk => 15,
+            Self::Gmac1Ptp => 31,
+            Self::Gmac0Gtxclk => 15,
+            Self::Gmac0Ptp => 31,
+            Self::GmacPhy => 31,
+            Self::Can0Timer | Self::Can1Timer => 24,
+            Self::Can0Can | Self::Can1Can => 63,
+            Self::TempCore => 24,
+            Self::Uart3Core | Self::Uart4Core | Self::Uart5Core => CLK_FRAC_MAX,
+            Self::AonOscDiv4 => 4,
+            Self::AonRtcInternal => 1022,
+            Self::StgUsb0Lpm => 2,
+            Self::StgUsb0Stb => 4,
+            Self::StgUsb0Refclk => 2,
+            Self::StgE2Rtc => 24,
+            Self::IspDom4ApbFunc => 15,
+            Self::IspMipiRx0Pxl => 8,
+            Self::IspM31dphyCfgIn | Self::IspM31dphyRefIn => 16,
+            Self::IspM31dphyTxEscLan0 => 60,
+            Self::IspVinSys => 16,
+            Self::VoutApb => 8,
+            Self::VoutDc8200Pix => 63,
+            Self::VoutDsiSys | Self::VoutTxEsc => 31,
+            Self::VoutHdmiTxBclk => 15,
+            _ => 0,
+        }
+    }
+
+    /// Decodes the divider value from a raw clock register value, `1` for clocks without a divider.
+    ///
+    /// For fractional dividers, the value is scaled by 100.
+    pub const fn div_from_bits(self, raw: u32) -> u32 {
+        match self.kind() {
+            FracDiv(_) => (raw & CLK_INT_MASK) * 100 + ((raw & CLK_FRAC_MASK) >> CLK_FRAC_SHIFT),
+            kind if kind.has_div() => raw & CLK_DIV_MASK,
+            _ => 1,
+        }
+    }
+
+    /// Encodes the divider value `div` into the bits of the divider field.
+    ///
+    /// Validates `div` against the divider range of the clock, fractional dividers are scaled by 100.
+    pub const fn div_to_bits(self, div: u32) -> Result<u32> {
+        if !self.kind().has_div() {
+            Err(Error::NoDivider(self))
+        } else if div < self.min_div() || div > self.max_div() {
+            Err(Error::InvalidDivider(self, div))
+        } else {
+            match self.kind() {
+                FracDiv(_) => Ok((div / 100) | ((div % 100) << CLK_FRAC_SHIFT)),
+                _ => Ok(div),
+            }
+        }
+    }
+
+    /// Gets the core (baud) clock of the UART instance `n`.
+    pub const fn uart_core(n: usize) -> Result<Self> {
+        match n {
+            0 => Ok(Self::Uart0Core),
+            1 => Ok(Self::Uart1Core),
+            2 => Ok(Self::Uart2Core),
+            3 => Ok(Self::Uart3Core),
+            4 => Ok(Self::Uart4Core),
+            5 => Ok(Self::Uart5Core),
+            _ => Err(Error::InvalidIndex(n)),
+        }
+    }
+
+    /// Gets the APB clock of the UART instance `n`.
+    pub const fn uart_apb(n: usize) -> Result<Self> {
+        match n {
+            0 => Ok(Self::Uart0Apb),
+            1 => Ok(Self::Uart1Apb),
+            2 => Ok(Self::Uart2Apb),
+            3 => Ok(Self::Uart3Apb),
+            4 => Ok(Self::Uart4Apb),
+            5 => Ok(Self::Uart5Apb),
+            _ => Err(Error::InvalidIndex(n)),
+        }
+    }
+
+    /// Gets the APB clock of the I2C instance `n`.
+    ///
+    /// The APB clock is also the reference clock used for SCL timing.
+    pub const fn i2c_apb(n: usize) -> Result<Self> {
+        match n {
+            0 => Ok(Self::I2c0Apb),
+            1 => Ok(Self::I2c1Apb),
+            2 => Ok(Self::I2c2Apb),
+            3 => Ok(Self::I2c3Apb),
+            4 => Ok(Self::I2c4Apb),
+            5 => Ok(Self::I2c5Apb),
+            6 => Ok(Self::I2c6Apb),
+            _ => Err(Error::InvalidIndex(n)),
+        }
+    }
+
+    /// Gets the APB clock of the SPI instance `n`.
+    ///
+    /// The APB clock is also the SSP reference clock used for SCK timing.
+    pub const fn spi_apb(n: usize) -> Result<Self> {
+        match n {
+            0 => Ok(Self::Spi0Apb),
+            1 => Ok(Self::Spi1Apb),
+            2 => Ok(Self::Spi2Apb),
+            3 => Ok(Self::Spi3Apb),
+            4 => Ok(Self::Spi4Apb),
+            5 => Ok(Self::Spi5Apb),
+            6 => Ok(Self::Spi6Apb),
+            _ => Err(Error::InvalidIndex(n)),
+        }
+    }
+
+    /// Gets the card clock of the SDIO instance `n`.
+    pub const fn sdio_sdcard(n: usize) -> Result<Self> {
+        match n {
+            0 => Ok(Self::Sdio0Sdcard),
+            1 => Ok(Self::Sdio1Sdcard),
+            _ => Err(Error::InvalidIndex(n)),
+        }
+    }
+
+    /// Gets the core clock of the CAN instance `n`.
+    pub const fn can_core(n: usize) -> Result<Self> {
+        match n {
+            0 => Ok(Self::Can0Can),
+            1 => Ok(Self::Can1Can),
+            _ => Err(Error::InvalidIndex(n)),
+        }
+    }
+
+    /// Gets the general purpose clock output `n`.
+    pub const fn gclk(n: usize) -> Result<Self> {
+        match n {
+            0 => Ok(Self::Gclk0),
+            1 => Ok(Self::Gclk1),
+            2 => Ok(Self::Gclk2),
+            _ => Err(Error::InvalidIndex(n)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parents_precede_children() {
+        // parents are listed before their children, so rate computation never recurses
+        // into a clock that is not described by the tree
+        ClockId::ALL.iter().enumerate().for_each(|(i, id)| {
+            let kind = id.kind();
+            let parents = kind
+                .parent()
+                .into_iter()
+                .chain(kind.parents().iter().copied());
+
+            parents.for_each(|p| {
+                if let Clk(parent) = p {
+                    assert!(
+                        (parent as usize) < i,
+                        "{} parent {}",
+                        id.name(),
+                        parent.name()
+                    );
+                }
+            });
+        });
+    }
+
+    #[test]
+    fn test_divider_ranges() {
+        ClockId::ALL.iter().for_each(|&id| {
+            assert_eq!(id.kind().has_div(), id.max_div() != 0, "{}", id.name());
+
+            if id.kind().has_div() {
+                let (min, max) = (id.min_div(), id.max_div());
+
+                assert_eq!(id.div_to_bits(min).map(|b| id.div_from_bits(b)), Ok(min));
+                assert_eq!(id.div_to_bits(max).map(|b| id.div_from_bits(b)), Ok(max));
+                assert_eq!(
+                    id.div_to_bits(min - 1),
+                    Err(Error::InvalidDivider(id, min - 1))
+                );
+                assert_eq!(
+                    id.div_to_bits(max + 1),
+                    Err(Error::InvalidDivider(id, max + 1))
+                );
+            } else {
+                assert_eq!(id.div_to_bits(1), Err(Error::NoDivider(id)));
+            }
+        });
+
+        // 6.08, as programmed by the VisionFive 2 firmware
+        assert_eq!(
+            ClockId::Uart3Core.div_to_bits(608),
+            Ok((8 << CLK_FRAC_SHIFT) | 6)
+        );
+    }
+
+    #[test]
+    fn test_unique_registers() {
+        ClockId::ALL.iter().enumerate().for_each(|(i, a)| {
+            ClockId::ALL[i + 1..].iter().for_each(|b| {
+                assert!(
+                    a.crg() != b.crg() || a.index() != b.index(),
+                    "{} and {} share a register",
+                    a.name(),
+                    b.name()
+                );
+            });
+        });
+    }
+}
@@ -1,7 +1,10 @@
+use super::*;
 use crate::pac;
 
 /// Contains configuration registers for SoC [`VOUTCRG`](pac::Voutcrg) clock peripheral.
-// FIXME: currently a place-holder, implement VOUTCRG specific functionality
+///
+/// The VOUTCRG registers are only accessible once the VOUT power domain is on, and the
+/// [`Peripheral::VoutTop`] clock domain is enabled.
 #[repr(C)]
 pub struct ClockVoutcrg {
     voutcrg: pac::Voutcrg,
@@ -23,6 +26,30 @@ impl ClockVoutcrg {
         Self { voutcrg }
     }
 
+    /// Reads the raw value of the clock register at `index`.
+    pub(crate) fn read_clk(&self, index: usize) -> u32 {
+        // SAFETY: all VOUTCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { read_crg(pac::Voutcrg::ptr() as *const u32, index) }
+    }
+
+    /// Modifies the raw value of the clock register at `index`.
+    pub(crate) fn modify_clk(&mut self, index: usize, f: impl FnOnce(u32) -> u32) {
+        // SAFETY: all VOUTCRG clock registers are contiguous 32-bit words, and `index` comes
+        // from the clock tree description.
+        unsafe { modify_crg(pac::Voutcrg::ptr() as *mut u32, index, f) }
+    }
+
+    /// Asserts or deasserts a VOUTCRG [Reset] line, waiting for the reset status to follow.
+    pub fn set_reset(&mut self, reset: Reset, assert: bool) -> Result<()> {
+        if reset.crg() != Crg::Vout {
+            return Err(Error::InvalidReset(reset));
+        }
+
+        // SAFETY: the reset line belongs to VOUTCRG.
+        unsafe { update_reset(pac::Voutcrg::ptr() as *mut u32, reset, assert) }
+    }
+
     /// Releases ownership of [`VOUTCRG`](pac::Voutcrg) clock configuration peripherals, conuming the [ClockVoutcrg].
     ///
     /// Gives ownership of the peripheral back to the caller, before calling [ClockVoutcrg] destructor.
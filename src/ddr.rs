@@ -6,26 +6,61 @@ use embedded_hal::delay::DelayNs;
 
 use crate::{clocks, delay, pac, pll};
 
+mod config;
 mod csr;
+mod error;
+mod info;
 mod mem;
+pub mod memtest;
 mod phy;
+mod timings;
 
+pub use config::*;
+pub use error::*;
+pub use info::*;
 pub use mem::*;
+pub use timings::*;
+
+/// Base address of the DRAM.
+pub const DRAM_BASE: usize = 0x4000_0000;
+/// Timeout (in microseconds) for the DDR controller and PHY requests.
+pub const REQUEST_TIMEOUT_US: u32 = 10_000;
+/// Timeout (in microseconds) for the DDR training.
+pub const TRAINING_TIMEOUT_US: u32 = 1_000_000;
+
+/// Gets the default size of the DRAM (in GiB), selected at build-time.
+///
+/// See [DramSize::new] for the default selection.
+pub const fn dram_size() -> usize {
+    DramSize::new().gib()
+}
+
+/// Gets the default number of DRAM ranks, selected at build-time.
+pub const fn dram_ranks() -> u8 {
+    DramSize::new().ranks()
+}
 
-#[cfg(not(any(feature = "2G", feature = "4G", feature = "8G")))]
-core::compile_error!("unsupported DRAM size or none set");
+/// Polls `done` every microsecond for up to `timeout_us` microseconds.
+///
+/// Returns `err` if `done` still returns `false` after `timeout_us`.
+fn wait_until(
+    udelay: &mut delay::McycleDelay,
+    timeout_us: u32,
+    err: Error,
+    mut done: impl FnMut() -> bool,
+) -> Result<()> {
+    let mut time = 0;
+
+    while !done() {
+        if time >= timeout_us {
+            return Err(err);
+        }
 
-/// Gets the configured size of the DRAM at build-time.
-pub const fn dram_size() -> usize {
-    if cfg!(feature = "2G") {
-        2
-    } else if cfg!(feature = "4G") {
-        4
-    } else if cfg!(feature = "8G") {
-        8
-    } else {
-        0
+        udelay.delay_us(1);
+        time += 1;
     }
+
+    Ok(())
 }
 
 /// Contains registers for initializing and configuring the DDR peripheral.
@@ -35,6 +70,8 @@ pub struct Ddr {
     dmc_phy: pac::DmcPhy,
     syscrg: clocks::ClockSyscrg,
     pll: pll::Pll,
+    config: DdrConfig,
+    timings: DdrTimings,
 }
 
 impl Ddr {
@@ -59,38 +96,104 @@ impl Ddr {
             dmc_phy,
             syscrg: clocks::ClockSyscrg::new(syscrg),
             pll: pll::Pll::new(syscon),
+            config: DdrConfig::new(),
+            timings: DdrTimings::lpddr4_2133(DramSize::new()),
         }
     }
 
+    /// Gets the [DdrConfig] used by the last call to [init](Self::init).
+    ///
+    /// Before initialization, this is the build-time default [DdrConfig].
+    pub const fn config(&self) -> DdrConfig {
+        self.config
+    }
+
+    /// Gets the [DdrTimings] used by the last call to [init](Self::init).
+    pub const fn timings(&self) -> &DdrTimings {
+        &self.timings
+    }
+
     /// Selects the [Ddr] Bus clock.
     pub fn select_bus_clock(&mut self, mux_sel: clocks::ClkDdrBusMuxSel) {
         self.syscrg.select_ddr_bus(mux_sel);
     }
 
-    /// Sets the [Pll] frequency for PLL1.
-    pub fn set_pll1(&mut self, freq: pll::Freq) {
+    /// Sets the [Pll](pll::Pll) frequency for PLL1, and waits for it to settle.
+    ///
+    /// Returns an error if PLL1 is powered down after programming, see
+    /// [`Pll::settle`](pll::Pll::settle).
+    pub fn set_pll1(&mut self, freq: pll::Freq) -> Result<()> {
         self.pll.set_pll1(freq);
+        self.pll.settle(pll::PllId::Pll1)?;
+
+        Ok(())
     }
 
-    /// Initializes the DDR DRAM peripheral.
-    pub fn init(&mut self) {
+    /// Initializes the DDR DRAM peripheral with the provided [DdrConfig].
+    ///
+    /// After training, each byte lane is verified on the first [LANE_CHECK_WORDS] words of DRAM
+    /// (see [check_lanes]). The words are restored after the check.
+    ///
+    /// Returns the [DdrInfo] with the byte lanes that passed the check, DRAM is only usable if
+    /// [DdrInfo::verified] is `true`. Returns an error if a step of the initialization times out.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{ddr, pac};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut ddr = ddr::Ddr::new(dp.dmc_ctrl, dp.dmc_phy, dp.syscrg, dp.sys_syscon);
+    ///
+    /// let config = ddr::DdrConfig::new().with_size(ddr::DramSize::Size8G);
+    /// let info = ddr.init(config).unwrap();
+    ///
+    /// for lane in 0..ddr::LANES {
+    ///     assert!(info.lane_verified(lane), "DDR byte lane {lane} failed");
+    /// }
+    /// ```
+    pub fn init(&mut self, config: DdrConfig) -> Result<DdrInfo> {
+        self.init_with_timings(config, config.speed_grade.timings(config.size))
+    }
+
+    /// Initializes the DDR DRAM peripheral with the provided [DdrConfig] and custom
+    /// [DdrTimings], instead of the preset of the [SpeedGrade].
+    ///
+    /// See [init](Self::init) for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{ddr, pac};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut ddr = ddr::Ddr::new(dp.dmc_ctrl, dp.dmc_phy, dp.syscrg, dp.sys_syscon);
+    ///
+    /// let config = ddr::DdrConfig::new();
+    /// let mut timings = config.speed_grade.timings(config.size);
+    /// timings.pi_mr11[1].dq_odt = ddr::Impedance::Rzq4;
+    ///
+    /// let _info = ddr.init_with_timings(config, timings).unwrap();
+    /// ```
+    pub fn init_with_timings(&mut self, config: DdrConfig, timings: DdrTimings) -> Result<DdrInfo> {
+        self.config = config;
+        self.timings = timings;
+
         let mut udelay = delay::u74_mdelay();
 
         self.syscrg
             .select_ddr_bus(clocks::ClkDdrBusMuxSel::ClkOscDiv2);
 
-        self.pll.set_pll1(pll::Freq::pll1_ddr2133_1066mhz());
-
-        udelay.delay_ns(500);
+        self.set_pll1(config.speed_grade.pll1_freq())?;
 
         self.syscrg
             .select_ddr_bus(clocks::ClkDdrBusMuxSel::ClkPll1Div2);
         udelay.delay_ns(200);
 
         // init the clocks.
-        self.init_osc(&mut udelay);
-        self.init_apb(&mut udelay);
-        self.init_axi(&mut udelay);
+        self.init_osc()?;
+        self.init_apb()?;
+        self.init_axi()?;
 
         // init the OMC PHY.
         self.phy_train();
@@ -101,136 +204,132 @@ impl Ddr {
             .select_ddr_bus(clocks::ClkDdrBusMuxSel::ClkOscDiv2);
 
         // init the OMC (Orbit Memory Controller).
-        self.omc_init();
+        let training_status = self.omc_init()?;
+
+        // SAFETY: DDR is initialized, and the start of DRAM is not in use yet
+        let mem =
+            unsafe { core::slice::from_raw_parts_mut(DRAM_BASE as *mut u32, LANE_CHECK_WORDS) };
+
+        // SAFETY: `mem[i]` is a valid, aligned reference
+        let saved: [u32; LANE_CHECK_WORDS] =
+            core::array::from_fn(|i| unsafe { core::ptr::read_volatile(&mem[i]) });
+
+        let failed = check_lanes(mem);
+
+        mem.iter_mut().zip(saved).for_each(|(w, val)| {
+            // SAFETY: `w` is a valid, aligned reference
+            unsafe { core::ptr::write_volatile(w, val) }
+        });
+
+        Ok(DdrInfo::new(
+            config.size.gib(),
+            config.size.ranks(),
+            LANES_MASK & !failed,
+            training_status,
+        ))
     }
 
-    /// Initialize the DDR OSC clock.
-    pub fn init_osc(&mut self, udelay: &mut delay::McycleDelay) {
-        self.syscrg
-            .inner()
-            .rst()
-            .software_address_selector()
-            .rst1()
-            .modify(|_, w| w.u0_ddr_osc().set_bit());
-
-        while self
-            .syscrg
-            .inner()
-            .rst()
-            .syscrg_status()
-            .rst1()
-            .read()
-            .u0_ddr_osc()
-            .is_reset()
-        {
-            udelay.delay_ns(1);
+    /// Initializes the DDR DRAM peripheral, detecting the DRAM size.
+    ///
+    /// The size is read from `serial`, the VisionFive 2 product serial number stored in the board
+    /// EEPROM (see [DramSize::from_product_serial]), if provided and valid.
+    ///
+    /// Otherwise, DDR is initialized for the build-time default [DramSize] (see [dram_size]), and
+    /// the size is detected by probing address aliasing (see [probe_size]):
+    ///
+    /// - if the DRAM is smaller, DDR is initialized again for the detected size.
+    /// - if the DRAM is at least as large, and the default is not the largest size, the probe is
+    ///   repeated with DDR initialized for [DramSize::Size8G].
+    ///
+    /// Returns an error if a byte lane fails the lane check before probing, since probing needs
+    /// working DRAM.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{ddr, pac};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut ddr = ddr::Ddr::new(dp.dmc_ctrl, dp.dmc_phy, dp.syscrg, dp.sys_syscon);
+    ///
+    /// let info = ddr.init_detect(ddr::SpeedGrade::Ddr2133, None).unwrap();
+    /// assert!(info.verified());
+    /// ```
+    pub fn init_detect(
+        &mut self,
+        speed_grade: SpeedGrade,
+        serial: Option<&str>,
+    ) -> Result<DdrInfo> {
+        let config = DdrConfig::new().with_speed_grade(speed_grade);
+
+        if let Some(size) = serial.and_then(DramSize::from_product_serial) {
+            return self.init(config.with_size(size));
         }
 
-        self.syscrg
-            .inner()
-            .rst()
-            .software_address_selector()
-            .rst1()
-            .modify(|_, w| w.u0_ddr_osc().none());
-
-        while self
-            .syscrg
-            .inner()
-            .rst()
-            .syscrg_status()
-            .rst1()
-            .read()
-            .u0_ddr_osc()
-            .is_none()
-        {
-            udelay.delay_ns(1);
+        let mut size = DramSize::new();
+
+        loop {
+            let info = self.init(config.with_size(size))?;
+
+            // probing needs working byte lanes
+            if !info.verified() {
+                return Err(Error::LaneCheckFailed(LANES_MASK & !info.verified_lanes()));
+            }
+
+            // SAFETY: DDR is initialized with the address map of `size`, and DRAM is not in use yet
+            let probed = unsafe { probe_size(DRAM_BASE as *mut u32, size) };
+
+            if probed != size {
+                return self.init(config.with_size(probed));
+            } else if size == DramSize::Size8G {
+                return Ok(info);
+            }
+
+            // DRAM is at least `size` large, probe with the largest address map
+            size = DramSize::Size8G;
         }
     }
 
-    /// Initialize the DDR APB clock.
-    pub fn init_apb(&mut self, udelay: &mut delay::McycleDelay) {
-        self.syscrg
-            .inner()
-            .rst()
-            .software_address_selector()
-            .rst1()
-            .modify(|_, w| w.u0_ddr_apb().reset());
-
-        while self
-            .syscrg
-            .inner()
-            .rst()
-            .syscrg_status()
-            .rst1()
-            .read()
-            .u0_ddr_apb()
-            .is_reset()
-        {
-            udelay.delay_ns(1);
-        }
+    /// Initialize the DDR OSC clock.
+    ///
+    /// Asserts, then deasserts the [`SYS_DDR_OSC`](clocks::Reset::SYS_DDR_OSC) reset line, waiting
+    /// for the reset status to follow each step.
+    ///
+    /// Returns an error if the reset status does not follow the reset request in time.
+    pub fn init_osc(&mut self) -> Result<()> {
+        self.reset(clocks::Reset::SYS_DDR_OSC)
+    }
 
-        self.syscrg
-            .inner()
-            .rst()
-            .software_address_selector()
-            .rst1()
-            .modify(|_, w| w.u0_ddr_apb().none());
-
-        while self
-            .syscrg
-            .inner()
-            .rst()
-            .syscrg_status()
-            .rst1()
-            .read()
-            .u0_ddr_apb()
-            .is_none()
-        {
-            udelay.delay_ns(1);
-        }
+    /// Initialize the DDR APB clock.
+    ///
+    /// Asserts, then deasserts the [`SYS_DDR_APB`](clocks::Reset::SYS_DDR_APB) reset line, waiting
+    /// for the reset status to follow each step.
+    ///
+    /// Returns an error if the reset status does not follow the reset request in time.
+    pub fn init_apb(&mut self) -> Result<()> {
+        self.reset(clocks::Reset::SYS_DDR_APB)
     }
 
     /// Initialize the DDR AXI clock.
-    pub fn init_axi(&mut self, udelay: &mut delay::McycleDelay) {
-        self.syscrg
-            .inner()
-            .rst()
-            .software_address_selector()
-            .rst1()
-            .modify(|_, w| w.u0_ddr_axi().reset());
-
-        while self
-            .syscrg
-            .inner()
-            .rst()
-            .syscrg_status()
-            .rst1()
-            .read()
-            .u0_ddr_axi()
-            .is_reset()
-        {
-            udelay.delay_ns(1);
-        }
+    ///
+    /// Asserts, then deasserts the [`SYS_DDR_AXI`](clocks::Reset::SYS_DDR_AXI) reset line, waiting
+    /// for the reset status to follow each step.
+    ///
+    /// Returns an error if the reset status does not follow the reset request in time.
+    pub fn init_axi(&mut self) -> Result<()> {
+        self.reset(clocks::Reset::SYS_DDR_AXI)
+    }
 
+    /// Asserts, then deasserts a DDR clock domain reset line.
+    ///
+    /// Each step waits up to [`clocks::RESET_TIMEOUT_US`] for the reset status to follow, and
+    /// returns [Error::ResetTimeout] otherwise.
+    fn reset(&mut self, reset: clocks::Reset) -> Result<()> {
+        // the DDR reset lines belong to SYSCRG, the only possible error is the status timeout
         self.syscrg
-            .inner()
-            .rst()
-            .software_address_selector()
-            .rst1()
-            .modify(|_, w| w.u0_ddr_axi().none());
-
-        while self
-            .syscrg
-            .inner()
-            .rst()
-            .syscrg_status()
-            .rst1()
-            .read()
-            .u0_ddr_axi()
-            .is_none()
-        {
-            udelay.delay_ns(1);
-        }
+            .set_reset(reset, true)
+            .and_then(|_| self.syscrg.set_reset(reset, false))
+            .map_err(|_| Error::ResetTimeout(reset))
     }
 
     /// Releases ownership of `DDR` peripherals, conuming the [Ddr].
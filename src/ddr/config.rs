@@ -0,0 +1,298 @@
+use super::DdrTimings;
+use crate::pll;
+
+/// Represents the size of the DRAM.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DramSize {
+    /// 2 GiB, single rank.
+    Size2G = 2,
+    /// 4 GiB, dual rank.
+    Size4G = 4,
+    /// 8 GiB, dual rank.
+    Size8G = 8,
+}
+
+impl DramSize {
+    /// Gets the default [DramSize] selected by the `2G`, `4G` or `8G` cargo features.
+    ///
+    /// Falls back to [DramSize::Size4G] if no size feature is enabled.
+    pub const fn new() -> Self {
+        if cfg!(feature = "2G") {
+            Self::Size2G
+        } else if cfg!(feature = "4G") {
+            Self::Size4G
+        } else if cfg!(feature = "8G") {
+            Self::Size8G
+        } else {
+            Self::Size4G
+        }
+    }
+
+    /// Creates a new [DramSize] from a size in GiB.
+    pub const fn from_gib(gib: usize) -> Option<Self> {
+        match gib {
+            2 => Some(Self::Size2G),
+            4 => Some(Self::Size4G),
+            8 => Some(Self::Size8G),
+            _ => None,
+        }
+    }
+
+    /// Parses the DRAM size from a VisionFive 2 product serial number, e.g.
+    /// `VF7110A1-2228-D008E000-00000001`, as stored in the board EEPROM.
+    ///
+    /// The size is encoded in GiB by the three digits following `D` in the third field.
+    pub fn from_product_serial(serial: &str) -> Option<Self> {
+        let field = serial.split('-').nth(2)?;
+        let digits = field.strip_prefix('D')?.get(..3)?;
+
+        digits.parse::<usize>().ok().and_then(Self::from_gib)
+    }
+
+    /// Gets the DRAM size in GiB.
+    pub const fn gib(&self) -> usize {
+        *self as usize
+    }
+
+    /// Gets the DRAM size in bytes.
+    pub const fn bytes(&self) -> u64 {
+        (*self as u64) << 30
+    }
+
+    /// Gets the number of DRAM ranks.
+    pub const fn ranks(&self) -> u8 {
+        match self {
+            Self::Size2G => 1,
+            Self::Size4G | Self::Size8G => 2,
+        }
+    }
+
+    /// Gets whether the DRAM has two ranks.
+    pub const fn is_dual_rank(&self) -> bool {
+        self.ranks() == 2
+    }
+}
+
+impl Default for DramSize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Probes the DRAM size by address aliasing.
+///
+/// A marker is written at `base`, then at the end of each [DramSize] smaller than `max`. If the
+/// write at the end of a size overwrites the marker, the address wrapped around, and DRAM has that
+/// size. Returns `max` if no address wrapped around, DRAM is at least `max` large.
+///
+/// # Safety
+///
+/// `base` must point to the start of DRAM, initialized with the address map of `max`. DRAM must
+/// not be in use: a word at `base` and at the end of each smaller size is overwritten.
+pub unsafe fn probe_size(base: *mut u32, max: DramSize) -> DramSize {
+    probe_size_in(&mut RawDram(base), max)
+}
+
+// Word access at a byte offset from the start of DRAM.
+trait Dram {
+    fn write(&mut self, offset: u64, val: u32);
+    fn read(&mut self, offset: u64) -> u32;
+}
+
+// DRAM mapped at a raw pointer.
+struct RawDram(*mut u32);
+
+impl Dram for RawDram {
+    fn write(&mut self, offset: u64, val: u32) {
+        // SAFETY: the caller of `probe_size` guarantees the offset is mapped and unused
+        unsafe { self.0.byte_add(offset as usize).write_volatile(val) }
+    }
+
+    fn read(&mut self, offset: u64) -> u32 {
+        // SAFETY: the caller of `probe_size` guarantees the offset is mapped and unused
+        unsafe { self.0.byte_add(offset as usize).read_volatile() }
+    }
+}
+
+fn probe_size_in<D: Dram>(dram: &mut D, max: DramSize) -> DramSize {
+    const MARKER: u32 = 0x5a5a_0000;
+
+    dram.write(0, MARKER);
+
+    for (i, size) in [DramSize::Size2G, DramSize::Size4G].into_iter().enumerate() {
+        if size.gib() >= max.gib() {
+            break;
+        }
+
+        dram.write(size.bytes(), MARKER | (i as u32 + 1));
+
+        if dram.read(0) != MARKER {
+            return size;
+        }
+    }
+
+    max
+}
+
+/// Represents the DRAM speed grade.
+///
+/// **NOTE**: only LPDDR4-2133 is supported. The controller and PHY tables (ported from `oreboot`)
+/// and the [DdrTimings] preset only exist for this grade, so it is the single variant.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SpeedGrade {
+    /// LPDDR4 at 2133 MT/s, PLL1 at 1,066 MHz.
+    #[default]
+    Ddr2133,
+}
+
+impl SpeedGrade {
+    /// Creates a new [SpeedGrade].
+    pub const fn new() -> Self {
+        Self::Ddr2133
+    }
+
+    /// Gets the data rate (in MT/s).
+    pub const fn mt_s(&self) -> u32 {
+        match self {
+            Self::Ddr2133 => 2133,
+        }
+    }
+
+    /// Gets the PLL1 [Freq](pll::Freq) settings that clock the DRAM.
+    pub const fn pll1_freq(&self) -> pll::Freq {
+        match self {
+            Self::Ddr2133 => pll::Freq::pll1_ddr2133_1066mhz(),
+        }
+    }
+
+    /// Gets the [DdrTimings] preset for a [DramSize].
+    pub const fn timings(&self, size: DramSize) -> DdrTimings {
+        match self {
+            Self::Ddr2133 => DdrTimings::lpddr4_2133(size),
+        }
+    }
+
+    /// Gets the PHY write leveling delay step (`PHY_WRLVL_DLY_STEP_X`).
+    pub const fn wrlvl_dly_step(&self) -> u32 {
+        match self {
+            Self::Ddr2133 => 0x12,
+        }
+    }
+}
+
+/// Configuration settings for DDR initialization.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DdrConfig {
+    pub size: DramSize,
+    pub speed_grade: SpeedGrade,
+}
+
+impl DdrConfig {
+    /// Creates a new [DdrConfig].
+    ///
+    /// The size defaults to the one selected by the cargo features, see [DramSize::new].
+    pub const fn new() -> Self {
+        Self {
+            size: DramSize::new(),
+            speed_grade: SpeedGrade::new(),
+        }
+    }
+
+    /// Builder function that sets the DRAM size.
+    pub const fn with_size(mut self, size: DramSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Builder function that sets the DRAM speed grade.
+    pub const fn with_speed_grade(mut self, speed_grade: SpeedGrade) -> Self {
+        self.speed_grade = speed_grade;
+        self
+    }
+}
+
+impl Default for DdrConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dram_size() {
+        assert_eq!(DramSize::from_gib(2), Some(DramSize::Size2G));
+        assert_eq!(DramSize::from_gib(8), Some(DramSize::Size8G));
+        assert_eq!(DramSize::from_gib(16), None);
+
+        assert_eq!(DramSize::Size2G.ranks(), 1);
+        assert_eq!(DramSize::Size4G.ranks(), 2);
+        assert_eq!(DramSize::Size8G.bytes(), 0x2_0000_0000);
+
+        assert_eq!(
+            DramSize::from_product_serial("VF7110A1-2228-D008E000-00000001"),
+            Some(DramSize::Size8G)
+        );
+        assert_eq!(
+            DramSize::from_product_serial("VF7110B1-2310-D004E000-00001234"),
+            Some(DramSize::Size4G)
+        );
+        assert_eq!(
+            DramSize::from_product_serial("VF7110A1-2228-D002E000-00000001"),
+            Some(DramSize::Size2G)
+        );
+        assert_eq!(
+            DramSize::from_product_serial("VF7110A1-2228-D016E000-00000001"),
+            None
+        );
+        assert_eq!(DramSize::from_product_serial("VF7110A1-2228"), None);
+        assert_eq!(DramSize::from_product_serial("VF7110A1-2228-E008"), None);
+    }
+
+    #[test]
+    fn test_probe_size() {
+        // DRAM of `size`, one word per GiB, addresses wrap around at the end of DRAM
+        struct Aliased {
+            size: DramSize,
+            words: [u32; 8],
+        }
+
+        impl Dram for Aliased {
+            fn write(&mut self, offset: u64, val: u32) {
+                self.words[((offset % self.size.bytes()) >> 30) as usize] = val;
+            }
+
+            fn read(&mut self, offset: u64) -> u32 {
+                self.words[((offset % self.size.bytes()) >> 30) as usize]
+            }
+        }
+
+        let sizes = [DramSize::Size2G, DramSize::Size4G, DramSize::Size8G];
+
+        for size in sizes {
+            for max in sizes {
+                let mut dram = Aliased {
+                    size,
+                    words: [0; 8],
+                };
+                let expected = if size.gib() < max.gib() { size } else { max };
+
+                assert_eq!(probe_size_in(&mut dram, max), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ddr_config() {
+        let config = DdrConfig::new().with_size(DramSize::Size2G);
+
+        assert_eq!(config.size, DramSize::Size2G);
+        assert_eq!(config.speed_grade, SpeedGrade::Ddr2133);
+        assert_eq!(config.speed_grade.mt_s(), 2133);
+    }
+}
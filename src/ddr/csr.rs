@@ -26,10 +26,17 @@ impl Ddr {
     /// # Safety
     ///
     /// Caller must have exclusive access to the DDR MC PHY and Control memory range.
-    fn train(&mut self, udelay: &mut delay::McycleDelay, training_status_reg: usize) {
+    ///
+    /// Returns an error if training, or a PHY frequency change request, does not complete in time.
+    fn train(&mut self, udelay: &mut delay::McycleDelay, training_status_reg: usize) -> Result<()> {
         let freq_change_req = FREQ_CHANGE;
+        let mut time = 0;
 
         while (self.dmc_ctrl.csr(training_status_reg >> 2).read().bits() & 0x2) != 0x0 {
+            if time >= TRAINING_TIMEOUT_US {
+                return Err(Error::TrainingTimeout);
+            }
+
             let req_type = self.dmc_phy.csr(freq_change_req).read().bits();
             if (req_type & 0x0000_0020) == 0x0000_0020 {
                 let freq_change_req = req_type & 0x0000_001f;
@@ -56,13 +63,18 @@ impl Ddr {
                     .csr(FREQ_CHANGE_ACK)
                     .write(|w| w.csr().variant(0x1));
 
-                while (self.dmc_phy.csr(FREQ_CHANGE_ACK).read().bits() & 0x1) != 0x0 {
-                    udelay.delay_ns(2);
-                }
+                let err = Error::FreqChangeTimeout(freq_change_req);
+
+                wait_until(udelay, REQUEST_TIMEOUT_US, err, || {
+                    (self.dmc_phy.csr(FREQ_CHANGE_ACK).read().bits() & 0x1) == 0x0
+                })?;
             }
 
-            udelay.delay_ns(1);
+            udelay.delay_us(1);
+            time += 1;
         }
+
+        Ok(())
     }
 
     /// Performs DDR MC CSR initialization algorithm.
@@ -70,16 +82,21 @@ impl Ddr {
     /// # Safety
     ///
     /// Caller must have exclusive access to the DDR MC PHY and Control memory range.
-    pub fn omc_init(&mut self) {
+    ///
+    /// Returns the raw training status read after training completed, or an error if the
+    /// controller or training does not respond in time.
+    pub fn omc_init(&mut self) -> Result<u32> {
+        let size = self.config.size;
+
         self.dmc_ctrl.csr(0).write(|w| w.csr().variant(0x1));
 
-        DDR_CSR_CFG0.iter().for_each(|cfg| {
+        ddr_csr_cfg0(size).iter().for_each(|cfg| {
             self.dmc_ctrl
                 .sec((cfg.reg_nr >> 2) as usize)
                 .write(|w| w.sec().variant(cfg.value));
         });
 
-        if cfg!(feature = "8G") || cfg!(feature = "4G") {
+        if size.is_dual_rank() {
             self.dmc_ctrl
                 .sec(0xf34 >> 2)
                 .write(|w| w.sec().variant(0x1f00_0041));
@@ -107,9 +124,12 @@ impl Ddr {
             .csr(REQ_RES_REG1 >> 2)
             .write(|w| w.csr().variant(0x4000_0000));
 
-        while self.dmc_ctrl.csr(REQ_RES_REG1 >> 2).read().bits() & 0x8000_0000 != 0x8000_0000 {
-            udelay.delay_ns(1);
-        }
+        wait_until(
+            &mut udelay,
+            REQUEST_TIMEOUT_US,
+            Error::ControllerTimeout,
+            || self.dmc_ctrl.csr(REQ_RES_REG1 >> 2).read().bits() & 0x8000_0000 == 0x8000_0000,
+        )?;
 
         self.dmc_ctrl
             .csr(REQ_RES_REG1 >> 2)
@@ -132,7 +152,7 @@ impl Ddr {
         udelay.delay_ns(3000);
 
         // Drive CKE high (clock enable)
-        let val = if cfg!(feature = "8G") || cfg!(feature = "4G") {
+        let val = if size.is_dual_rank() {
             0x000_0003c
         } else {
             0x0000_001c
@@ -148,7 +168,7 @@ impl Ddr {
 
         // Waits tINIT5 (2 us): Minimum idle time before first MRW/MRR command
         udelay.delay_ns(4);
-        DDR_CSR_CFG2.iter().for_each(|cfg| {
+        self.timings.csr_boot().iter().for_each(|cfg| {
             self.dmc_ctrl
                 .csr((cfg.reg_nr >> 2) as usize)
                 .write(|w| w.csr().variant(cfg.value));
@@ -165,7 +185,7 @@ impl Ddr {
             .csr(0x0014 >> 2)
             .write(|w| w.csr().variant(0x0000_0001));
 
-        if cfg!(feature = "8G") || cfg!(feature = "4G") {
+        if size.is_dual_rank() {
             self.dmc_ctrl
                 .csr(0x0010 >> 2)
                 .write(|w| w.csr().variant(0x0000_0020));
@@ -193,11 +213,16 @@ impl Ddr {
         // This register seems to first indicate that we are ready for training,
         // and then, that training is done. See the train() function using the same
         // mask again.
-        while self.dmc_ctrl.csr(TRAINING_STATUS_MAYBE >> 2).read().bits() & 0x2 != 0x2 {
-            udelay.delay_ns(1);
-        }
+        wait_until(
+            &mut udelay,
+            REQUEST_TIMEOUT_US,
+            Error::TrainingStartTimeout,
+            || self.dmc_ctrl.csr(TRAINING_STATUS_MAYBE >> 2).read().bits() & 0x2 == 0x2,
+        )?;
+
+        self.train(&mut udelay, TRAINING_STATUS_MAYBE)?;
 
-        self.train(&mut udelay, TRAINING_STATUS_MAYBE);
+        let training_status = self.dmc_ctrl.csr(TRAINING_STATUS_MAYBE >> 2).read().bits();
 
         // NOTE: This here even worked when I was accidentally off to 0x150 / 0x154.
         self.dmc_phy.base(0x14c >> 2).read().bits();
@@ -212,7 +237,7 @@ impl Ddr {
                 .modify(|r, w| w.base().variant((r.bits() & cfg.mask) | cfg.value));
         });
 
-        DDR_CSR_CFG4.iter().for_each(|cfg| {
+        self.timings.csr().iter().for_each(|cfg| {
             self.dmc_ctrl
                 .csr((cfg.reg_nr >> 2) as usize)
                 .write(|w| w.csr().variant(cfg.value));
@@ -222,7 +247,7 @@ impl Ddr {
             .sec(0x0704 >> 2)
             .write(|w| w.sec().variant(0x0000_0007));
 
-        DDR_CSR_CFG5.iter().for_each(|cfg| {
+        ddr_csr_cfg5(size).iter().for_each(|cfg| {
             self.dmc_ctrl
                 .csr((cfg.reg_nr >> 2) as usize)
                 .write(|w| w.csr().variant(cfg.value));
@@ -239,5 +264,7 @@ impl Ddr {
         self.dmc_ctrl
             .csr(0x0020 >> 2)
             .write(|w| w.csr().variant(0x0000_0001));
+
+        Ok(training_status)
     }
 }
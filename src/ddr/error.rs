@@ -0,0 +1,62 @@
+use core::fmt;
+
+use super::memtest::Failure;
+use crate::clocks::Reset;
+use crate::pll;
+
+/// Convenience [`Result`](core::result::Result) alias for JH71xx DDR module.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// DDR initialization errors.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Reset status of a DDR clock domain did not follow the reset request in time.
+    ResetTimeout(Reset),
+    /// DDR controller did not acknowledge the initialization request in time.
+    ControllerTimeout,
+    /// PHY did not acknowledge the frequency change request (`0..=2`) in time.
+    FreqChangeTimeout(u32),
+    /// DDR controller did not become ready for training in time.
+    TrainingStartTimeout,
+    /// DDR training did not complete in time.
+    TrainingTimeout,
+    /// Byte lanes (bitmask, bit `n` for lane `n`) failed the write/read-back check after
+    /// training, see [check_lanes](super::check_lanes) and [init_detect](super::Ddr::init_detect).
+    LaneCheckFailed(u8),
+    /// Memory test read back a wrong value.
+    Memtest(Failure),
+    /// PLL1 configuration error.
+    Pll(pll::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ResetTimeout(err) => {
+                write!(f, "DDR reset line {} timed out", err.line())
+            }
+            Self::ControllerTimeout => write!(f, "DDR controller init request timed out"),
+            Self::FreqChangeTimeout(err) => {
+                write!(f, "DDR PHY frequency change request {err} timed out")
+            }
+            Self::TrainingStartTimeout => write!(f, "DDR training start timed out"),
+            Self::TrainingTimeout => write!(f, "DDR training timed out"),
+            Self::LaneCheckFailed(err) => write!(f, "DDR byte lane check failed: {err:#06b}"),
+            Self::Memtest(err) => write!(
+                f,
+                "DDR memory test failed at {:#x}: wrote {:#010x}, read {:#010x}",
+                err.addr(),
+                err.expected(),
+                err.actual()
+            ),
+            Self::Pll(err) => write!(f, "DDR {err}"),
+        }
+    }
+}
+
+impl From<pll::Error> for Error {
+    fn from(err: pll::Error) -> Self {
+        Self::Pll(err)
+    }
+}
@@ -0,0 +1,164 @@
+use core::fmt;
+
+/// Number of DDR byte lanes (PHY data slices) on the 32-bit DRAM bus.
+pub const LANES: usize = 4;
+
+/// Bitmask of all DDR byte lanes.
+pub const LANES_MASK: u8 = (1 << LANES) - 1;
+
+/// Number of 32-bit words written by [check_lanes] after training.
+pub const LANE_CHECK_WORDS: usize = 64;
+
+/// Test patterns written by [check_lanes].
+pub const LANE_PATTERNS: [u32; 6] = [
+    0x0000_0000,
+    0xffff_ffff,
+    0x5555_5555,
+    0xaaaa_aaaa,
+    0x0f0f_0f0f,
+    0xf0f0_f0f0,
+];
+
+/// Gets the bitmask of byte lanes that differ between `expected` and `actual` words.
+///
+/// Bit `n` of the result is set if byte `n` of the words differ.
+pub const fn lane_errors(expected: u32, actual: u32) -> u8 {
+    let diff = expected ^ actual;
+    let mut lanes = 0;
+    let mut lane = 0;
+
+    while lane < LANES {
+        if (diff >> (lane * 8)) & 0xff != 0 {
+            lanes |= 1 << lane;
+        }
+        lane += 1;
+    }
+
+    lanes
+}
+
+/// Writes [LANE_PATTERNS] to `mem`, and returns the bitmask of byte lanes that read back wrong.
+///
+/// Each word is mixed with its index, so address line faults also show up as lane errors.
+pub fn check_lanes(mem: &mut [u32]) -> u8 {
+    LANE_PATTERNS.iter().fold(0, |errors, &pattern| {
+        let word = |i: usize| pattern ^ (i as u32).wrapping_mul(0x0101_0101);
+
+        mem.iter_mut().enumerate().for_each(|(i, w)| {
+            // SAFETY: `w` is a valid, aligned reference
+            unsafe { core::ptr::write_volatile(w, word(i)) }
+        });
+
+        mem.iter().enumerate().fold(errors, |errors, (i, w)| {
+            // SAFETY: `w` is a valid, aligned reference
+            errors | lane_errors(word(i), unsafe { core::ptr::read_volatile(w) })
+        })
+    })
+}
+
+/// Represents the result of a DDR initialization.
+///
+/// Size and ranks are the [DdrConfig](super::DdrConfig) DDR was initialized for.
+///
+/// Verified byte lanes are the result of the write/read-back check of [check_lanes] on DRAM after
+/// training, not results reported by the PHY training.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DdrInfo {
+    size: usize,
+    ranks: u8,
+    verified_lanes: u8,
+    training_status: u32,
+}
+
+impl DdrInfo {
+    /// Creates a new [DdrInfo] from the provided parameters.
+    pub const fn new(size: usize, ranks: u8, verified_lanes: u8, training_status: u32) -> Self {
+        Self {
+            size,
+            ranks,
+            verified_lanes,
+            training_status,
+        }
+    }
+
+    /// Gets the configured DRAM size (in GiB).
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Gets the configured number of DRAM ranks.
+    pub const fn ranks(&self) -> u8 {
+        self.ranks
+    }
+
+    /// Gets the bitmask of byte lanes that passed the [check_lanes] verification, bit `n` for
+    /// lane `n`.
+    pub const fn verified_lanes(&self) -> u8 {
+        self.verified_lanes
+    }
+
+    /// Gets whether byte lane `n` passed the [check_lanes] verification.
+    pub const fn lane_verified(&self, n: usize) -> bool {
+        n < LANES && self.verified_lanes & (1 << n) != 0
+    }
+
+    /// Gets whether all byte lanes passed the [check_lanes] verification.
+    pub const fn verified(&self) -> bool {
+        self.verified_lanes & LANES_MASK == LANES_MASK
+    }
+
+    /// Gets the raw OMC training status register read after training completed.
+    ///
+    /// **NOTE**: only bit 1 (training in progress) is known, the register has no known per-lane
+    /// fields.
+    pub const fn training_status(&self) -> u32 {
+        self.training_status
+    }
+}
+
+impl fmt::Display for DdrInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DDR: {} GiB, {} rank(s), verified lanes",
+            self.size, self.ranks
+        )?;
+
+        (0..LANES).try_for_each(|n| {
+            let status = if self.lane_verified(n) { "ok" } else { "fail" };
+            write!(f, " {n}:{status}")
+        })?;
+
+        write!(f, ", training status: {:#010x}", self.training_status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lane_errors() {
+        assert_eq!(lane_errors(0x1234_5678, 0x1234_5678), 0b0000);
+        assert_eq!(lane_errors(0x1234_5678, 0x1234_5679), 0b0001);
+        assert_eq!(lane_errors(0x1234_5678, 0x1234_0078), 0b0010);
+        assert_eq!(lane_errors(0x0000_0000, 0x8000_0000), 0b1000);
+        assert_eq!(lane_errors(0x0000_0000, 0xffff_ffff), LANES_MASK);
+    }
+
+    #[test]
+    fn test_check_lanes() {
+        let mut mem = [0u32; LANE_CHECK_WORDS];
+        assert_eq!(check_lanes(&mut mem), 0);
+
+        let info = DdrInfo::new(4, 2, LANES_MASK, 0x2);
+        assert!(info.verified());
+        assert!(info.lane_verified(3));
+        assert!(!info.lane_verified(LANES));
+
+        let info = DdrInfo::new(4, 2, 0b1011, 0x2);
+        assert!(!info.verified());
+        assert!(!info.lane_verified(2));
+    }
+}
@@ -0,0 +1,411 @@
+//! DRAM memory tests.
+//!
+//! Tests run on a slice of 32-bit words, so they can be pointed at DRAM right after
+//! [Ddr::init](super::Ddr::init) with [dram], or at a plain slice on the host.
+//!
+//! Each test returns the first failing address and pattern as a [Failure].
+
+use core::ops::Range;
+
+use super::{Error, Result, DRAM_BASE};
+
+/// Patterns used by [moving_inversions] in [run_all].
+pub const PATTERNS: [u32; 4] = [0x0000_0000, 0xffff_ffff, 0x5555_5555, 0x3333_3333];
+
+/// Represents the first failing word of a memory test.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Failure {
+    addr: usize,
+    expected: u32,
+    actual: u32,
+}
+
+impl Failure {
+    /// Creates a new [Failure] from the provided parameters.
+    pub const fn new(addr: usize, expected: u32, actual: u32) -> Self {
+        Self {
+            addr,
+            expected,
+            actual,
+        }
+    }
+
+    /// Gets the address of the failing word.
+    pub const fn addr(&self) -> usize {
+        self.addr
+    }
+
+    /// Gets the pattern written to the failing word.
+    pub const fn expected(&self) -> u32 {
+        self.expected
+    }
+
+    /// Gets the value read back from the failing word.
+    pub const fn actual(&self) -> u32 {
+        self.actual
+    }
+}
+
+/// Gets a DRAM range as a slice of words, for use with the memory tests.
+///
+/// `range` is a byte offset range from [DRAM_BASE], truncated to whole words.
+///
+/// # Safety
+///
+/// DRAM must be initialized, and `range` must be inside DRAM and not in use: the tests overwrite
+/// its contents.
+pub unsafe fn dram(range: Range<usize>) -> &'static mut [u32] {
+    let len = range.end.saturating_sub(range.start) / 4;
+
+    core::slice::from_raw_parts_mut((DRAM_BASE + range.start) as *mut u32, len)
+}
+
+// Word access to the memory under test, so the tests also run on fault-injecting memory in
+// host tests.
+trait Words {
+    fn len(&self) -> usize;
+    fn addr(&self, i: usize) -> usize;
+    fn read(&self, i: usize) -> u32;
+    fn write(&mut self, i: usize, val: u32);
+}
+
+impl Words for [u32] {
+    fn len(&self) -> usize {
+        <[u32]>::len(self)
+    }
+
+    fn addr(&self, i: usize) -> usize {
+        self.as_ptr() as usize + i * 4
+    }
+
+    fn read(&self, i: usize) -> u32 {
+        // SAFETY: `self[i]` is a valid, aligned reference
+        unsafe { core::ptr::read_volatile(&self[i]) }
+    }
+
+    fn write(&mut self, i: usize, val: u32) {
+        // SAFETY: `self[i]` is a valid, aligned reference
+        unsafe { core::ptr::write_volatile(&mut self[i], val) }
+    }
+}
+
+/// Reads back `mem[i]`, and returns a [Failure] if it is not `expected`.
+pub fn verify(mem: &[u32], i: usize, expected: u32) -> Result<()> {
+    check(mem, i, expected)
+}
+
+fn check<M: Words + ?Sized>(mem: &M, i: usize, expected: u32) -> Result<()> {
+    let actual = mem.read(i);
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(Error::Memtest(Failure::new(mem.addr(i), expected, actual)))
+    }
+}
+
+/// Tests the data bus by walking a one, then a zero, through the first word of `mem`.
+pub fn data_bus(mem: &mut [u32]) -> Result<()> {
+    data_bus_words(mem)
+}
+
+fn data_bus_words<M: Words + ?Sized>(mem: &mut M) -> Result<()> {
+    if mem.len() == 0 {
+        return Ok(());
+    }
+
+    (0..32)
+        .flat_map(|bit| [1u32 << bit, !(1u32 << bit)])
+        .try_for_each(|pattern| {
+            mem.write(0, pattern);
+            check(mem, 0, pattern)
+        })
+}
+
+/// Tests the address lines covering `mem` for stuck and shorted bits.
+///
+/// Words at power-of-two offsets are written with a pattern, then each offset is written in
+/// turn with the inverted pattern, and all other offsets are checked to be unchanged.
+pub fn address_bus(mem: &mut [u32]) -> Result<()> {
+    address_bus_words(mem)
+}
+
+fn address_bus_words<M: Words + ?Sized>(mem: &mut M) -> Result<()> {
+    const PATTERN: u32 = 0xaaaa_aaaa;
+    const ANTI: u32 = !PATTERN;
+
+    if mem.len() == 0 {
+        return Ok(());
+    }
+
+    let len = mem.len();
+    let offsets =
+        || core::iter::successors(Some(1usize), |o| o.checked_mul(2)).take_while(move |&o| o < len);
+
+    offsets().for_each(|o| mem.write(o, PATTERN));
+
+    // check for address bits stuck high
+    mem.write(0, ANTI);
+    offsets().try_for_each(|o| check(mem, o, PATTERN))?;
+    mem.write(0, PATTERN);
+
+    // check for address bits stuck low or shorted
+    offsets().try_for_each(|test| {
+        mem.write(test, ANTI);
+
+        check(mem, 0, PATTERN)?;
+        offsets()
+            .filter(|&o| o != test)
+            .try_for_each(|o| check(mem, o, PATTERN))?;
+
+        mem.write(test, PATTERN);
+        Ok(())
+    })
+}
+
+/// Runs the moving inversions test with `pattern` over `mem`.
+///
+/// `mem` is filled with `pattern`, then each word is checked and inverted going up, and checked
+/// and restored going down.
+pub fn moving_inversions(mem: &mut [u32], pattern: u32) -> Result<()> {
+    moving_inversions_words(mem, pattern)
+}
+
+fn moving_inversions_words<M: Words + ?Sized>(mem: &mut M, pattern: u32) -> Result<()> {
+    (0..mem.len()).for_each(|i| mem.write(i, pattern));
+
+    (0..mem.len()).try_for_each(|i| -> Result<()> {
+        check(mem, i, pattern)?;
+        mem.write(i, !pattern);
+        Ok(())
+    })?;
+
+    (0..mem.len()).rev().try_for_each(|i| {
+        check(mem, i, !pattern)?;
+        mem.write(i, pattern);
+        Ok(())
+    })
+}
+
+/// Runs the March C- test over the `range` of word indices of `mem`.
+///
+/// The range is clamped to the length of `mem`.
+pub fn march(mem: &mut [u32], range: Range<usize>) -> Result<()> {
+    march_words(mem, range)
+}
+
+fn march_words<M: Words + ?Sized>(mem: &mut M, range: Range<usize>) -> Result<()> {
+    const ZERO: u32 = 0x0000_0000;
+    const ONE: u32 = 0xffff_ffff;
+
+    let range = range.start.min(mem.len())..range.end.min(mem.len());
+
+    march_step(mem, range.clone(), true, None, Some(ZERO))?;
+    march_step(mem, range.clone(), true, Some(ZERO), Some(ONE))?;
+    march_step(mem, range.clone(), true, Some(ONE), Some(ZERO))?;
+    march_step(mem, range.clone(), false, Some(ZERO), Some(ONE))?;
+    march_step(mem, range.clone(), false, Some(ONE), Some(ZERO))?;
+    march_step(mem, range, true, Some(ZERO), None)
+}
+
+// Reads back `r`, then writes `w`, for each word of `range` going up or down.
+fn march_step<M: Words + ?Sized>(
+    mem: &mut M,
+    range: Range<usize>,
+    up: bool,
+    r: Option<u32>,
+    w: Option<u32>,
+) -> Result<()> {
+    let mut cell = |i: usize| {
+        if let Some(r) = r {
+            check(mem, i, r)?;
+        }
+        if let Some(w) = w {
+            mem.write(i, w);
+        }
+        Ok(())
+    };
+
+    if up {
+        range.into_iter().try_for_each(&mut cell)
+    } else {
+        range.rev().try_for_each(&mut cell)
+    }
+}
+
+/// Runs all memory tests over `mem`: data bus, address bus, moving inversions with [PATTERNS],
+/// and March C-.
+///
+/// # Example
+///
+/// ```no_run
+/// use jh71xx_hal::{ddr, pac};
+///
+/// let dp = pac::Peripherals::take().unwrap();
+/// let mut ddr = ddr::Ddr::new(dp.dmc_ctrl, dp.dmc_phy, dp.syscrg, dp.sys_syscon);
+/// ddr.init(ddr::DdrConfig::new()).unwrap();
+///
+/// // SAFETY: DDR is initialized, and the first MiB of DRAM is not in use
+/// let mem = unsafe { ddr::memtest::dram(0..0x10_0000) };
+/// ddr::memtest::run_all(mem).unwrap();
+/// ```
+pub fn run_all(mem: &mut [u32]) -> Result<()> {
+    run_all_words(mem)
+}
+
+fn run_all_words<M: Words + ?Sized>(mem: &mut M) -> Result<()> {
+    data_bus_words(mem)?;
+    address_bus_words(mem)?;
+
+    PATTERNS
+        .iter()
+        .try_for_each(|&pattern| moving_inversions_words(mem, pattern))?;
+
+    let len = mem.len();
+    march_words(mem, 0..len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Memory with a faulty word `at`, reading back with `high` bits set and `low` bits cleared,
+    // and word indices aliased with the `alias` bits cleared.
+    struct Faulty<'a> {
+        mem: &'a mut [u32],
+        at: Option<usize>,
+        high: u32,
+        low: u32,
+        alias: usize,
+    }
+
+    impl<'a> Faulty<'a> {
+        fn stuck(mem: &'a mut [u32], at: usize, high: u32, low: u32) -> Self {
+            Self {
+                mem,
+                at: Some(at),
+                high,
+                low,
+                alias: 0,
+            }
+        }
+
+        fn aliased(mem: &'a mut [u32], alias: usize) -> Self {
+            Self {
+                mem,
+                at: None,
+                high: 0,
+                low: 0,
+                alias,
+            }
+        }
+    }
+
+    impl Words for Faulty<'_> {
+        fn len(&self) -> usize {
+            self.mem.len()
+        }
+
+        fn addr(&self, i: usize) -> usize {
+            self.mem.addr(i)
+        }
+
+        fn read(&self, i: usize) -> u32 {
+            let i = i & !self.alias;
+            let val = self.mem.read(i);
+
+            if self.at == Some(i) {
+                (val | self.high) & !self.low
+            } else {
+                val
+            }
+        }
+
+        fn write(&mut self, i: usize, val: u32) {
+            self.mem.write(i & !self.alias, val)
+        }
+    }
+
+    fn failure(addr: usize, expected: u32, actual: u32) -> Result<()> {
+        Err(Error::Memtest(Failure::new(addr, expected, actual)))
+    }
+
+    #[test]
+    fn test_memtest() {
+        let mut mem = [0u32; 1024];
+
+        assert_eq!(data_bus(&mut mem), Ok(()));
+        assert_eq!(address_bus(&mut mem), Ok(()));
+        assert_eq!(moving_inversions(&mut mem, 0x5555_5555), Ok(()));
+        assert_eq!(march(&mut mem, 16..512), Ok(()));
+        assert_eq!(march(&mut mem, 512..4096), Ok(()));
+        assert_eq!(run_all(&mut mem[..1000]), Ok(()));
+        assert_eq!(run_all(&mut []), Ok(()));
+
+        // march leaves zeros in the range, and the rest untouched
+        mem.fill(0x1234_5678);
+        assert_eq!(march(&mut mem, 16..32), Ok(()));
+        assert!(mem[16..32].iter().all(|&w| w == 0));
+        assert_eq!(mem[15], 0x1234_5678);
+        assert_eq!(mem[32], 0x1234_5678);
+    }
+
+    #[test]
+    fn test_failure() {
+        let mem = [0u32, 0xdead_beef];
+        let addr = mem.as_ptr() as usize + 4;
+
+        assert_eq!(verify(&mem, 0, 0), Ok(()));
+        assert_eq!(
+            verify(&mem, 1, 0xffff_ffff),
+            Err(Error::Memtest(Failure::new(addr, 0xffff_ffff, 0xdead_beef)))
+        );
+    }
+
+    #[test]
+    fn test_faults() {
+        let mut mem = [0u32; 1024];
+        let base = mem.as_ptr() as usize;
+
+        // data line 3 stuck high, caught by the first walking one
+        let mut faulty = Faulty::stuck(&mut mem, 0, 1 << 3, 0);
+        assert_eq!(data_bus_words(&mut faulty), failure(base, 1, 0b1001));
+
+        // data line 3 stuck low, caught by the first walking zero
+        let mut faulty = Faulty::stuck(&mut mem, 0, 0, 1 << 3);
+        assert_eq!(
+            data_bus_words(&mut faulty),
+            failure(base, 0xffff_fffe, 0xffff_fff6)
+        );
+
+        // address line 2 stuck low: word 4 aliases word 0
+        let mut faulty = Faulty::aliased(&mut mem, 1 << 2);
+        assert_eq!(
+            address_bus_words(&mut faulty),
+            failure(base + 4 * 4, 0xaaaa_aaaa, 0x5555_5555)
+        );
+
+        // bit 0 of word 5 stuck low, caught going down
+        let mut faulty = Faulty::stuck(&mut mem, 5, 0, 1);
+        assert_eq!(
+            moving_inversions_words(&mut faulty, 0),
+            failure(base + 5 * 4, 0xffff_ffff, 0xffff_fffe)
+        );
+
+        // bit 31 of word 100 stuck low, caught by the third march element
+        let mut faulty = Faulty::stuck(&mut mem, 100, 0, 1 << 31);
+        assert_eq!(
+            march_words(&mut faulty, 16..512),
+            failure(base + 100 * 4, 0xffff_ffff, 0x7fff_ffff)
+        );
+        assert_eq!(march_words(&mut faulty, 101..512), Ok(()));
+
+        // bit 7 of word 200 stuck high, caught by the first moving inversions pattern
+        let mut faulty = Faulty::stuck(&mut mem, 200, 1 << 7, 0);
+        assert_eq!(
+            run_all_words(&mut faulty),
+            failure(base + 200 * 4, 0, 1 << 7)
+        );
+    }
+}
@@ -128,6 +128,8 @@ impl Ddr {
     ///
     /// Typically, this means calling from the monitor core in Supervisor mode during bootloader initialization.
     pub fn phy_start(&mut self) {
+        let size = self.config.size;
+
         START_CFG0.iter().for_each(|cfg| {
             self.dmc_phy
                 .ac_base(cfg.reg_nr as usize)
@@ -141,13 +143,13 @@ impl Ddr {
         });
 
         // NOTE: Commented out in VF1 code
-        if cfg!(feature = "2G") {
+        if !size.is_dual_rank() {
             self.dmc_phy
                 .base(11)
                 .modify(|r, w| w.base().variant((r.bits() & 0xffff_fff0) | 0x0000_0005));
         }
 
-        START_CFG2.iter().for_each(|cfg| {
+        self.timings.pi_mr().iter().for_each(|cfg| {
             self.dmc_phy
                 .base(cfg.reg_nr as usize)
                 .modify(|r, w| w.base().variant((r.bits() & cfg.mask) | cfg.value));
@@ -174,14 +176,16 @@ impl Ddr {
 
         // PHY_WRLVL_DLY_STEP_X: 8'hC -> 8'h12
         // NOTE: This is h18 in the JH7100 code
-        // This is for G_SPEED_2133.
+        // This is 8'h12 for G_SPEED_2133.
         //    G_SPEED_2666: 0x00140000
         //    G_SPEED_3200: 0x00180000
         // TODO: try lower speed?
+        let wrlvl_dly_step = self.config.speed_grade.wrlvl_dly_step() << 16;
         [96, 352, 608, 864].iter().for_each(|&reg| {
-            self.dmc_phy
-                .ac_base(reg)
-                .modify(|r, w| w.ac_base().variant((r.bits() & 0xff00_ffff) | 0x0012_0000));
+            self.dmc_phy.ac_base(reg).modify(|r, w| {
+                w.ac_base()
+                    .variant((r.bits() & 0xff00_ffff) | wrlvl_dly_step)
+            });
         });
 
         START_CFG4.iter().for_each(|cfg| {
@@ -196,7 +200,7 @@ impl Ddr {
                 .write(|w| w.ac_base().variant(cfg.value));
         });
 
-        START_CFG6.iter().for_each(|cfg| {
+        start_cfg6(size).iter().for_each(|cfg| {
             self.dmc_phy
                 .ac_base(cfg.reg_nr as usize)
                 .modify(|r, w| w.ac_base().variant((r.bits() & cfg.mask) | cfg.value));
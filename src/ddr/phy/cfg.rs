@@ -1,15 +1,17 @@
-use crate::ddr::{MemCfg, MemSet};
+use crate::ddr::{DramSize, MemCfg, MemSet};
 use crate::{mem_cfg_arr, mem_set_arr};
 
-#[cfg(feature = "8G")]
-pub const VAL_X2: u32 = 0x3600_0000;
-#[cfg(not(feature = "8G"))]
-pub const VAL_X2: u32 = 0x6600_0000;
+/// Selects the 8G or the 2G/4G value of a size-dependent register.
+const fn by_size(size: DramSize, size_8g: u32, other: u32) -> u32 {
+    match size {
+        DramSize::Size8G => size_8g,
+        _ => other,
+    }
+}
 
-#[cfg(feature = "8G")]
-pub const VAL_X3: u32 = 0xff;
-#[cfg(not(feature = "8G"))]
-pub const VAL_X3: u32 = 0xfb;
+pub const fn val_x3(size: DramSize) -> u32 {
+    by_size(size, 0xff, 0xfb)
+}
 
 #[rustfmt::skip]
 pub const START_CFG0: [MemCfg; 41] = mem_cfg_arr![
@@ -109,59 +111,6 @@ pub const START_CFG1: [MemCfg; 28] = mem_cfg_arr![
     {0,   0xffff_fffe, 0x0000_0001},
 ];
 
-#[rustfmt::skip]
-pub const START_CFG2: [MemCfg; 36] = mem_cfg_arr![
-    //set CS0 MR13.VRCG=1
-    {247, 0xffff_ffff, 0x0000_0008},
-    //set CS1 MR13.VRCG=1
-    {249, 0xffff_ffff, 0x0000_0800},
-    //set CS2 MR13.VRCG=1
-    {252, 0xffff_ffff, 0x0000_0008},
-    //set CS3 MR13.VRCG=1
-    {254, 0xffff_ffff, 0x0000_0800},
-
-    //PI_MR11_DATA_F1_X
-    {281, 0xffff_ffff, 0x3300_0000},
-    {305, 0xffff_ffff, 0x3300_0000},
-    {329, 0xffff_ffff, 0x3300_0000},
-    {353, 0xffff_ffff, 0x3300_0000},
-
-    //PI_MR11_DATA_F2_X
-    {289, 0xffff_ffff, VAL_X2},
-    {313, 0xffff_ffff, VAL_X2},
-    {337, 0xffff_ffff, VAL_X2},
-    {361, 0xffff_ffff, VAL_X2},
-
-    //PI_MR22_DATA_F1_X
-    {282, 0xffff_ffff, 0x0016_0000},
-    {306, 0xffff_ffff, 0x0016_0000},
-    {330, 0xffff_ffff, 0x0016_0000},
-    {354, 0xffff_ffff, 0x0016_0000},
-    //PI_MR22_DATA_F2_X
-    {290, 0xffff_ffff, 0x0016_0000},
-    {314, 0xffff_ffff, 0x0016_0000},
-    {338, 0xffff_ffff, 0x0016_0000},
-    {362, 0xffff_ffff, 0x0016_0000},
-
-    {282, 0xffff_ff00, 0x17},
-    {306, 0xffff_ff00, 0x17},
-    {330, 0xffff_ff00, 0x17},
-    {354, 0xffff_ff00, 0x17},
-    {290, 0xffff_ff00, 0x17},
-    {314, 0xffff_ff00, 0x17},
-    {338, 0xffff_ff00, 0x17},
-    {362, 0xffff_ff00, 0x17},
-
-    {282, 0xffff_00ff, 0x2000},
-    {306, 0xffff_00ff, 0x2000},
-    {330, 0xffff_00ff, 0x2000},
-    {354, 0xffff_00ff, 0x2000},
-    {290, 0xffff_00ff, 0x2000},
-    {314, 0xffff_00ff, 0x2000},
-    {338, 0xffff_00ff, 0x2000},
-    {362, 0xffff_00ff, 0x2000},
-];
-
 #[rustfmt::skip]
 pub const START_CFG3: [MemCfg; 4] = mem_cfg_arr![
     {65,  0xffff_ffff, 0x0000_0100},
@@ -243,27 +192,29 @@ pub const START_CFG5: [MemSet; 21] = mem_set_arr![
 ];
 
 #[rustfmt::skip]
-pub const START_CFG6: [MemCfg; 14] = mem_cfg_arr![
-    {76,  0xff00_00f8, 0x00ff_8f07},
-    {332, 0xff00_00f8, 0x00ff_8f07},
-    {588, 0xff00_00f8, 0x00ff_8f07},
-    {844, 0xff00_00f8, 0x00ff_8f07},
-
-    {77,  0xffff_0000, 0xff8f},
-    {333, 0xffff_0000, 0xff8f},
-    {589, 0xffff_0000, 0xff8f},
-    {845, 0xffff_0000, 0xff8f},
-
-    //PHY_ADR_TSEL_SELECT_X:bit[7:0]:{ENSLICEP_ODT/DRV,PENSLICEN_ODT/DRV}
-    {1062, 0xfff_fff00, VAL_X3}, // addr5-0
-    {1318, 0xfff_fff00, VAL_X3}, // addr11-6
-    {1574, 0xfff_fff00, VAL_X3}, // addr15-12
-
-    //PHY_TST_CLK_PAD_CTRL_x
-    {1028, 0xffff_ffff, 0x0100_0000},
-    {1284, 0xffff_ffff, 0x0100_0000},
-    {1540, 0xffff_ffff, 0x0100_0000},
-];
+pub const fn start_cfg6(size: DramSize) -> [MemCfg; 14] {
+    mem_cfg_arr![
+        {76,  0xff00_00f8, 0x00ff_8f07},
+        {332, 0xff00_00f8, 0x00ff_8f07},
+        {588, 0xff00_00f8, 0x00ff_8f07},
+        {844, 0xff00_00f8, 0x00ff_8f07},
+
+        {77,  0xffff_0000, 0xff8f},
+        {333, 0xffff_0000, 0xff8f},
+        {589, 0xffff_0000, 0xff8f},
+        {845, 0xffff_0000, 0xff8f},
+
+        //PHY_ADR_TSEL_SELECT_X:bit[7:0]:{ENSLICEP_ODT/DRV,PENSLICEN_ODT/DRV}
+        {1062, 0xfff_fff00, val_x3(size)}, // addr5-0
+        {1318, 0xfff_fff00, val_x3(size)}, // addr11-6
+        {1574, 0xfff_fff00, val_x3(size)}, // addr15-12
+
+        //PHY_TST_CLK_PAD_CTRL_x
+        {1028, 0xffff_ffff, 0x0100_0000},
+        {1284, 0xffff_ffff, 0x0100_0000},
+        {1540, 0xffff_ffff, 0x0100_0000},
+    ]
+}
 
 // PHY_TST_CLK_PAD_CTRL_x
 #[rustfmt::skip]
@@ -0,0 +1,945 @@
+//! Typed DDR timings and LPDDR4 mode registers.
+//!
+//! LPDDR4 latencies, write recovery, drive strength and ODT are set through the mode registers,
+//! written by the controller (MRW), and by the PHY-independent training module (PI).
+//!
+//! The controller timing registers ([CTL_TIMING_REGS]) are generated from [ControllerTimings]. The
+//! typed fields were located by matching the reference LPDDR4-2133 values against the JEDEC
+//! timings at the 1066 MHz clock, e.g. tRAS `45` and tRC `68` in `0x628`. The other bits of these
+//! registers are not known, and are kept as raw values.
+
+use super::csr::{LPDDR4_2133_BOOT_TIMING_REGS, LPDDR4_2133_RCLK_REGS, LPDDR4_2133_TIMING_REGS};
+use super::{DramSize, MemCfg, MemSet};
+
+/// Controller register to write an MRW command to.
+pub const MRW_REG: u32 = 0x4;
+/// Controller register to trigger the MRW command.
+pub const MRW_TRIGGER_REG: u32 = 0xc;
+/// Value to trigger the MRW command.
+pub const MRW_TRIGGER: u32 = 0x2;
+
+/// Bit offset of the rank mask in an MRW command.
+pub const MRW_RANKS_SHIFT: u32 = 28;
+/// Bit offset of the mode register address in an MRW command.
+pub const MRW_ADDR_SHIFT: u32 = 16;
+
+/// Controller timing registers, in write order.
+pub const CTL_TIMING_REGS: [u32; 26] = [
+    0x620, 0x624, 0x628, 0x62c, 0x630, 0x634, 0x638, 0x63c, 0x640, 0x644, 0x648, 0x64c, 0x650,
+    0x654, 0x658, 0x65c, 0x660, 0x680, 0x684, 0x688, 0x68c, 0x690, 0x69c, 0x6a0, 0x6a4, 0x6a8,
+];
+
+/// PI registers of the MR11 data, for frequency set 1, per chip select.
+pub const PI_MR11_F1_REGS: [u32; 4] = [281, 305, 329, 353];
+/// PI registers of the MR11 data, for frequency set 2, per chip select.
+pub const PI_MR11_F2_REGS: [u32; 4] = [289, 313, 337, 361];
+/// PI registers of the MR22 data, for frequency set 1, per chip select.
+pub const PI_MR22_F1_REGS: [u32; 4] = [282, 306, 330, 354];
+/// PI registers of the MR22 data, for frequency set 2, per chip select.
+pub const PI_MR22_F2_REGS: [u32; 4] = [290, 314, 338, 362];
+
+/// LPDDR4 burst length (MR1 OP\[1:0\]).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BurstLength {
+    Bl16 = 0b00,
+    Bl32 = 0b01,
+    OnTheFly = 0b10,
+}
+
+/// LPDDR4 write recovery nWR (MR1 OP\[6:4\]), in clock cycles.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WriteRecovery {
+    Nwr6 = 0,
+    Nwr10,
+    Nwr16,
+    Nwr20,
+    Nwr24,
+    Nwr30,
+    Nwr34,
+    Nwr40,
+}
+
+/// LPDDR4 read latency RL, i.e. CAS latency (MR2 OP\[2:0\]), in clock cycles, with read DBI
+/// disabled.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReadLatency {
+    Rl6 = 0,
+    Rl10,
+    Rl14,
+    Rl20,
+    Rl24,
+    Rl28,
+    Rl32,
+    Rl36,
+}
+
+impl ReadLatency {
+    /// Gets the read latency, in clock cycles.
+    pub const fn cycles(self) -> u8 {
+        match self {
+            Self::Rl6 => 6,
+            Self::Rl10 => 10,
+            Self::Rl14 => 14,
+            Self::Rl20 => 20,
+            Self::Rl24 => 24,
+            Self::Rl28 => 28,
+            Self::Rl32 => 32,
+            Self::Rl36 => 36,
+        }
+    }
+}
+
+/// LPDDR4 write latency WL, i.e. CAS write latency (MR2 OP\[5:3\]), in clock cycles, for write
+/// latency set A.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WriteLatency {
+    Wl4 = 0,
+    Wl6,
+    Wl8,
+    Wl10,
+    Wl12,
+    Wl14,
+    Wl16,
+    Wl18,
+}
+
+impl WriteLatency {
+    /// Gets the write latency, in clock cycles.
+    pub const fn cycles(self) -> u8 {
+        4 + 2 * self as u8
+    }
+}
+
+/// LPDDR4 drive strength and ODT impedance, as a fraction of RZQ (240 Ohm).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Impedance {
+    /// Disabled, for ODT.
+    Off = 0,
+    /// 240 Ohm.
+    Rzq1,
+    /// 120 Ohm.
+    Rzq2,
+    /// 80 Ohm.
+    Rzq3,
+    /// 60 Ohm.
+    Rzq4,
+    /// 48 Ohm.
+    Rzq5,
+    /// 40 Ohm.
+    Rzq6,
+}
+
+/// LPDDR4 mode register 1: burst length, preambles, write recovery and read postamble.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Mr1 {
+    pub burst_length: BurstLength,
+    pub wr_preamble_2tck: bool,
+    pub rd_preamble_toggle: bool,
+    pub nwr: WriteRecovery,
+    pub rd_postamble_1_5tck: bool,
+}
+
+impl Mr1 {
+    /// Gets the mode register value.
+    pub const fn op(&self) -> u8 {
+        self.burst_length as u8
+            | (self.wr_preamble_2tck as u8) << 2
+            | (self.rd_preamble_toggle as u8) << 3
+            | (self.nwr as u8) << 4
+            | (self.rd_postamble_1_5tck as u8) << 7
+    }
+}
+
+/// LPDDR4 mode register 2: read and write latencies.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Mr2 {
+    pub rl: ReadLatency,
+    pub wl: WriteLatency,
+    pub wl_set_b: bool,
+    pub write_leveling: bool,
+}
+
+impl Mr2 {
+    /// Gets the mode register value.
+    pub const fn op(&self) -> u8 {
+        self.rl as u8
+            | (self.wl as u8) << 3
+            | (self.wl_set_b as u8) << 6
+            | (self.write_leveling as u8) << 7
+    }
+}
+
+/// LPDDR4 mode register 3: pull-up calibration, write postamble, drive strength and DBI.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Mr3 {
+    pub pu_cal_vddq_2_5: bool,
+    pub wr_postamble_1_5tck: bool,
+    pub ppr: bool,
+    pub drive_strength: Impedance,
+    pub dbi_rd: bool,
+    pub dbi_wr: bool,
+}
+
+impl Mr3 {
+    /// Gets the mode register value.
+    pub const fn op(&self) -> u8 {
+        self.pu_cal_vddq_2_5 as u8
+            | (self.wr_postamble_1_5tck as u8) << 1
+            | (self.ppr as u8) << 2
+            | (self.drive_strength as u8) << 3
+            | (self.dbi_rd as u8) << 6
+            | (self.dbi_wr as u8) << 7
+    }
+}
+
+/// LPDDR4 mode register 11: DQ and CA ODT.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Mr11 {
+    pub dq_odt: Impedance,
+    pub ca_odt: Impedance,
+}
+
+impl Mr11 {
+    /// Gets the mode register value.
+    pub const fn op(&self) -> u8 {
+        self.dq_odt as u8 | (self.ca_odt as u8) << 4
+    }
+}
+
+/// LPDDR4 mode register 22: SoC ODT and ODT overrides.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Mr22 {
+    pub soc_odt: Impedance,
+    pub odte_ck: bool,
+    pub odte_cs: bool,
+    pub odtd_ca: bool,
+}
+
+impl Mr22 {
+    /// Gets the mode register value.
+    pub const fn op(&self) -> u8 {
+        self.soc_odt as u8
+            | (self.odte_ck as u8) << 3
+            | (self.odte_cs as u8) << 4
+            | (self.odtd_ca as u8) << 5
+    }
+}
+
+/// Represents an LPDDR4 mode register value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModeRegister {
+    Mr1(Mr1),
+    Mr2(Mr2),
+    Mr3(Mr3),
+    Mr11(Mr11),
+    Mr22(Mr22),
+}
+
+impl ModeRegister {
+    /// Gets the mode register address.
+    pub const fn address(&self) -> u8 {
+        match self {
+            Self::Mr1(_) => 1,
+            Self::Mr2(_) => 2,
+            Self::Mr3(_) => 3,
+            Self::Mr11(_) => 11,
+            Self::Mr22(_) => 22,
+        }
+    }
+
+    /// Gets the mode register value.
+    pub const fn op(&self) -> u8 {
+        match self {
+            Self::Mr1(mr) => mr.op(),
+            Self::Mr2(mr) => mr.op(),
+            Self::Mr3(mr) => mr.op(),
+            Self::Mr11(mr) => mr.op(),
+            Self::Mr22(mr) => mr.op(),
+        }
+    }
+}
+
+/// Represents a mode register write (MRW) command issued by the controller.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ModeRegisterWrite {
+    /// Bitmask of the ranks to write, bit `n` for rank `n`.
+    pub ranks: u8,
+    pub mr: ModeRegister,
+}
+
+impl ModeRegisterWrite {
+    /// Creates a new [ModeRegisterWrite] from the provided parameters.
+    pub const fn new(ranks: u8, mr: ModeRegister) -> Self {
+        Self { ranks, mr }
+    }
+
+    /// Creates a new [ModeRegisterWrite] to all the ranks of a [DramSize].
+    pub const fn all_ranks(size: DramSize, mr: ModeRegister) -> Self {
+        Self::new((1 << size.ranks()) - 1, mr)
+    }
+
+    /// Gets the MRW command value written to [MRW_REG].
+    pub const fn bits(&self) -> u32 {
+        (self.ranks as u32) << MRW_RANKS_SHIFT
+            | (self.mr.address() as u32) << MRW_ADDR_SHIFT
+            | self.mr.op() as u32
+    }
+}
+
+// Bit field of a controller timing register.
+struct CtlField {
+    reg: u32,
+    shift: u32,
+    mask: u32,
+}
+
+impl CtlField {
+    const TRP: Self = Self::new(0x620, 0, 0xff);
+    const TRCD: Self = Self::new(0x620, 8, 0xff);
+    const TRAS: Self = Self::new(0x628, 16, 0xff);
+    const TRC: Self = Self::new(0x628, 24, 0xff);
+    const WL: Self = Self::new(0x630, 0, 0xff);
+    const RL: Self = Self::new(0x630, 8, 0xff);
+    const TRFC: Self = Self::new(0x644, 0, 0xffff);
+
+    const fn new(reg: u32, shift: u32, mask: u32) -> Self {
+        Self { reg, shift, mask }
+    }
+
+    // Inserts the field value into the register values of [CTL_TIMING_REGS].
+    fn insert(&self, regs: &mut [u32; 26], val: u32) {
+        if let Some(i) = CTL_TIMING_REGS.iter().position(|&r| r == self.reg) {
+            regs[i] = (regs[i] & !(self.mask << self.shift)) | ((val & self.mask) << self.shift);
+        }
+    }
+}
+
+/// Represents the controller timings at one DDR clock frequency, in clock cycles.
+///
+/// LPDDR4 drive strength and ODT are not set in the controller timing registers, but through
+/// the mode registers, see [Mr3] and [Mr11].
+///
+/// NOTE: tRCD and tRPpb are both 18 ns on LPDDR4, the field in `0x620[15:8]` may be either.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ControllerTimings {
+    /// ACTIVATE to READ or WRITE delay, tRCD (`0x620[15:8]`).
+    pub trcd: u8,
+    /// All-bank PRECHARGE time, tRPab (`0x620[7:0]`).
+    pub trp: u8,
+    /// Row active time, tRAS (`0x628[23:16]`).
+    pub tras: u8,
+    /// Row cycle time, tRC (`0x628[31:24]`).
+    pub trc: u8,
+    /// All-bank REFRESH cycle time, tRFCab (`0x644[15:0]`).
+    pub trfc: u16,
+    /// Read (CAS) latency, CL (`0x630[15:8]`).
+    pub rl: ReadLatency,
+    /// Write (CAS write) latency, CWL (`0x630[7:0]`).
+    pub wl: WriteLatency,
+    /// Value of the controller register `0x100`, its fields are not known.
+    pub reg_100: u32,
+    /// Values of the [CTL_TIMING_REGS], the typed fields replace their bits.
+    pub raw: [u32; 26],
+}
+
+impl ControllerTimings {
+    /// Gets the values of the [CTL_TIMING_REGS].
+    pub fn timing_regs(&self) -> [u32; 26] {
+        let mut regs = self.raw;
+
+        CtlField::TRP.insert(&mut regs, self.trp.into());
+        CtlField::TRCD.insert(&mut regs, self.trcd.into());
+        CtlField::TRAS.insert(&mut regs, self.tras.into());
+        CtlField::TRC.insert(&mut regs, self.trc.into());
+        CtlField::WL.insert(&mut regs, self.wl.cycles().into());
+        CtlField::RL.insert(&mut regs, self.rl.cycles().into());
+        CtlField::TRFC.insert(&mut regs, self.trfc.into());
+
+        regs
+    }
+
+    /// Gets the controller register writes: register `0x100`, then the [CTL_TIMING_REGS].
+    pub fn regs(&self) -> [MemSet; 27] {
+        let timing = self.timing_regs();
+        let regs: [MemSet; 26] =
+            core::array::from_fn(|i| MemSet::create(CTL_TIMING_REGS[i], timing[i]));
+
+        fill(([MemSet::create(0x100, self.reg_100)], regs), MemSet::new())
+    }
+}
+
+/// Represents the DDR timings, written during [Ddr::init](super::Ddr::init).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DdrTimings {
+    /// RCLK-related controller registers, written at the boot frequency before training.
+    pub rclk_regs: [MemSet; 9],
+    /// Controller timings at the boot frequency before training.
+    pub boot_ctl: ControllerTimings,
+    /// Mode register writes at the boot frequency before training.
+    pub boot_mrw: [ModeRegisterWrite; 5],
+    /// Controller timings at the operating frequency after training.
+    pub ctl: ControllerTimings,
+    /// Mode register writes at the operating frequency after training.
+    pub mrw: [ModeRegisterWrite; 5],
+    /// MR11 used by PHY training, for frequency sets 1 and 2.
+    pub pi_mr11: [Mr11; 2],
+    /// MR22 used by PHY training, for frequency sets 1 and 2.
+    pub pi_mr22: [Mr22; 2],
+}
+
+impl DdrTimings {
+    /// Creates the LPDDR4-2133 [DdrTimings] for a [DramSize].
+    ///
+    /// RL 20, WL 10, nWR 20, 40 Ohm drive strength, 40 Ohm DQ ODT and 80 Ohm CA ODT.
+    pub const fn lpddr4_2133(size: DramSize) -> Self {
+        const MR1_BOOT: Mr1 = Mr1 {
+            burst_length: BurstLength::OnTheFly,
+            wr_preamble_2tck: true,
+            rd_preamble_toggle: false,
+            nwr: WriteRecovery::Nwr6,
+            rd_postamble_1_5tck: false,
+        };
+        const MR1: Mr1 = Mr1 {
+            nwr: WriteRecovery::Nwr20,
+            ..MR1_BOOT
+        };
+        const MR2_BOOT: Mr2 = Mr2 {
+            rl: ReadLatency::Rl6,
+            wl: WriteLatency::Wl4,
+            wl_set_b: false,
+            write_leveling: false,
+        };
+        const MR2: Mr2 = Mr2 {
+            rl: ReadLatency::Rl20,
+            wl: WriteLatency::Wl10,
+            ..MR2_BOOT
+        };
+        const MR3: Mr3 = Mr3 {
+            pu_cal_vddq_2_5: true,
+            wr_postamble_1_5tck: false,
+            ppr: false,
+            drive_strength: Impedance::Rzq6,
+            dbi_rd: false,
+            dbi_wr: false,
+        };
+        const MR11_BOOT: Mr11 = Mr11 {
+            dq_odt: Impedance::Rzq3,
+            ca_odt: Impedance::Rzq3,
+        };
+        const MR11: Mr11 = Mr11 {
+            dq_odt: Impedance::Rzq6,
+            ca_odt: Impedance::Rzq3,
+        };
+        const MR11_RZQ6: Mr11 = Mr11 {
+            dq_odt: Impedance::Rzq6,
+            ca_odt: Impedance::Rzq6,
+        };
+        const MR22: Mr22 = Mr22 {
+            soc_odt: Impedance::Rzq6,
+            odte_ck: false,
+            odte_cs: true,
+            odtd_ca: false,
+        };
+
+        let mrw = if size.is_dual_rank() {
+            [
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr1(MR1)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr2(MR2)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr3(MR3)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr11(MR11)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr22(MR22)),
+            ]
+        } else {
+            // NOTE: reproduces the reference 2G sequence: MR1 is written twice instead of MR2,
+            // and MR11 is written to both ranks with 40 Ohm CA ODT.
+            [
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr1(MR1)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr1(MR1)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr3(MR3)),
+                ModeRegisterWrite::new(0b11, ModeRegister::Mr11(MR11_RZQ6)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr22(MR22)),
+            ]
+        };
+
+        let pi_mr11_f2 = match size {
+            DramSize::Size8G => MR11,
+            _ => MR11_RZQ6,
+        };
+
+        Self {
+            rclk_regs: LPDDR4_2133_RCLK_REGS,
+            boot_ctl: ControllerTimings {
+                trcd: 4,
+                trp: 4,
+                tras: 3,
+                trc: 7,
+                trfc: 10,
+                rl: MR2_BOOT.rl,
+                wl: MR2_BOOT.wl,
+                reg_100: 0xf8,
+                raw: LPDDR4_2133_BOOT_TIMING_REGS,
+            },
+            boot_mrw: [
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr1(MR1_BOOT)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr2(MR2_BOOT)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr3(MR3)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr11(MR11_BOOT)),
+                ModeRegisterWrite::all_ranks(size, ModeRegister::Mr22(MR22)),
+            ],
+            // 18 ns tRCD, 21 ns tRPab, 42 ns tRAS, 380 ns tRFCab
+            ctl: ControllerTimings {
+                trcd: 20,
+                trp: 23,
+                tras: 45,
+                trc: 68,
+                trfc: 406,
+                rl: MR2.rl,
+                wl: MR2.wl,
+                reg_100: 0xe0,
+                raw: LPDDR4_2133_TIMING_REGS,
+            },
+            mrw,
+            pi_mr11: [MR11_BOOT, pi_mr11_f2],
+            pi_mr22: [MR22, MR22],
+        }
+    }
+
+    /// Gets the controller register writes at the boot frequency, before training.
+    pub fn csr_boot(&self) -> [MemSet; 50] {
+        fill(
+            (
+                [
+                    MemSet::create(0x310, 0x0002_0000),
+                    MemSet::create(0x310, 0x0002_0001),
+                ],
+                self.rclk_regs,
+                self.boot_ctl.regs(),
+                mrw_sets(&self.boot_mrw),
+                [
+                    MemSet::create(0x10, 0x0000_0010),
+                    MemSet::create(0x14, 0x0000_0001),
+                ],
+            ),
+            MemSet::new(),
+        )
+    }
+
+    /// Gets the controller register writes at the operating frequency, after training.
+    pub fn csr(&self) -> [MemSet; 43] {
+        fill(
+            (
+                self.ctl.regs(),
+                mrw_sets(&self.mrw),
+                [
+                    MemSet::create(0x410, 0x0010_1010),
+                    MemSet::create(0x420, 0x0c18_1006),
+                    MemSet::create(0x424, 0x2020_0820),
+                    MemSet::create(0x428, 0x8000_0020),
+                    MemSet::create(0x0, 0x0000_0001),
+                    MemSet::create(0x108, 0x0000_3000),
+                ],
+            ),
+            MemSet::new(),
+        )
+    }
+
+    /// Gets the PI register writes of the mode register data used by PHY training.
+    pub fn pi_mr(&self) -> [MemCfg; 36] {
+        let set = |regs: [u32; 4], value: u32| regs.map(|r| MemCfg::create(r, 0xffff_ffff, value));
+        let mask =
+            |regs: [u32; 4], mask: u32, value: u32| regs.map(|r| MemCfg::create(r, mask, value));
+
+        let [mr11_f1, mr11_f2] = self.pi_mr11.map(|mr| (mr.op() as u32) << 24);
+        let [mr22_f1, mr22_f2] = self.pi_mr22.map(|mr| (mr.op() as u32) << 16);
+        let mr22_regs = [PI_MR22_F1_REGS, PI_MR22_F2_REGS];
+
+        fill(
+            (
+                // set CS0-3 MR13.VRCG=1
+                [
+                    MemCfg::create(247, 0xffff_ffff, 0x0000_0008),
+                    MemCfg::create(249, 0xffff_ffff, 0x0000_0800),
+                    MemCfg::create(252, 0xffff_ffff, 0x0000_0008),
+                    MemCfg::create(254, 0xffff_ffff, 0x0000_0800),
+                ],
+                set(PI_MR11_F1_REGS, mr11_f1),
+                set(PI_MR11_F2_REGS, mr11_f2),
+                set(PI_MR22_F1_REGS, mr22_f1),
+                set(PI_MR22_F2_REGS, mr22_f2),
+                mr22_regs.map(|regs| mask(regs, 0xffff_ff00, 0x17)),
+                mr22_regs.map(|regs| mask(regs, 0xffff_00ff, 0x2000)),
+            ),
+            MemCfg::new(),
+        )
+    }
+}
+
+// Gets the controller register writes of MRW commands.
+fn mrw_sets(mrw: &[ModeRegisterWrite; 5]) -> [MemSet; 10] {
+    let mut sets = [MemSet::new(); 10];
+
+    sets.chunks_exact_mut(2).zip(mrw).for_each(|(set, mrw)| {
+        set[0] = MemSet::create(MRW_REG, mrw.bits());
+        set[1] = MemSet::create(MRW_TRIGGER_REG, MRW_TRIGGER);
+    });
+
+    sets
+}
+
+// Fixed-length parts of a register table, concatenated by `fill`.
+trait Parts<T> {
+    // Number of items in the parts.
+    const LEN: usize;
+
+    // Copies the parts to the start of `out`, and returns the rest of `out`.
+    fn copy_to<'a>(&self, out: &'a mut [T]) -> &'a mut [T];
+}
+
+impl<T: Copy, const A: usize> Parts<T> for [T; A] {
+    const LEN: usize = A;
+
+    fn copy_to<'a>(&self, out: &'a mut [T]) -> &'a mut [T] {
+        let (head, rest) = out.split_at_mut(A);
+        head.copy_from_slice(self);
+        rest
+    }
+}
+
+impl<T: Copy, const A: usize, const B: usize> Parts<T> for [[T; A]; B] {
+    const LEN: usize = A * B;
+
+    fn copy_to<'a>(&self, out: &'a mut [T]) -> &'a mut [T] {
+        let (head, rest) = out.split_at_mut(A * B);
+        head.copy_from_slice(self.as_flattened());
+        rest
+    }
+}
+
+macro_rules! impl_parts {
+    ($($part:ident),+) => {
+        impl<T, $($part: Parts<T>),+> Parts<T> for ($($part,)+) {
+            const LEN: usize = 0 $(+ $part::LEN)+;
+
+            #[allow(non_snake_case)]
+            fn copy_to<'a>(&self, out: &'a mut [T]) -> &'a mut [T] {
+                let ($($part,)+) = self;
+                $(let out = $part.copy_to(out);)+
+                out
+            }
+        }
+    };
+}
+
+impl_parts!(A, B);
+impl_parts!(A, B, C);
+impl_parts!(A, B, C, D);
+impl_parts!(A, B, C, D, E);
+impl_parts!(A, B, C, D, E, F);
+impl_parts!(A, B, C, D, E, F, G);
+
+// Concatenates `parts` into an array of exactly `N` items, the length is checked at compile time.
+fn fill<T: Copy, P: Parts<T>, const N: usize>(parts: P, default: T) -> [T; N] {
+    const { assert!(P::LEN == N, "register table length mismatch") };
+
+    let mut out = [default; N];
+    parts.copy_to(&mut out);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mem_cfg_arr, mem_set_arr};
+
+    // Reference tables, as defined before typed timings.
+
+    const fn by_rank(size: DramSize, dual: u32, single: u32) -> u32 {
+        if size.is_dual_rank() {
+            dual
+        } else {
+            single
+        }
+    }
+
+    const fn by_size(size: DramSize, size_8g: u32, other: u32) -> u32 {
+        match size {
+            DramSize::Size8G => size_8g,
+            _ => other,
+        }
+    }
+
+    const fn cfg1_x1(size: DramSize) -> u32 {
+        by_rank(size, 0x3001_0006, 0x1001_0006)
+    }
+
+    const fn cfg1_x2(size: DramSize) -> u32 {
+        by_rank(size, 0x3002_0000, 0x1002_0000)
+    }
+
+    const fn cfg1_x3(size: DramSize) -> u32 {
+        by_rank(size, 0x3003_0031, 0x1003_0031)
+    }
+
+    const fn cfg1_x4(size: DramSize) -> u32 {
+        by_rank(size, 0x300b_0033, 0x100b_0033)
+    }
+
+    const fn cfg1_x5(size: DramSize) -> u32 {
+        by_rank(size, 0x3016_0016, 0x1016_0016)
+    }
+
+    const fn cfg3_x1(size: DramSize) -> u32 {
+        by_rank(size, 0x3001_0036, 0x1001_0036)
+    }
+
+    const fn cfg3_x2(size: DramSize) -> u32 {
+        by_rank(size, 0x3002_001b, 0x1001_0036)
+    }
+
+    const fn cfg3_x3(size: DramSize) -> u32 {
+        by_rank(size, 0x3003_0031, 0x1003_0031)
+    }
+
+    const fn cfg3_x4(size: DramSize) -> u32 {
+        by_rank(size, 0x300b_0036, 0x300b_0066)
+    }
+
+    const fn cfg3_x5(size: DramSize) -> u32 {
+        by_rank(size, 0x3016_0016, 0x1016_0016)
+    }
+
+    #[rustfmt::skip]
+    const fn ddr_csr_cfg2(size: DramSize) -> [MemSet; 50] {
+        mem_set_arr![
+            {0x310, 0x0002_0000},
+            {0x310, 0x0002_0001},
+            // Write down RCLK-related CRs
+            {0x600, 0x002e_0176},
+            {0x604, 0x002e_0176},
+            {0x608, 0x0017_00bb},
+            {0x60c, 0x000b_005d},
+            {0x610, 0x0005_002e},
+            {0x614, 0x0002_0017},
+            {0x618, 0x0002_0017},
+            {0x61c, 0x0002_0017},
+            {0x678, 0x0000_0019},
+            {0x100, 0x0000_00f8},
+            {0x620, 0x0303_0404},
+            {0x624, 0x0403_0505},
+            {0x628, 0x0703_0884},
+            {0x62c, 0x1315_0401},
+            {0x630, 0x1715_0604},
+            {0x634, 0x0011_0000},
+            {0x638, 0x200a_0a08},
+            {0x63c, 0x1730_f803},
+            {0x640, 0x000a_0c00},
+            {0x644, 0xa005_000a},
+            {0x648, 0x0000_0000},
+            {0x64c, 0x0008_1306},
+            {0x650, 0x0407_0304},
+            {0x654, 0x0000_0404},
+            {0x658, 0x0000_0060},
+            {0x65c, 0x0003_0008},
+            {0x660, 0x0000_0000},
+            {0x680, 0x0000_0603},
+            {0x684, 0x0100_0202},
+            {0x688, 0x0413_040d},
+            {0x68c, 0x2000_2420},
+            {0x690, 0x0014_0000},
+            {0x69c, 0x0124_0074},
+            {0x6a0, 0x0000_0000},
+            {0x6a4, 0x2024_0c00},
+            {0x6a8, 0x0004_0000},
+
+            {0x4, cfg1_x1(size)},
+            {0xc, 0x00000002},
+            {0x4, cfg1_x2(size)},
+            {0xc, 0x00000002},
+            {0x4, cfg1_x3(size)},
+            {0xc, 0x00000002},
+            {0x4, cfg1_x4(size)},
+            {0xc, 0x00000002},
+            {0x4, cfg1_x5(size)},
+            {0xc, 0x00000002},
+
+            {0x10, 0x0000_0010},
+            {0x14, 0x0000_0001},
+        ]
+    }
+
+    #[rustfmt::skip]
+    const fn ddr_csr_cfg4(size: DramSize) -> [MemSet; 43] {
+        mem_set_arr![
+            {0x100, 0x000000e0},
+            {0x620, 0x04041417},
+            {0x624, 0x09110609},
+            {0x628, 0x442d0994},
+            {0x62c, 0x271e102b},
+            {0x630, 0x291b140a},
+            {0x634, 0x001c0000},
+            {0x638, 0x200f0f08},
+            {0x63c, 0x29420a06},
+            {0x640, 0x019e1fc1},
+            {0x644, 0x10cb0196},
+            {0x648, 0x00000000},
+            {0x64c, 0x00082714},
+            {0x650, 0x16442f0d},
+            {0x654, 0x00001916},
+            {0x658, 0x00000060},
+            {0x65c, 0x00600020},
+            {0x660, 0x00000000},
+            {0x680, 0x0c00040f},
+            {0x684, 0x03000604},
+            {0x688, 0x0515040d},
+            {0x68c, 0x20002c20},
+            {0x690, 0x00140000},
+            {0x69c, 0x01240074},
+            {0x6a0, 0x00000000},
+            {0x6a4, 0x202c0c00},
+            {0x6a8, 0x00040000},
+
+            {0x4, cfg3_x1(size)},
+            {0xc, 0x00000002},
+            {0x4, cfg3_x2(size)},
+            {0xc, 0x00000002},
+            {0x4, cfg3_x3(size)},
+            {0xc, 0x00000002},
+            {0x4, cfg3_x4(size)},
+            {0xc, 0x00000002},
+            {0x4, cfg3_x5(size)},
+            {0xc, 0x00000002},
+
+            {0x410, 0x00101010},
+            {0x420, 0x0c181006},
+            {0x424, 0x20200820},
+            {0x428, 0x80000020},
+            {0x0,   0x00000001},
+            {0x108, 0x00003000},
+        ]
+    }
+
+    const fn val_x2(size: DramSize) -> u32 {
+        by_size(size, 0x3600_0000, 0x6600_0000)
+    }
+
+    #[rustfmt::skip]
+    const fn start_cfg2(size: DramSize) -> [MemCfg; 36] {
+        mem_cfg_arr![
+            //set CS0 MR13.VRCG=1
+            {247, 0xffff_ffff, 0x0000_0008},
+            //set CS1 MR13.VRCG=1
+            {249, 0xffff_ffff, 0x0000_0800},
+            //set CS2 MR13.VRCG=1
+            {252, 0xffff_ffff, 0x0000_0008},
+            //set CS3 MR13.VRCG=1
+            {254, 0xffff_ffff, 0x0000_0800},
+
+            //PI_MR11_DATA_F1_X
+            {281, 0xffff_ffff, 0x3300_0000},
+            {305, 0xffff_ffff, 0x3300_0000},
+            {329, 0xffff_ffff, 0x3300_0000},
+            {353, 0xffff_ffff, 0x3300_0000},
+
+            //PI_MR11_DATA_F2_X
+            {289, 0xffff_ffff, val_x2(size)},
+            {313, 0xffff_ffff, val_x2(size)},
+            {337, 0xffff_ffff, val_x2(size)},
+            {361, 0xffff_ffff, val_x2(size)},
+
+            //PI_MR22_DATA_F1_X
+            {282, 0xffff_ffff, 0x0016_0000},
+            {306, 0xffff_ffff, 0x0016_0000},
+            {330, 0xffff_ffff, 0x0016_0000},
+            {354, 0xffff_ffff, 0x0016_0000},
+            //PI_MR22_DATA_F2_X
+            {290, 0xffff_ffff, 0x0016_0000},
+            {314, 0xffff_ffff, 0x0016_0000},
+            {338, 0xffff_ffff, 0x0016_0000},
+            {362, 0xffff_ffff, 0x0016_0000},
+
+            {282, 0xffff_ff00, 0x17},
+            {306, 0xffff_ff00, 0x17},
+            {330, 0xffff_ff00, 0x17},
+            {354, 0xffff_ff00, 0x17},
+            {290, 0xffff_ff00, 0x17},
+            {314, 0xffff_ff00, 0x17},
+            {338, 0xffff_ff00, 0x17},
+            {362, 0xffff_ff00, 0x17},
+
+            {282, 0xffff_00ff, 0x2000},
+            {306, 0xffff_00ff, 0x2000},
+            {330, 0xffff_00ff, 0x2000},
+            {354, 0xffff_00ff, 0x2000},
+            {290, 0xffff_00ff, 0x2000},
+            {314, 0xffff_00ff, 0x2000},
+            {338, 0xffff_00ff, 0x2000},
+            {362, 0xffff_00ff, 0x2000},
+        ]
+    }
+
+    const SIZES: [DramSize; 3] = [DramSize::Size2G, DramSize::Size4G, DramSize::Size8G];
+
+    #[test]
+    fn test_mode_registers() {
+        let timings = DdrTimings::lpddr4_2133(DramSize::Size4G);
+        let ops = timings.mrw.map(|mrw| (mrw.mr.address(), mrw.mr.op()));
+
+        assert_eq!(
+            ops,
+            [(1, 0x36), (2, 0x1b), (3, 0x31), (11, 0x36), (22, 0x16)]
+        );
+        assert_eq!(timings.mrw[1].bits(), 0x3002_001b);
+        assert_eq!(
+            DdrTimings::lpddr4_2133(DramSize::Size2G).boot_mrw[0].bits(),
+            0x1001_0006
+        );
+    }
+
+    #[test]
+    fn test_controller_timings() {
+        let ctl = ControllerTimings {
+            trcd: 0x12,
+            trp: 0x34,
+            tras: 0x56,
+            trc: 0x78,
+            trfc: 0x1abc,
+            rl: ReadLatency::Rl14,
+            wl: WriteLatency::Wl8,
+            reg_100: 0xe0,
+            raw: [0xffff_ffff; 26],
+        };
+        let regs = ctl.regs();
+
+        assert_eq!(regs[0], MemSet::create(0x100, 0xe0));
+        assert_eq!(regs[1], MemSet::create(0x620, 0xffff_1234));
+        assert_eq!(regs[3], MemSet::create(0x628, 0x7856_ffff));
+        assert_eq!(regs[5], MemSet::create(0x630, 0xffff_0e08));
+        assert_eq!(regs[10], MemSet::create(0x644, 0xffff_1abc));
+        assert_eq!(regs[2], MemSet::create(0x624, 0xffff_ffff));
+    }
+
+    #[test]
+    fn test_lpddr4_2133_tables() {
+        SIZES.into_iter().for_each(|size| {
+            let timings = DdrTimings::lpddr4_2133(size);
+
+            assert_eq!(timings.csr_boot(), ddr_csr_cfg2(size), "{size:?}");
+            assert_eq!(timings.csr(), ddr_csr_cfg4(size), "{size:?}");
+            assert_eq!(timings.pi_mr(), start_cfg2(size), "{size:?}");
+        });
+    }
+}
@@ -1,9 +1,31 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
 use embedded_hal::delay::DelayNs;
 use riscv::register::{cycle, mcycle};
 
-/// Clock rate of the U74 core (in Hertz): 1,500 MHz
+use crate::clocks::ClockRates;
+
+/// Clock rate of the U74 core (in Hertz) before the clock tree is frozen: 1,500 MHz
 pub const U74_CLOCK_HZ: u64 = 1_500_000_000;
 
+static U74_TICKS_SECOND: AtomicU64 = AtomicU64::new(U74_CLOCK_HZ);
+
+/// Gets the current clock rate of the U74 core (in Hertz), used by [u74_mdelay] and [u74_udelay].
+///
+/// Follows the CPU core rate of the last [`Clocks::freeze`](crate::clocks::Clocks::freeze),
+/// defaults to [U74_CLOCK_HZ] until then.
+pub fn u74_clock_hz() -> u64 {
+    U74_TICKS_SECOND.load(Ordering::Relaxed)
+}
+
+/// Sets the current clock rate of the U74 core (in Hertz), used by [u74_mdelay] and [u74_udelay].
+///
+/// Called by [`Clocks::freeze`](crate::clocks::Clocks::freeze), also after scaling the CPU clock
+/// with [`Clocks::set_cpu_frequency`](crate::clocks::Clocks::set_cpu_frequency).
+pub fn set_u74_clock_hz(hz: u64) {
+    U74_TICKS_SECOND.store(hz, Ordering::Relaxed);
+}
+
 /// Machine mode cycle counter (`mcycle`) as a delay provider
 #[derive(Clone, Copy)]
 pub struct McycleDelay {
@@ -60,10 +82,20 @@ impl DelayNs for UcycleDelay {
 
 /// Convenience function to get a [McycleDelay] for the `U74` riscv core.
 pub fn u74_mdelay() -> McycleDelay {
-    McycleDelay::new(U74_CLOCK_HZ)
+    McycleDelay::new(u74_clock_hz())
 }
 
 /// Convenience function to get a [UcycleDelay] for the `U74` riscv core.
 pub fn u74_udelay() -> UcycleDelay {
-    UcycleDelay::new(U74_CLOCK_HZ)
+    UcycleDelay::new(u74_clock_hz())
+}
+
+/// Convenience function to get a [McycleDelay] at the CPU core rate of a frozen clock tree.
+pub fn cpu_mdelay(rates: &ClockRates) -> McycleDelay {
+    McycleDelay::new(rates.cpu_hz())
+}
+
+/// Convenience function to get a [UcycleDelay] at the CPU core rate of a frozen clock tree.
+pub fn cpu_udelay(rates: &ClockRates) -> UcycleDelay {
+    UcycleDelay::new(rates.cpu_hz())
 }
@@ -38,27 +38,71 @@
 //! }
 //! ```
 //!
-//! ### WIP
+//! ### Alternate functions
 //!
 //! `JH7110` SoCs use a pin multiplexer to configure pins for specialized functionality (I2C, SPI, etc.).
 //!
-//! Work is on-going to provide high-level interfaces to configure specialized functions for GPIO pins.
+//! Peripheral signals implementing [Function] are routed to GPIO pads 0-63 with [Gpio::into_alternate]:
 //!
-//! Low-level configuration can currently be achieved through the `jh71xx-pac` crate which is re-exported as `jh71xx_hal::pac`.
+//! ```no_run
+//! use jh71xx_hal::{pac, gpio};
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//! let padcfg = dp.sys_pinctrl.padcfg();
+//!
+//! let _tx = gpio::get_gpio(padcfg.gpio5()).into_alternate::<gpio::Uart0Tx>();
+//! let _rx = gpio::get_gpio(padcfg.gpio6()).into_alternate::<gpio::Uart0Rx>();
+//!
+//! let _scl = gpio::get_gpio(padcfg.gpio57()).into_alternate::<gpio::I2c0Scl>();
+//! let _sda = gpio::get_gpio(padcfg.gpio58()).into_alternate::<gpio::I2c0Sda>();
+//! ```
+//!
+//! ### Async inputs
+//!
+//! With the `async` feature, enabled inputs implement [`embedded_hal_async::digital::Wait`],
+//! woken by [wake_pending] from the SYS GPIO interrupt handler:
+//!
+//! ```ignore
+//! use jh71xx_hal::{pac, gpio};
+//! use embedded_hal_async::digital::Wait;
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//! let mut button = gpio::get_gpio(dp.sys_pinctrl.padcfg().gpio38())
+//!     .into_enabled_input()
+//!     .into_input_pull_up();
+//!
+//! button.wait_for_falling_edge().await.unwrap();
+//! ```
+//!
+//! Low-level configuration can also be achieved through the `jh71xx-pac` crate which is re-exported as `jh71xx_hal::pac`.
 
-use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+use core::marker::PhantomData;
 
-use crate::pac::SysPinctrl;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
 
+mod aon;
 mod config;
+mod dynamic;
 mod error;
 mod functions;
+mod gmac;
+mod irq;
 mod pad;
+mod port;
+#[cfg(feature = "async")]
+mod wait;
 
+pub use aon::*;
 pub use config::*;
+pub use dynamic::*;
 pub use error::*;
 pub use functions::*;
+pub use gmac::*;
+pub use irq::*;
 pub use pad::*;
+pub use port::*;
+#[cfg(feature = "async")]
+pub use wait::*;
 
 /// Configures the GPIO as enabled.
 pub struct Enabled;
@@ -76,10 +120,58 @@ pub struct HiZ;
 pub struct PullDown;
 /// Configures the GPIO as pull-up input.
 pub struct PullUp;
+/// Configures the GPIO as open-drain output: driven low, or released to the pull-up.
+pub struct OpenDrain;
+
+/// Configures the GPIO as an alternate function, see [Function].
+pub struct Alternate<F: Function>(PhantomData<F>);
+
+impl<F: Function> Alternate<F> {
+    /// Creates a new [Alternate].
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<F: Function> Default for Alternate<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// Not-important placeholder
 pub struct Nop;
 
+/// Represents a [Gpio] routed to the alternate function `F`.
+pub type AlternatePin<'g, GPIO, F> = Gpio<'g, GPIO, Enabled, Alternate<F>, Nop>;
+
+/// Converts a [Gpio] in any state into an [AlternatePin].
+///
+/// Used by peripheral constructors taking pins, e.g. [I2c::with_pins](crate::i2c::I2c::with_pins),
+/// to route the pins they are given.
+pub trait IntoAlternate<'g, GPIO: GpioCfg> {
+    /// Converts into an [AlternatePin], see [Gpio::into_alternate].
+    fn into_alternate<F: Function>(self) -> AlternatePin<'g, GPIO, F>;
+}
+
+impl<'g, GPIO: GpioCfg, ENABLED, DIRECTION, MODE> IntoAlternate<'g, GPIO>
+    for Gpio<'g, GPIO, ENABLED, DIRECTION, MODE>
+{
+    fn into_alternate<F: Function>(self) -> AlternatePin<'g, GPIO, F> {
+        Gpio::into_alternate::<F>(self)
+    }
+}
+
+// Rejects AON functions at compile time, they can only be routed to the AON pads.
+struct SysFunction<F: Function>(PhantomData<F>);
+
+impl<F: Function> SysFunction<F> {
+    const CHECK: () = assert!(
+        !F::GROUP.is_aon(),
+        "AON functions can only be routed to the AON pads"
+    );
+}
+
 /// Configures how to drive a GPIO.
 #[repr(u8)]
 pub enum OutputConfig {
@@ -186,6 +278,106 @@ impl<'g, GPIO: GpioCfg, ENABLED, DIRECTION, MODE> Gpio<'g, GPIO, ENABLED, DIRECT
         }
     }
 
+    /// Converts the [Gpio] into an open-drain output, released high.
+    ///
+    /// DOUT is fixed low, and the output driver is enabled to drive the pin low. The input stays
+    /// enabled to read back the line, e.g. for bit-banged I2C or 1-Wire. The pull configuration
+    /// is kept, an external pull-up is usually needed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{gpio, pac};
+    /// use embedded_hal::digital::{InputPin, OutputPin};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut sda = gpio::get_gpio(dp.sys_pinctrl.padcfg().gpio58()).into_open_drain_output();
+    ///
+    /// sda.set_low().unwrap();
+    /// sda.set_high().unwrap();
+    ///
+    /// // the line is held low by another device
+    /// let _stretched = sda.is_low().unwrap();
+    /// ```
+    pub fn into_open_drain_output(mut self) -> Gpio<'g, GPIO, Enabled, Output, OpenDrain> {
+        self.periph.input_enable(true);
+        self.disable_output();
+        self.drive_output(OutputDrive::Low);
+
+        Gpio {
+            periph: self.periph,
+            _enabled: Enabled,
+            _direction: Output,
+            _mode: OpenDrain,
+        }
+    }
+
+    /// Converts the [Gpio] into an alternate function pin, routing the signal `F` through the
+    /// GPIO function multiplexer.
+    ///
+    /// The input is enabled if `F` has a GPI function. Only GPIO pads 0-63 are routed, the
+    /// function multiplexer is left untouched for other pads.
+    ///
+    /// The GPI routing is kept when the pin is converted again, until another pad is routed to
+    /// the same signal.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{gpio, pac};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let gpio5 = gpio::get_gpio(dp.sys_pinctrl.padcfg().gpio5());
+    ///
+    /// let _uart0_tx = gpio5.into_alternate::<gpio::Uart0Tx>();
+    /// ```
+    pub fn into_alternate<F: Function>(self) -> AlternatePin<'g, GPIO, F> {
+        let () = SysFunction::<F>::CHECK;
+
+        self.periph.input_enable(F::GPI.is_some());
+        set_sys_function::<F>(GPIO::pad());
+
+        Gpio {
+            periph: self.periph,
+            _enabled: Enabled,
+            _direction: Alternate::new(),
+            _mode: Nop,
+        }
+    }
+
+    /// Reads the [PadConfig] of the [Gpio] pad.
+    pub fn pad_config(&self) -> Result<PadConfig> {
+        PadConfig::read(Pad::from(GPIO::pad()))
+    }
+
+    /// Writes the electrical options of a [PadConfig] to the [Gpio] pad, with a single register
+    /// write.
+    ///
+    /// The input enable and pull resistors are kept, they are part of the [Gpio] type state.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{gpio, pac};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut gpio0 = gpio::get_gpio(dp.sys_pinctrl.padcfg().gpio0()).into_enabled_output();
+    ///
+    /// let cfg = gpio::PadConfig::new()
+    ///     .with_drive_strength(gpio::DriveStrength::Twelve)
+    ///     .with_slew(gpio::Slew::Fast);
+    ///
+    /// gpio0.set_pad_config(cfg).unwrap();
+    /// ```
+    pub fn set_pad_config(&mut self, config: PadConfig) -> Result<()> {
+        let current = self.pad_config()?;
+
+        config
+            .with_input_enable(current.input_enable)
+            .with_pull(current.pull)
+            .apply(Pad::from(GPIO::pad()))
+    }
+
     fn enable_output(&mut self) {
         self.config_output(OutputConfig::Low);
     }
@@ -195,7 +387,6 @@ impl<'g, GPIO: GpioCfg, ENABLED, DIRECTION, MODE> Gpio<'g, GPIO, ENABLED, DIRECT
     }
 
     fn config_output(&mut self, config: OutputConfig) {
-        let pinctrl = unsafe { &*SysPinctrl::ptr() };
         let pad = GPIO::pad();
 
         // StarFive uses a GPIO muxer, the lower two bits of the DOEN registers configure
@@ -205,565 +396,105 @@ impl<'g, GPIO: GpioCfg, ENABLED, DIRECTION, MODE> Gpio<'g, GPIO, ENABLED, DIRECT
         // - setting SET_LOW to zero enables the GPIO driven low
         // - setting SET_HIGH to one enables the GPIO driven high
         // - setting SET_LOW(1) SET_HIGH(0) brings the GPIO to driven neutral
-        let cfg: u8 = config.into();
-        match pad {
-            0 => pinctrl
-                .gpo_doen()
-                .gpo_doen0()
-                .modify(|_, w| w.doen0().variant(cfg)),
-            1 => pinctrl
-                .gpo_doen()
-                .gpo_doen0()
-                .modify(|_, w| w.doen1().variant(cfg)),
-            2 => pinctrl
-                .gpo_doen()
-                .gpo_doen0()
-                .modify(|_, w| w.doen2().variant(cfg)),
-            3 => pinctrl
-                .gpo_doen()
-                .gpo_doen0()
-                .modify(|_, w| w.doen3().variant(cfg)),
-            4 => pinctrl
-                .gpo_doen()
-                .gpo_doen1()
-                .modify(|_, w| w.doen4().variant(cfg)),
-            5 => pinctrl
-                .gpo_doen()
-                .gpo_doen1()
-                .modify(|_, w| w.doen5().variant(cfg)),
-            6 => pinctrl
-                .gpo_doen()
-                .gpo_doen1()
-                .modify(|_, w| w.doen6().variant(cfg)),
-            7 => pinctrl
-                .gpo_doen()
-                .gpo_doen1()
-                .modify(|_, w| w.doen7().variant(cfg)),
-            8 => pinctrl
-                .gpo_doen()
-                .gpo_doen2()
-                .modify(|_, w| w.doen8().variant(cfg)),
-            9 => pinctrl
-                .gpo_doen()
-                .gpo_doen2()
-                .modify(|_, w| w.doen9().variant(cfg)),
-            10 => pinctrl
-                .gpo_doen()
-                .gpo_doen2()
-                .modify(|_, w| w.doen10().variant(cfg)),
-            11 => pinctrl
-                .gpo_doen()
-                .gpo_doen2()
-                .modify(|_, w| w.doen11().variant(cfg)),
-            12 => pinctrl
-                .gpo_doen()
-                .gpo_doen3()
-                .modify(|_, w| w.doen12().variant(cfg)),
-            13 => pinctrl
-                .gpo_doen()
-                .gpo_doen3()
-                .modify(|_, w| w.doen13().variant(cfg)),
-            14 => pinctrl
-                .gpo_doen()
-                .gpo_doen3()
-                .modify(|_, w| w.doen14().variant(cfg)),
-            15 => pinctrl
-                .gpo_doen()
-                .gpo_doen3()
-                .modify(|_, w| w.doen15().variant(cfg)),
-            16 => pinctrl
-                .gpo_doen()
-                .gpo_doen4()
-                .modify(|_, w| w.doen16().variant(cfg)),
-            17 => pinctrl
-                .gpo_doen()
-                .gpo_doen4()
-                .modify(|_, w| w.doen17().variant(cfg)),
-            18 => pinctrl
-                .gpo_doen()
-                .gpo_doen4()
-                .modify(|_, w| w.doen18().variant(cfg)),
-            19 => pinctrl
-                .gpo_doen()
-                .gpo_doen4()
-                .modify(|_, w| w.doen19().variant(cfg)),
-            20 => pinctrl
-                .gpo_doen()
-                .gpo_doen5()
-                .modify(|_, w| w.doen20().variant(cfg)),
-            21 => pinctrl
-                .gpo_doen()
-                .gpo_doen5()
-                .modify(|_, w| w.doen21().variant(cfg)),
-            22 => pinctrl
-                .gpo_doen()
-                .gpo_doen5()
-                .modify(|_, w| w.doen22().variant(cfg)),
-            23 => pinctrl
-                .gpo_doen()
-                .gpo_doen5()
-                .modify(|_, w| w.doen23().variant(cfg)),
-            24 => pinctrl
-                .gpo_doen()
-                .gpo_doen6()
-                .modify(|_, w| w.doen24().variant(cfg)),
-            25 => pinctrl
-                .gpo_doen()
-                .gpo_doen6()
-                .modify(|_, w| w.doen25().variant(cfg)),
-            26 => pinctrl
-                .gpo_doen()
-                .gpo_doen6()
-                .modify(|_, w| w.doen26().variant(cfg)),
-            27 => pinctrl
-                .gpo_doen()
-                .gpo_doen6()
-                .modify(|_, w| w.doen27().variant(cfg)),
-            28 => pinctrl
-                .gpo_doen()
-                .gpo_doen7()
-                .modify(|_, w| w.doen28().variant(cfg)),
-            29 => pinctrl
-                .gpo_doen()
-                .gpo_doen7()
-                .modify(|_, w| w.doen29().variant(cfg)),
-            30 => pinctrl
-                .gpo_doen()
-                .gpo_doen7()
-                .modify(|_, w| w.doen30().variant(cfg)),
-            31 => pinctrl
-                .gpo_doen()
-                .gpo_doen7()
-                .modify(|_, w| w.doen31().variant(cfg)),
-            32 => pinctrl
-                .gpo_doen()
-                .gpo_doen8()
-                .modify(|_, w| w.doen32().variant(cfg)),
-            33 => pinctrl
-                .gpo_doen()
-                .gpo_doen8()
-                .modify(|_, w| w.doen33().variant(cfg)),
-            34 => pinctrl
-                .gpo_doen()
-                .gpo_doen8()
-                .modify(|_, w| w.doen34().variant(cfg)),
-            35 => pinctrl
-                .gpo_doen()
-                .gpo_doen8()
-                .modify(|_, w| w.doen35().variant(cfg)),
-            36 => pinctrl
-                .gpo_doen()
-                .gpo_doen9()
-                .modify(|_, w| w.doen36().variant(cfg)),
-            37 => pinctrl
-                .gpo_doen()
-                .gpo_doen9()
-                .modify(|_, w| w.doen37().variant(cfg)),
-            38 => pinctrl
-                .gpo_doen()
-                .gpo_doen9()
-                .modify(|_, w| w.doen38().variant(cfg)),
-            39 => pinctrl
-                .gpo_doen()
-                .gpo_doen9()
-                .modify(|_, w| w.doen39().variant(cfg)),
-            40 => pinctrl
-                .gpo_doen()
-                .gpo_doen10()
-                .modify(|_, w| w.doen40().variant(cfg)),
-            41 => pinctrl
-                .gpo_doen()
-                .gpo_doen10()
-                .modify(|_, w| w.doen41().variant(cfg)),
-            42 => pinctrl
-                .gpo_doen()
-                .gpo_doen10()
-                .modify(|_, w| w.doen42().variant(cfg)),
-            43 => pinctrl
-                .gpo_doen()
-                .gpo_doen10()
-                .modify(|_, w| w.doen43().variant(cfg)),
-            44 => pinctrl
-                .gpo_doen()
-                .gpo_doen11()
-                .modify(|_, w| w.doen44().variant(cfg)),
-            45 => pinctrl
-                .gpo_doen()
-                .gpo_doen11()
-                .modify(|_, w| w.doen45().variant(cfg)),
-            46 => pinctrl
-                .gpo_doen()
-                .gpo_doen11()
-                .modify(|_, w| w.doen46().variant(cfg)),
-            47 => pinctrl
-                .gpo_doen()
-                .gpo_doen11()
-                .modify(|_, w| w.doen47().variant(cfg)),
-            48 => pinctrl
-                .gpo_doen()
-                .gpo_doen12()
-                .modify(|_, w| w.doen48().variant(cfg)),
-            49 => pinctrl
-                .gpo_doen()
-                .gpo_doen12()
-                .modify(|_, w| w.doen49().variant(cfg)),
-            50 => pinctrl
-                .gpo_doen()
-                .gpo_doen12()
-                .modify(|_, w| w.doen50().variant(cfg)),
-            51 => pinctrl
-                .gpo_doen()
-                .gpo_doen12()
-                .modify(|_, w| w.doen51().variant(cfg)),
-            52 => pinctrl
-                .gpo_doen()
-                .gpo_doen13()
-                .modify(|_, w| w.doen52().variant(cfg)),
-            53 => pinctrl
-                .gpo_doen()
-                .gpo_doen13()
-                .modify(|_, w| w.doen53().variant(cfg)),
-            54 => pinctrl
-                .gpo_doen()
-                .gpo_doen13()
-                .modify(|_, w| w.doen54().variant(cfg)),
-            55 => pinctrl
-                .gpo_doen()
-                .gpo_doen13()
-                .modify(|_, w| w.doen55().variant(cfg)),
-            56 => pinctrl
-                .gpo_doen()
-                .gpo_doen14()
-                .modify(|_, w| w.doen56().variant(cfg)),
-            57 => pinctrl
-                .gpo_doen()
-                .gpo_doen14()
-                .modify(|_, w| w.doen57().variant(cfg)),
-            58 => pinctrl
-                .gpo_doen()
-                .gpo_doen14()
-                .modify(|_, w| w.doen58().variant(cfg)),
-            59 => pinctrl
-                .gpo_doen()
-                .gpo_doen14()
-                .modify(|_, w| w.doen59().variant(cfg)),
-            60 => pinctrl
-                .gpo_doen()
-                .gpo_doen15()
-                .modify(|_, w| w.doen60().variant(cfg)),
-            61 => pinctrl
-                .gpo_doen()
-                .gpo_doen15()
-                .modify(|_, w| w.doen61().variant(cfg)),
-            62 => pinctrl
-                .gpo_doen()
-                .gpo_doen15()
-                .modify(|_, w| w.doen62().variant(cfg)),
-            63 => pinctrl
-                .gpo_doen()
-                .gpo_doen15()
-                .modify(|_, w| w.doen63().variant(cfg)),
-            _ => (),
+        if pad < FMUX_PADS {
+            modify_sys_fmux(SYS_DOEN, pad, SYS_DOEN_MASK, config.into());
+        }
+    }
+
+    #[inline]
+    fn drive_output(&mut self, drive: OutputDrive) {
+        let pad = GPIO::pad();
+
+        if pad < FMUX_PADS {
+            modify_sys_fmux(SYS_DOUT, pad, SYS_DOUT_MASK, drive.into());
         }
     }
 }
 
 impl<'g, GPIO: GpioCfg> Gpio<'g, GPIO, Enabled, Output, Nop> {
     /// Sets whether the [Gpio] is driven high.
+    ///
+    /// The DOUT byte of the pad is computed from the pad number, setting the pin is a single
+    /// read-modify-write of one FMUX register, suitable for bit-banging.
+    #[inline]
     pub fn set_pin(&mut self, high: bool) {
         self.drive_output(high.into())
     }
 
-    fn drive_output(&mut self, drive: OutputDrive) {
-        let pinctrl = unsafe { &*SysPinctrl::ptr() };
+    /// Gets whether the [Gpio] is driven high, read back from DOUT.
+    pub fn is_pin_set(&self) -> bool {
         let pad = GPIO::pad();
 
-        let val: u8 = drive.into();
-        match pad {
-            0 => pinctrl
-                .gpo_dout()
-                .gpo_dout0()
-                .modify(|_, w| w.dout0().variant(val)),
-            1 => pinctrl
-                .gpo_dout()
-                .gpo_dout0()
-                .modify(|_, w| w.dout1().variant(val)),
-            2 => pinctrl
-                .gpo_dout()
-                .gpo_dout0()
-                .modify(|_, w| w.dout2().variant(val)),
-            3 => pinctrl
-                .gpo_dout()
-                .gpo_dout0()
-                .modify(|_, w| w.dout3().variant(val)),
-            4 => pinctrl
-                .gpo_dout()
-                .gpo_dout1()
-                .modify(|_, w| w.dout4().variant(val)),
-            5 => pinctrl
-                .gpo_dout()
-                .gpo_dout1()
-                .modify(|_, w| w.dout5().variant(val)),
-            6 => pinctrl
-                .gpo_dout()
-                .gpo_dout1()
-                .modify(|_, w| w.dout6().variant(val)),
-            7 => pinctrl
-                .gpo_dout()
-                .gpo_dout1()
-                .modify(|_, w| w.dout7().variant(val)),
-            8 => pinctrl
-                .gpo_dout()
-                .gpo_dout2()
-                .modify(|_, w| w.dout8().variant(val)),
-            9 => pinctrl
-                .gpo_dout()
-                .gpo_dout2()
-                .modify(|_, w| w.dout9().variant(val)),
-            10 => pinctrl
-                .gpo_dout()
-                .gpo_dout2()
-                .modify(|_, w| w.dout10().variant(val)),
-            11 => pinctrl
-                .gpo_dout()
-                .gpo_dout2()
-                .modify(|_, w| w.dout11().variant(val)),
-            12 => pinctrl
-                .gpo_dout()
-                .gpo_dout3()
-                .modify(|_, w| w.dout12().variant(val)),
-            13 => pinctrl
-                .gpo_dout()
-                .gpo_dout3()
-                .modify(|_, w| w.dout13().variant(val)),
-            14 => pinctrl
-                .gpo_dout()
-                .gpo_dout3()
-                .modify(|_, w| w.dout14().variant(val)),
-            15 => pinctrl
-                .gpo_dout()
-                .gpo_dout3()
-                .modify(|_, w| w.dout15().variant(val)),
-            16 => pinctrl
-                .gpo_dout()
-                .gpo_dout4()
-                .modify(|_, w| w.dout16().variant(val)),
-            17 => pinctrl
-                .gpo_dout()
-                .gpo_dout4()
-                .modify(|_, w| w.dout17().variant(val)),
-            18 => pinctrl
-                .gpo_dout()
-                .gpo_dout4()
-                .modify(|_, w| w.dout18().variant(val)),
-            19 => pinctrl
-                .gpo_dout()
-                .gpo_dout4()
-                .modify(|_, w| w.dout19().variant(val)),
-            20 => pinctrl
-                .gpo_dout()
-                .gpo_dout5()
-                .modify(|_, w| w.dout20().variant(val)),
-            21 => pinctrl
-                .gpo_dout()
-                .gpo_dout5()
-                .modify(|_, w| w.dout21().variant(val)),
-            22 => pinctrl
-                .gpo_dout()
-                .gpo_dout5()
-                .modify(|_, w| w.dout22().variant(val)),
-            23 => pinctrl
-                .gpo_dout()
-                .gpo_dout5()
-                .modify(|_, w| w.dout23().variant(val)),
-            24 => pinctrl
-                .gpo_dout()
-                .gpo_dout6()
-                .modify(|_, w| w.dout24().variant(val)),
-            25 => pinctrl
-                .gpo_dout()
-                .gpo_dout6()
-                .modify(|_, w| w.dout25().variant(val)),
-            26 => pinctrl
-                .gpo_dout()
-                .gpo_dout6()
-                .modify(|_, w| w.dout26().variant(val)),
-            27 => pinctrl
-                .gpo_dout()
-                .gpo_dout6()
-                .modify(|_, w| w.dout27().variant(val)),
-            28 => pinctrl
-                .gpo_dout()
-                .gpo_dout7()
-                .modify(|_, w| w.dout28().variant(val)),
-            29 => pinctrl
-                .gpo_dout()
-                .gpo_dout7()
-                .modify(|_, w| w.dout29().variant(val)),
-            30 => pinctrl
-                .gpo_dout()
-                .gpo_dout7()
-                .modify(|_, w| w.dout30().variant(val)),
-            31 => pinctrl
-                .gpo_dout()
-                .gpo_dout7()
-                .modify(|_, w| w.dout31().variant(val)),
-            32 => pinctrl
-                .gpo_dout()
-                .gpo_dout8()
-                .modify(|_, w| w.dout32().variant(val)),
-            33 => pinctrl
-                .gpo_dout()
-                .gpo_dout8()
-                .modify(|_, w| w.dout33().variant(val)),
-            34 => pinctrl
-                .gpo_dout()
-                .gpo_dout8()
-                .modify(|_, w| w.dout34().variant(val)),
-            35 => pinctrl
-                .gpo_dout()
-                .gpo_dout8()
-                .modify(|_, w| w.dout35().variant(val)),
-            36 => pinctrl
-                .gpo_dout()
-                .gpo_dout9()
-                .modify(|_, w| w.dout36().variant(val)),
-            37 => pinctrl
-                .gpo_dout()
-                .gpo_dout9()
-                .modify(|_, w| w.dout37().variant(val)),
-            38 => pinctrl
-                .gpo_dout()
-                .gpo_dout9()
-                .modify(|_, w| w.dout38().variant(val)),
-            39 => pinctrl
-                .gpo_dout()
-                .gpo_dout9()
-                .modify(|_, w| w.dout39().variant(val)),
-            40 => pinctrl
-                .gpo_dout()
-                .gpo_dout10()
-                .modify(|_, w| w.dout40().variant(val)),
-            41 => pinctrl
-                .gpo_dout()
-                .gpo_dout10()
-                .modify(|_, w| w.dout41().variant(val)),
-            42 => pinctrl
-                .gpo_dout()
-                .gpo_dout10()
-                .modify(|_, w| w.dout42().variant(val)),
-            43 => pinctrl
-                .gpo_dout()
-                .gpo_dout10()
-                .modify(|_, w| w.dout43().variant(val)),
-            44 => pinctrl
-                .gpo_dout()
-                .gpo_dout11()
-                .modify(|_, w| w.dout44().variant(val)),
-            45 => pinctrl
-                .gpo_dout()
-                .gpo_dout11()
-                .modify(|_, w| w.dout45().variant(val)),
-            46 => pinctrl
-                .gpo_dout()
-                .gpo_dout11()
-                .modify(|_, w| w.dout46().variant(val)),
-            47 => pinctrl
-                .gpo_dout()
-                .gpo_dout11()
-                .modify(|_, w| w.dout47().variant(val)),
-            48 => pinctrl
-                .gpo_dout()
-                .gpo_dout12()
-                .modify(|_, w| w.dout48().variant(val)),
-            49 => pinctrl
-                .gpo_dout()
-                .gpo_dout12()
-                .modify(|_, w| w.dout49().variant(val)),
-            50 => pinctrl
-                .gpo_dout()
-                .gpo_dout12()
-                .modify(|_, w| w.dout50().variant(val)),
-            51 => pinctrl
-                .gpo_dout()
-                .gpo_dout12()
-                .modify(|_, w| w.dout51().variant(val)),
-            52 => pinctrl
-                .gpo_dout()
-                .gpo_dout13()
-                .modify(|_, w| w.dout52().variant(val)),
-            53 => pinctrl
-                .gpo_dout()
-                .gpo_dout13()
-                .modify(|_, w| w.dout53().variant(val)),
-            54 => pinctrl
-                .gpo_dout()
-                .gpo_dout13()
-                .modify(|_, w| w.dout54().variant(val)),
-            55 => pinctrl
-                .gpo_dout()
-                .gpo_dout13()
-                .modify(|_, w| w.dout55().variant(val)),
-            56 => pinctrl
-                .gpo_dout()
-                .gpo_dout14()
-                .modify(|_, w| w.dout56().variant(val)),
-            57 => pinctrl
-                .gpo_dout()
-                .gpo_dout14()
-                .modify(|_, w| w.dout57().variant(val)),
-            58 => pinctrl
-                .gpo_dout()
-                .gpo_dout14()
-                .modify(|_, w| w.dout58().variant(val)),
-            59 => pinctrl
-                .gpo_dout()
-                .gpo_dout14()
-                .modify(|_, w| w.dout59().variant(val)),
-            60 => pinctrl
-                .gpo_dout()
-                .gpo_dout15()
-                .modify(|_, w| w.dout60().variant(val)),
-            61 => pinctrl
-                .gpo_dout()
-                .gpo_dout15()
-                .modify(|_, w| w.dout61().variant(val)),
-            62 => pinctrl
-                .gpo_dout()
-                .gpo_dout15()
-                .modify(|_, w| w.dout62().variant(val)),
-            63 => pinctrl
-                .gpo_dout()
-                .gpo_dout15()
-                .modify(|_, w| w.dout63().variant(val)),
-            _ => (),
-        }
+        pad < FMUX_PADS && read_sys_fmux(SYS_DOUT, pad, SYS_DOUT_MASK) == GpoFunction::HIGH
+    }
+}
+
+impl<'g, GPIO: GpioCfg> Gpio<'g, GPIO, Enabled, Output, OpenDrain> {
+    /// Sets whether the open-drain [Gpio] is released high, or driven low.
+    #[inline]
+    pub fn set_pin(&mut self, high: bool) {
+        self.config_output(if high {
+            OutputConfig::Neutral
+        } else {
+            OutputConfig::Low
+        });
+    }
+
+    /// Gets whether the open-drain [Gpio] is released high, read back from DOEN.
+    pub fn is_pin_set(&self) -> bool {
+        let pad = GPIO::pad();
+
+        pad >= FMUX_PADS || read_sys_fmux(SYS_DOEN, pad, SYS_DOEN_MASK) != GpenFunction::ENABLE
+    }
+
+    /// Gets whether the line of the open-drain [Gpio] is high.
+    pub fn bit_is_set(&self) -> bool {
+        sys_input(GPIO::pad())
     }
 }
 
 impl<'g, GPIO: GpioCfg, MODE> Gpio<'g, GPIO, Enabled, Input, MODE> {
     /// Gets whether the input pin is set.
     pub fn bit_is_set(&self) -> bool {
-        // [`IOIRQ_15`] and [`IOIRQ_16`] are the GPIO sync registers, for GPIO 0-31 and 32-63
-        // respectively.
-        //
-        // SAFETY:
-        //
-        // It is safe to access to IOIRQ15/16 because they are `read-only`.
-        // Their values are only changed by the hardware.
-        let pinctrl = unsafe { &*SysPinctrl::ptr() };
+        sys_input(GPIO::pad())
+    }
 
-        let pad = GPIO::pad();
-        let pad_per_reg = u32::from(Pad::Gpio32);
+    /// Enables the interrupt of the input pin, triggered by `edge`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{gpio, pac};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let mut button = gpio::get_gpio(dp.sys_pinctrl.padcfg().gpio38())
+    ///     .into_enabled_input()
+    ///     .into_input_pull_up();
+    ///
+    /// button.listen(gpio::Edge::Falling);
+    ///
+    /// // from the SYS GPIO interrupt handler
+    /// if button.is_interrupt_pending() {
+    ///     button.clear_interrupt();
+    /// }
+    /// ```
+    pub fn listen(&mut self, edge: Edge) {
+        listen(Pad::from(GPIO::pad()), edge);
+    }
 
-        if pad < pad_per_reg {
-            (pinctrl.ioirq().ioirq15().read().bits() >> pad) & 0x1 != 0
-        } else if pad < u32::from(Pad::Gpio63) {
-            let idx = pad.saturating_sub(pad_per_reg);
-            (pinctrl.ioirq().ioirq16().read().bits() >> idx) & 0x1 != 0
-        } else {
-            false
-        }
+    /// Disables the interrupt of the input pin.
+    pub fn unlisten(&mut self) {
+        unlisten(Pad::from(GPIO::pad()));
+    }
+
+    /// Gets whether the interrupt of the input pin is pending.
+    pub fn is_interrupt_pending(&self) -> bool {
+        is_interrupt_pending(Pad::from(GPIO::pad()))
+    }
+
+    /// Clears the pending edge interrupt of the input pin.
+    pub fn clear_interrupt(&mut self) {
+        clear_interrupt(Pad::from(GPIO::pad()));
     }
 
     /// Converts the [Gpio] into a high-impedance input.
@@ -821,6 +552,48 @@ impl<'g, GPIO: GpioCfg> OutputPin for Gpio<'g, GPIO, Enabled, Output, Nop> {
     }
 }
 
+impl<'g, GPIO: GpioCfg> StatefulOutputPin for Gpio<'g, GPIO, Enabled, Output, Nop> {
+    fn is_set_high(&mut self) -> Result<bool> {
+        Ok(self.is_pin_set())
+    }
+
+    fn is_set_low(&mut self) -> Result<bool> {
+        Ok(!self.is_pin_set())
+    }
+}
+
+impl<'g, GPIO: GpioCfg> OutputPin for Gpio<'g, GPIO, Enabled, Output, OpenDrain> {
+    fn set_low(&mut self) -> Result<()> {
+        self.set_pin(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<()> {
+        self.set_pin(true);
+        Ok(())
+    }
+}
+
+impl<'g, GPIO: GpioCfg> StatefulOutputPin for Gpio<'g, GPIO, Enabled, Output, OpenDrain> {
+    fn is_set_high(&mut self) -> Result<bool> {
+        Ok(self.is_pin_set())
+    }
+
+    fn is_set_low(&mut self) -> Result<bool> {
+        Ok(!self.is_pin_set())
+    }
+}
+
+impl<'g, GPIO: GpioCfg> InputPin for Gpio<'g, GPIO, Enabled, Output, OpenDrain> {
+    fn is_low(&mut self) -> Result<bool> {
+        self.is_high().map(|v| !v)
+    }
+
+    fn is_high(&mut self) -> Result<bool> {
+        Ok(self.bit_is_set())
+    }
+}
+
 impl<'g, GPIO: GpioCfg, MODE> InputPin for Gpio<'g, GPIO, Enabled, Input, MODE> {
     fn is_low(&mut self) -> Result<bool> {
         self.is_high().map(|v| !v)
@@ -0,0 +1,425 @@
+//! AON (always-on) GPIO pads: RGPIO0-3.
+//!
+//! Register layout follows the Linux [`pinctrl-starfive-jh7110-aon`](https://github.com/torvalds/linux/blob/master/drivers/pinctrl/starfive/pinctrl-starfive-jh7110-aon.c) driver.
+//!
+//! The AON pads stay powered in low-power states, they are the only pads able to output PWM4-7
+//! and the 32k clock, or to wake up the PMU.
+//!
+//! ```no_run
+//! use jh71xx_hal::{gpio, pac};
+//! use embedded_hal::digital::OutputPin;
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//!
+//! let mut led = gpio::get_aon_gpio(&dp.aon_pinctrl, gpio::AonPad::Rgpio0).into_enabled_output();
+//! led.set_high().unwrap();
+//!
+//! let _pwm4 = gpio::get_aon_gpio(&dp.aon_pinctrl, gpio::AonPad::Rgpio1)
+//!     .into_alternate::<gpio::AonPwm4>();
+//! ```
+
+use core::marker::PhantomData;
+
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+use super::{
+    clear_aon_interrupt, is_aon_interrupt_pending, listen_aon, unlisten_aon, Alternate, Disabled,
+    DriveStrength, Edge, Enabled, Error, Function, HiZ, Input, Nop, Output, PullDown, PullUp,
+    Result, SchmittTrigger, Slew, GPI_PAD_OFFSET, PADCFG_DS_MASK, PADCFG_DS_SHIFT, PADCFG_IE,
+    PADCFG_PD, PADCFG_PU, PADCFG_SLEW, PADCFG_SMT,
+};
+use crate::pac;
+
+/// AON GPO enable register index, one byte per pad.
+pub(crate) const AON_DOEN: usize = 0x0 >> 2;
+/// AON GPO output register index, one byte per pad.
+pub(crate) const AON_DOUT: usize = 0x4 >> 2;
+/// AON GPI register index, one byte per GPI function.
+pub(crate) const AON_GPI: usize = 0x8 >> 2;
+/// AON GPIO input register index, one bit per pad.
+pub(crate) const AON_GPIOIN: usize = 0x2c >> 2;
+/// AON pad configuration register index of RGPIO0, following the TESTEN pad.
+pub(crate) const AON_PADCFG: usize = 0x34 >> 2;
+
+/// AON GPO function driving the pad low.
+pub const AON_GPO_LOW: u8 = 0;
+/// AON GPO function driving the pad high.
+pub const AON_GPO_HIGH: u8 = 1;
+
+/// AON GPEN function index enabling the output driver.
+pub const AON_GPEN_ENABLE: u8 = 0;
+/// AON GPEN function index disabling the output driver.
+pub const AON_GPEN_DISABLE: u8 = 1;
+
+/// Represents one of the AON GPIO pads.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AonPad {
+    Rgpio0 = 0,
+    Rgpio1 = 1,
+    Rgpio2 = 2,
+    Rgpio3 = 3,
+}
+
+impl AonPad {
+    /// Gets the AON pad `n`.
+    pub const fn new(n: u32) -> Result<Self> {
+        match n {
+            0 => Ok(Self::Rgpio0),
+            1 => Ok(Self::Rgpio1),
+            2 => Ok(Self::Rgpio2),
+            3 => Ok(Self::Rgpio3),
+            _ => Err(Error::InvalidPad(n)),
+        }
+    }
+
+    /// Gets the bit offset of the pad byte in the AON FMUX registers.
+    pub const fn shift(self) -> u32 {
+        (self as u32) * 8
+    }
+}
+
+impl From<AonPad> for u32 {
+    fn from(val: AonPad) -> Self {
+        val as u32
+    }
+}
+
+// Modifies the pad byte of an AON FMUX register.
+fn modify_aon_fmux(index: usize, pad: AonPad, val: u8) {
+    modify_aon_reg(index, |r| {
+        (r & !(0xff << pad.shift())) | ((val as u32) << pad.shift())
+    });
+}
+
+fn read_aon_reg(index: usize) -> u32 {
+    // SAFETY: `index` is one of the 32-bit registers of the AON pinctrl block
+    unsafe {
+        (pac::AonPinctrl::ptr() as *const u32)
+            .add(index)
+            .read_volatile()
+    }
+}
+
+// Modifies an AON pinctrl register in a critical section: the FMUX registers hold the bytes of
+// all four AON pads.
+fn modify_aon_reg(index: usize, f: impl FnOnce(u32) -> u32) {
+    critical_section::with(|_| {
+        // SAFETY: `index` is one of the 32-bit registers of the AON pinctrl block, and the
+        // critical section keeps other contexts from modifying it between the read and the write.
+        unsafe {
+            let reg = (pac::AonPinctrl::ptr() as *mut u32).add(index);
+
+            reg.write_volatile(f(reg.read_volatile()));
+        }
+    })
+}
+
+// Gets the pad configuration register index of an AON pad.
+pub(crate) const fn aon_padcfg_index(pad: AonPad) -> usize {
+    AON_PADCFG + pad as usize
+}
+
+// Replaces the drive strength field of a pad configuration value.
+pub(crate) const fn padcfg_with_drive_strength(cfg: u32, ds: DriveStrength) -> u32 {
+    (cfg & !PADCFG_DS_MASK) | (((ds as u32) << PADCFG_DS_SHIFT) & PADCFG_DS_MASK)
+}
+
+// Sets or clears the `bits` of a pad configuration value.
+pub(crate) const fn padcfg_with(cfg: u32, bits: u32, set: bool) -> u32 {
+    if set {
+        cfg | bits
+    } else {
+        cfg & !bits
+    }
+}
+
+/// Routes an AON GPO function (see [`AonGpoFunction`](super::AonGpoFunction)) to an AON pad,
+/// and enables the output driver.
+pub fn set_aon_output_function(pad: AonPad, function: u8) {
+    modify_aon_fmux(AON_DOUT, pad, function);
+    modify_aon_fmux(AON_DOEN, pad, AON_GPEN_ENABLE);
+}
+
+/// Disables the output driver of an AON pad, and routes the constant low function to it.
+pub fn disable_aon_output(pad: AonPad) {
+    modify_aon_fmux(AON_DOEN, pad, AON_GPEN_DISABLE);
+    modify_aon_fmux(AON_DOUT, pad, 0);
+}
+
+/// Routes an AON GPI function (see [`AonGpiFunction`](super::AonGpiFunction)) from an AON pad.
+pub fn set_aon_input_function(pad: AonPad, function: u8) {
+    let shift = ((function % 4) as u32) * 8;
+    let index = AON_GPI + (function / 4) as usize;
+    let val = pad as u8 + GPI_PAD_OFFSET;
+
+    modify_aon_reg(index, |r| (r & !(0xff << shift)) | ((val as u32) << shift));
+}
+
+// Rejects SYS functions at compile time, they can only be routed to the SYS pads.
+struct AonFunction<F: Function>(PhantomData<F>);
+
+impl<F: Function> AonFunction<F> {
+    const CHECK: () = assert!(
+        F::GROUP.is_aon(),
+        "SYS functions can only be routed to the SYS pads"
+    );
+}
+
+/// Represents an [AonGpio] routed to the AON alternate function `F`.
+pub type AonAlternatePin<'g, F> = AonGpio<'g, Enabled, Alternate<F>, Nop>;
+
+/// Represents an AON GPIO pad on a JH71xx-based board.
+pub struct AonGpio<'g, ENABLED, DIRECTION, MODE> {
+    pad: AonPad,
+    _pinctrl: PhantomData<&'g pac::AonPinctrl>,
+    _enabled: ENABLED,
+    _direction: DIRECTION,
+    _mode: MODE,
+}
+
+impl<'g, ENABLED, DIRECTION, MODE> AonGpio<'g, ENABLED, DIRECTION, MODE> {
+    /// Gets the [AonPad] of the [AonGpio].
+    pub const fn pad(&self) -> AonPad {
+        self.pad
+    }
+
+    /// Gets the drive-strength (in milliamps) of the pad output.
+    pub fn drive_strength(&self) -> DriveStrength {
+        (((self.padcfg() & PADCFG_DS_MASK) >> PADCFG_DS_SHIFT) as u8).into()
+    }
+
+    /// Sets the drive-strength (in milliamps) of the pad output.
+    pub fn set_drive_strength(&mut self, drive_strength: DriveStrength) {
+        self.modify_padcfg(|r| padcfg_with_drive_strength(r, drive_strength));
+    }
+
+    /// Gets the slew control rate of the pad.
+    pub fn slew(&self) -> Slew {
+        (self.padcfg() & PADCFG_SLEW != 0).into()
+    }
+
+    /// Sets the slew control rate of the pad.
+    pub fn set_slew(&mut self, slew: Slew) {
+        self.modify_padcfg(|r| padcfg_with(r, PADCFG_SLEW, slew.into()));
+    }
+
+    /// Gets the Schmitt Trigger configuration of the pad.
+    pub fn schmitt_trigger(&self) -> SchmittTrigger {
+        (self.padcfg() & PADCFG_SMT != 0).into()
+    }
+
+    /// Sets the Schmitt Trigger configuration of the pad.
+    pub fn set_schmitt_trigger(&mut self, trigger: SchmittTrigger) {
+        self.modify_padcfg(|r| padcfg_with(r, PADCFG_SMT, trigger.into()));
+    }
+
+    /// Converts the [AonGpio] into a disabled pin.
+    pub fn into_disabled(mut self) -> AonGpio<'g, Disabled, Nop, Nop> {
+        self.input_enable(false);
+        disable_aon_output(self.pad);
+
+        self.into_state(Disabled, Nop, Nop)
+    }
+
+    /// Converts the [AonGpio] into an enabled input.
+    pub fn into_enabled_input(mut self) -> AonGpio<'g, Enabled, Input, HiZ> {
+        self.input_enable(true);
+        self.set_pull(false, false);
+        disable_aon_output(self.pad);
+
+        self.into_state(Enabled, Input, HiZ)
+    }
+
+    /// Converts the [AonGpio] into an enabled output, driven low.
+    pub fn into_enabled_output(mut self) -> AonGpio<'g, Enabled, Output, Nop> {
+        self.input_enable(false);
+        set_aon_output_function(self.pad, AON_GPO_LOW);
+
+        self.into_state(Enabled, Output, Nop)
+    }
+
+    /// Converts the [AonGpio] into an alternate function pin, routing the AON signal `F` through
+    /// the AON function multiplexer.
+    ///
+    /// The input is enabled if `F` has a GPI function.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{gpio, pac};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let rgpio3 = gpio::get_aon_gpio(&dp.aon_pinctrl, gpio::AonPad::Rgpio3);
+    ///
+    /// let _wakeup = rgpio3.into_alternate::<gpio::PmuWakeup0>();
+    /// ```
+    pub fn into_alternate<F: Function>(mut self) -> AonAlternatePin<'g, F> {
+        let () = AonFunction::<F>::CHECK;
+
+        self.input_enable(F::GPI.is_some());
+
+        modify_aon_fmux(AON_DOUT, self.pad, F::DOUT);
+        modify_aon_fmux(AON_DOEN, self.pad, F::DOEN);
+
+        if let Some(gpi) = F::GPI {
+            set_aon_input_function(self.pad, gpi);
+        }
+
+        self.into_state(Enabled, Alternate::new(), Nop)
+    }
+
+    fn into_state<E, D, M>(self, enabled: E, direction: D, mode: M) -> AonGpio<'g, E, D, M> {
+        AonGpio {
+            pad: self.pad,
+            _pinctrl: PhantomData,
+            _enabled: enabled,
+            _direction: direction,
+            _mode: mode,
+        }
+    }
+
+    fn padcfg(&self) -> u32 {
+        read_aon_reg(aon_padcfg_index(self.pad))
+    }
+
+    fn modify_padcfg(&mut self, f: impl FnOnce(u32) -> u32) {
+        modify_aon_reg(aon_padcfg_index(self.pad), f);
+    }
+
+    fn input_enable(&mut self, enable: bool) {
+        self.modify_padcfg(|r| padcfg_with(r, PADCFG_IE, enable));
+    }
+
+    fn set_pull(&mut self, up: bool, down: bool) {
+        self.modify_padcfg(|r| padcfg_with(padcfg_with(r, PADCFG_PU, up), PADCFG_PD, down));
+    }
+}
+
+impl<'g> AonGpio<'g, Enabled, Output, Nop> {
+    /// Sets whether the [AonGpio] is driven high.
+    pub fn set_pin(&mut self, high: bool) {
+        modify_aon_fmux(
+            AON_DOUT,
+            self.pad,
+            if high { AON_GPO_HIGH } else { AON_GPO_LOW },
+        );
+    }
+}
+
+impl<'g, MODE> AonGpio<'g, Enabled, Input, MODE> {
+    /// Gets whether the input pin is set.
+    pub fn bit_is_set(&self) -> bool {
+        read_aon_reg(AON_GPIOIN) & (1 << self.pad as u32) != 0
+    }
+
+    /// Enables the interrupt of the input pin, triggered by `edge`.
+    ///
+    /// The handler of the [AON_GPIO_IRQ](super::AON_GPIO_IRQ) finds the pins that fired with
+    /// [dispatch_aon](super::dispatch_aon).
+    pub fn listen(&mut self, edge: Edge) {
+        listen_aon(self.pad, edge);
+    }
+
+    /// Disables the interrupt of the input pin.
+    pub fn unlisten(&mut self) {
+        unlisten_aon(self.pad);
+    }
+
+    /// Gets whether the interrupt of the input pin is pending.
+    pub fn is_interrupt_pending(&self) -> bool {
+        is_aon_interrupt_pending(self.pad)
+    }
+
+    /// Clears the pending edge interrupt of the input pin.
+    pub fn clear_interrupt(&mut self) {
+        clear_aon_interrupt(self.pad);
+    }
+
+    /// Converts the [AonGpio] into a high-impedance input.
+    pub fn into_input_high_z(mut self) -> AonGpio<'g, Enabled, Input, HiZ> {
+        self.set_pull(false, false);
+        self.into_state(Enabled, Input, HiZ)
+    }
+
+    /// Converts the [AonGpio] into a pull-down input.
+    pub fn into_input_pull_down(mut self) -> AonGpio<'g, Enabled, Input, PullDown> {
+        self.set_pull(false, true);
+        self.into_state(Enabled, Input, PullDown)
+    }
+
+    /// Converts the [AonGpio] into a pull-up input.
+    pub fn into_input_pull_up(mut self) -> AonGpio<'g, Enabled, Input, PullUp> {
+        self.set_pull(true, false);
+        self.into_state(Enabled, Input, PullUp)
+    }
+}
+
+impl<'g, ENABLED, DIRECTION, MODE> ErrorType for AonGpio<'g, ENABLED, DIRECTION, MODE> {
+    type Error = Error;
+}
+
+impl<'g> OutputPin for AonGpio<'g, Enabled, Output, Nop> {
+    fn set_low(&mut self) -> Result<()> {
+        self.set_pin(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<()> {
+        self.set_pin(true);
+        Ok(())
+    }
+}
+
+impl<'g, MODE> InputPin for AonGpio<'g, Enabled, Input, MODE> {
+    fn is_low(&mut self) -> Result<bool> {
+        self.is_high().map(|v| !v)
+    }
+
+    fn is_high(&mut self) -> Result<bool> {
+        Ok(self.bit_is_set())
+    }
+}
+
+/// Creates a new [AonGpio].
+///
+/// Example:
+///
+/// ```no_run
+/// use jh71xx_hal::{gpio, pac};
+///
+/// let dp = pac::Peripherals::take().unwrap();
+/// let rgpio0 = gpio::get_aon_gpio(&dp.aon_pinctrl, gpio::AonPad::Rgpio0);
+/// ```
+pub fn get_aon_gpio(_pinctrl: &pac::AonPinctrl, pad: AonPad) -> AonGpio<'_, Disabled, Nop, Nop> {
+    AonGpio {
+        pad,
+        _pinctrl: PhantomData,
+        _enabled: Disabled,
+        _direction: Nop,
+        _mode: Nop,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aon_pad() {
+        assert_eq!(AonPad::new(3), Ok(AonPad::Rgpio3));
+        assert_eq!(AonPad::new(4), Err(Error::InvalidPad(4)));
+        assert_eq!(AonPad::Rgpio2.shift(), 16);
+
+        assert_eq!(aon_padcfg_index(AonPad::Rgpio0), 0x34 >> 2);
+        assert_eq!(aon_padcfg_index(AonPad::Rgpio3), 0x40 >> 2);
+    }
+
+    #[test]
+    fn test_padcfg() {
+        assert_eq!(padcfg_with_drive_strength(0xff, DriveStrength::Two), 0xf9);
+        assert_eq!(padcfg_with_drive_strength(0, DriveStrength::Twelve), 0b110);
+        assert_eq!(padcfg_with(0, PADCFG_IE | PADCFG_PU, true), 0b1001);
+        assert_eq!(padcfg_with(0xff, PADCFG_PD, false), 0xef);
+    }
+}
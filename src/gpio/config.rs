@@ -1,5 +1,33 @@
+use super::{
+    aon_padcfg_index, AonPad, Error, Pad, Result, PAD_GPIO0, PAD_QSPI_DATA3, PAD_QSPI_SCLK,
+    PAD_SD0_STRB,
+};
+use crate::pac;
 use crate::pac::sys_pinctrl::padcfg::*;
 
+/// SYS pad configuration register index of GPIO0, one register per pad.
+///
+/// The GMAC1 pads (75-88) have their own register layout, see
+/// [GmacPadConfig](super::GmacPadConfig).
+pub(crate) const SYS_PADCFG: usize = 0x120 >> 2;
+
+/// Pad configuration input enable bit.
+pub(crate) const PADCFG_IE: u32 = 1 << 0;
+/// Pad configuration drive strength shift, two bits wide.
+pub(crate) const PADCFG_DS_SHIFT: u32 = 1;
+/// Pad configuration drive strength mask.
+pub(crate) const PADCFG_DS_MASK: u32 = 0b11 << PADCFG_DS_SHIFT;
+/// Pad configuration pull-up bit.
+pub(crate) const PADCFG_PU: u32 = 1 << 3;
+/// Pad configuration pull-down bit.
+pub(crate) const PADCFG_PD: u32 = 1 << 4;
+/// Pad configuration slew rate bit.
+pub(crate) const PADCFG_SLEW: u32 = 1 << 5;
+/// Pad configuration Schmitt trigger bit.
+pub(crate) const PADCFG_SMT: u32 = 1 << 6;
+/// Pad configuration Power-on-Start bit.
+pub(crate) const PADCFG_POS: u32 = 1 << 7;
+
 /// Configuration options for the GPIO drive-strength (in milliamps).
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -110,6 +138,8 @@ impl From<SchmittTrigger> for u8 {
 }
 
 /// Configuration options for the GPIO Power-on-Start feature.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum PowerOnStart {
     /// No active pull-down on loss of core power.
     Disable = 0,
@@ -144,6 +174,256 @@ impl From<PowerOnStart> for u8 {
     }
 }
 
+/// Configuration options for the GPIO pull resistors.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Pull {
+    /// No pull resistor (high-impedance input).
+    #[default]
+    None,
+    /// Pull-up resistor.
+    Up,
+    /// Pull-down resistor.
+    Down,
+}
+
+impl Pull {
+    /// Creates a new [Pull].
+    pub const fn new() -> Self {
+        Self::None
+    }
+}
+
+/// Represents the electrical configuration of a SYS or AON pad.
+///
+/// All options are written with a single register access, see [apply](Self::apply) and
+/// [apply_aon](Self::apply_aon).
+///
+/// The GMAC pads have a different register layout, and are configured with
+/// [GmacPadConfig](super::GmacPadConfig).
+///
+/// # Example
+///
+/// ```no_run
+/// use jh71xx_hal::gpio;
+///
+/// let cfg = gpio::PadConfig::new()
+///     .with_input_enable(true)
+///     .with_drive_strength(gpio::DriveStrength::Eight)
+///     .with_slew(gpio::Slew::Fast)
+///     .with_pull(gpio::Pull::Up);
+///
+/// cfg.apply_all(&[gpio::Pad::Sd0Cmd, gpio::Pad::Sd0Data0, gpio::Pad::Sd0Data1]).unwrap();
+///
+/// let _clk = gpio::PadConfig::read(gpio::Pad::Sd0Clk).unwrap();
+///
+/// gpio::PadConfig::new()
+///     .with_input_enable(true)
+///     .with_pull(gpio::Pull::Down)
+///     .apply_aon(gpio::AonPad::Rgpio2);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PadConfig {
+    /// Input enable.
+    pub input_enable: bool,
+    /// Output drive strength.
+    pub drive_strength: DriveStrength,
+    /// Pull resistors.
+    pub pull: Pull,
+    /// Slew rate control.
+    pub slew: Slew,
+    /// Schmitt trigger hysteresis.
+    pub schmitt_trigger: SchmittTrigger,
+    /// Power-on-Start active pull-down.
+    pub power_on_start: PowerOnStart,
+}
+
+impl PadConfig {
+    /// Creates a new [PadConfig].
+    pub const fn new() -> Self {
+        Self {
+            input_enable: false,
+            drive_strength: DriveStrength::Two,
+            pull: Pull::None,
+            slew: Slew::Slow,
+            schmitt_trigger: SchmittTrigger::Disable,
+            power_on_start: PowerOnStart::Disable,
+        }
+    }
+
+    /// Builder function that sets the input enable.
+    pub const fn with_input_enable(mut self, val: bool) -> Self {
+        self.input_enable = val;
+        self
+    }
+
+    /// Builder function that sets the [DriveStrength].
+    pub const fn with_drive_strength(mut self, val: DriveStrength) -> Self {
+        self.drive_strength = val;
+        self
+    }
+
+    /// Builder function that sets the [Pull].
+    pub const fn with_pull(mut self, val: Pull) -> Self {
+        self.pull = val;
+        self
+    }
+
+    /// Builder function that sets the [Slew].
+    pub const fn with_slew(mut self, val: Slew) -> Self {
+        self.slew = val;
+        self
+    }
+
+    /// Builder function that sets the [SchmittTrigger].
+    pub const fn with_schmitt_trigger(mut self, val: SchmittTrigger) -> Self {
+        self.schmitt_trigger = val;
+        self
+    }
+
+    /// Builder function that sets the [PowerOnStart].
+    pub const fn with_power_on_start(mut self, val: PowerOnStart) -> Self {
+        self.power_on_start = val;
+        self
+    }
+
+    /// Gets the pad configuration register value.
+    pub const fn bits(&self) -> u32 {
+        let pull = match self.pull {
+            Pull::None => 0,
+            Pull::Up => PADCFG_PU,
+            Pull::Down => PADCFG_PD,
+        };
+
+        (self.input_enable as u32)
+            | (((self.drive_strength as u32) << PADCFG_DS_SHIFT) & PADCFG_DS_MASK)
+            | pull
+            | ((self.slew as u32) * PADCFG_SLEW)
+            | ((self.schmitt_trigger as u32) * PADCFG_SMT)
+            | ((self.power_on_start as u32) * PADCFG_POS)
+    }
+
+    /// Creates a [PadConfig] from a pad configuration register value.
+    ///
+    /// The pull-up takes precedence if both pull resistors are enabled.
+    pub const fn from_bits(bits: u32) -> Self {
+        let drive_strength = match (bits & PADCFG_DS_MASK) >> PADCFG_DS_SHIFT {
+            0b00 => DriveStrength::Two,
+            0b01 => DriveStrength::Four,
+            0b10 => DriveStrength::Eight,
+            _ => DriveStrength::Twelve,
+        };
+
+        let pull = if bits & PADCFG_PU != 0 {
+            Pull::Up
+        } else if bits & PADCFG_PD != 0 {
+            Pull::Down
+        } else {
+            Pull::None
+        };
+
+        Self {
+            input_enable: bits & PADCFG_IE != 0,
+            drive_strength,
+            pull,
+            slew: if bits & PADCFG_SLEW != 0 {
+                Slew::Fast
+            } else {
+                Slew::Slow
+            },
+            schmitt_trigger: if bits & PADCFG_SMT != 0 {
+                SchmittTrigger::Enable
+            } else {
+                SchmittTrigger::Disable
+            },
+            power_on_start: if bits & PADCFG_POS != 0 {
+                PowerOnStart::Enable
+            } else {
+                PowerOnStart::Disable
+            },
+        }
+    }
+
+    /// Reads the [PadConfig] of a SYS pad.
+    ///
+    /// Returns an error for the GMAC1 pads, see [GmacPadConfig](super::GmacPadConfig).
+    pub fn read(pad: Pad) -> Result<Self> {
+        let index = sys_padcfg_index(pad)?;
+
+        // SAFETY: `index` is the pad configuration register of `pad` in the SYS pinctrl block
+        let bits = unsafe {
+            (pac::SysPinctrl::ptr() as *const u32)
+                .add(index)
+                .read_volatile()
+        };
+
+        Ok(Self::from_bits(bits))
+    }
+
+    /// Writes the [PadConfig] to a SYS pad, with a single register write.
+    ///
+    /// Returns an error for the GMAC1 pads, see [GmacPadConfig](super::GmacPadConfig).
+    pub fn apply(&self, pad: Pad) -> Result<()> {
+        let index = sys_padcfg_index(pad)?;
+
+        // SAFETY: `index` is the pad configuration register of `pad` in the SYS pinctrl block
+        unsafe {
+            (pac::SysPinctrl::ptr() as *mut u32)
+                .add(index)
+                .write_volatile(self.bits())
+        };
+
+        Ok(())
+    }
+
+    /// Writes the [PadConfig] to a list of SYS pads.
+    ///
+    /// Returns an error, before writing any pad, if the list contains a GMAC1 pad.
+    pub fn apply_all(&self, pads: &[Pad]) -> Result<()> {
+        pads.iter()
+            .try_for_each(|&pad| sys_padcfg_index(pad).map(|_| ()))?;
+        pads.iter().try_for_each(|&pad| self.apply(pad))
+    }
+
+    /// Reads the [PadConfig] of an AON pad.
+    pub fn read_aon(pad: AonPad) -> Self {
+        // SAFETY: the index is the pad configuration register of `pad` in the AON pinctrl block
+        let bits = unsafe {
+            (pac::AonPinctrl::ptr() as *const u32)
+                .add(aon_padcfg_index(pad))
+                .read_volatile()
+        };
+
+        Self::from_bits(bits)
+    }
+
+    /// Writes the [PadConfig] to an AON pad, with a single register write.
+    pub fn apply_aon(&self, pad: AonPad) {
+        // SAFETY: the index is the pad configuration register of `pad` in the AON pinctrl block
+        unsafe {
+            (pac::AonPinctrl::ptr() as *mut u32)
+                .add(aon_padcfg_index(pad))
+                .write_volatile(self.bits())
+        };
+    }
+}
+
+impl Default for PadConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Gets the pad configuration register index of a SYS pad.
+pub(crate) fn sys_padcfg_index(pad: Pad) -> Result<usize> {
+    match u32::from(pad) {
+        n @ (PAD_GPIO0..=PAD_SD0_STRB | PAD_QSPI_SCLK..=PAD_QSPI_DATA3) => {
+            Ok(SYS_PADCFG + n as usize)
+        }
+        n => Err(Error::InvalidPad(n)),
+    }
+}
+
 /// Configuration trait for GPIO peripheral registers.
 pub trait GpioCfg {
     /// Gets the pad number for the GPIO.
@@ -366,3 +646,43 @@ gpio_cfg!(Sd0Strb, 74);
 gpio_cfg!(QspiSclk, 89);
 gpio_cfg!(QspiCsn0, 90);
 gpio_cfg!(QspiData, 91);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pad_config_bits() {
+        assert_eq!(PadConfig::new().bits(), 0);
+
+        let cfg = PadConfig::new()
+            .with_input_enable(true)
+            .with_drive_strength(DriveStrength::Twelve)
+            .with_pull(Pull::Down)
+            .with_slew(Slew::Fast)
+            .with_schmitt_trigger(SchmittTrigger::Enable)
+            .with_power_on_start(PowerOnStart::Enable);
+
+        assert_eq!(cfg.bits(), 0xf7);
+        assert_eq!(PadConfig::from_bits(cfg.bits()), cfg);
+
+        let cfg = cfg
+            .with_pull(Pull::Up)
+            .with_drive_strength(DriveStrength::Four);
+
+        assert_eq!(cfg.bits(), 0xeb);
+        assert_eq!(PadConfig::from_bits(cfg.bits()), cfg);
+    }
+
+    #[test]
+    fn test_sys_padcfg_index() {
+        assert_eq!(sys_padcfg_index(Pad::Gpio0), Ok(0x120 >> 2));
+        assert_eq!(sys_padcfg_index(Pad::Sd0Strb), Ok(0x248 >> 2));
+        assert_eq!(sys_padcfg_index(Pad::QspiSclk), Ok(0x284 >> 2));
+        assert_eq!(sys_padcfg_index(Pad::QspiData3), Ok(0x298 >> 2));
+        assert_eq!(
+            sys_padcfg_index(Pad::Gmac1Mdc),
+            Err(Error::InvalidPad(u32::from(Pad::Gmac1Mdc)))
+        );
+    }
+}
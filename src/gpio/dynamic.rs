@@ -0,0 +1,381 @@
+//! Runtime-typed GPIO pins.
+//!
+//! [Gpio::into_dynamic] erases the pad and mode type parameters, so pins can be stored in arrays
+//! or selected at runtime. The mode is checked on each access instead.
+//!
+//! ```no_run
+//! use jh71xx_hal::{gpio, pac};
+//! use embedded_hal::digital::OutputPin;
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//! let padcfg = dp.sys_pinctrl.padcfg();
+//!
+//! let mut leds = [
+//!     gpio::get_gpio(padcfg.gpio40()).into_enabled_output().into_dynamic(),
+//!     gpio::get_gpio(padcfg.gpio41()).into_enabled_output().into_dynamic(),
+//! ];
+//!
+//! for led in leds.iter_mut() {
+//!     led.set_high().unwrap();
+//! }
+//! ```
+//!
+//! [DynPin::from_pad] creates a pin from a [Pad] selected at runtime, e.g. from a configuration
+//! table, and the mode is changed at runtime:
+//!
+//! ```no_run
+//! use jh71xx_hal::{gpio, pac};
+//! use embedded_hal::digital::{InputPin, OutputPin};
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//!
+//! let pad = gpio::Pad::Gpio44;
+//! let mut pin = gpio::DynPin::from_pad(&dp.sys_pinctrl, pad).unwrap().into_output();
+//! pin.set_low().unwrap();
+//!
+//! let mut pin = pin.into_input();
+//! let _high = pin.is_high().unwrap();
+//! ```
+
+use core::marker::PhantomData;
+
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
+
+use super::*;
+use crate::pac::SysPinctrl;
+
+/// Represents the runtime mode of a [DynPin].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DynMode {
+    /// Disabled pin.
+    #[default]
+    Disabled,
+    /// Enabled input.
+    Input,
+    /// Enabled output.
+    Output,
+    /// Open-drain output.
+    OpenDrain,
+    /// Alternate function pin.
+    Alternate,
+}
+
+impl DynMode {
+    /// Creates a new [DynMode].
+    pub const fn new() -> Self {
+        Self::Disabled
+    }
+}
+
+/// Maps a [Gpio] direction type state to its [DynMode].
+pub trait PinDirection {
+    /// Runtime mode of the direction.
+    const DYN_MODE: DynMode;
+}
+
+impl PinDirection for Nop {
+    const DYN_MODE: DynMode = DynMode::Disabled;
+}
+
+impl PinDirection for Input {
+    const DYN_MODE: DynMode = DynMode::Input;
+}
+
+impl PinDirection for Output {
+    const DYN_MODE: DynMode = DynMode::Output;
+}
+
+impl<F: Function> PinDirection for Alternate<F> {
+    const DYN_MODE: DynMode = DynMode::Alternate;
+}
+
+/// Marks the [Gpio] mode type states, open-drain outputs have their own [DynMode].
+pub trait PinMode {
+    /// Whether the mode is [OpenDrain].
+    const OPEN_DRAIN: bool = false;
+}
+
+impl PinMode for Nop {}
+impl PinMode for HiZ {}
+impl PinMode for PullDown {}
+impl PinMode for PullUp {}
+
+impl PinMode for OpenDrain {
+    const OPEN_DRAIN: bool = true;
+}
+
+/// Represents a SYS GPIO pin with the pad and mode known at runtime.
+pub struct DynPin<'g> {
+    pad: Pad,
+    mode: DynMode,
+    _pinctrl: PhantomData<&'g SysPinctrl>,
+}
+
+impl<'g> DynPin<'g> {
+    pub(crate) const fn new(pad: Pad, mode: DynMode) -> Self {
+        Self {
+            pad,
+            mode,
+            _pinctrl: PhantomData,
+        }
+    }
+
+    /// Creates a disabled [DynPin] from a SYS GPIO pad selected at runtime.
+    ///
+    /// The SYS pinctrl peripheral is borrowed for the lifetime of the pin. The input and the
+    /// output of the pad are disabled.
+    ///
+    /// Returns an error for pads outside GPIO 0-63.
+    pub fn from_pad(_pinctrl: &'g SysPinctrl, pad: Pad) -> Result<Self> {
+        check_pad(pad)?;
+
+        Ok(Self::new(pad, DynMode::Disabled).into_disabled())
+    }
+
+    /// Converts the [DynPin] into a disabled pin.
+    pub fn into_disabled(self) -> Self {
+        self.set_input(false, None);
+        self.config_output(OutputConfig::Neutral);
+
+        self.with_mode(DynMode::Disabled)
+    }
+
+    /// Converts the [DynPin] into a high-impedance input.
+    pub fn into_input(self) -> Self {
+        self.set_input(true, Some(Pull::None));
+        self.config_output(OutputConfig::Neutral);
+
+        self.with_mode(DynMode::Input)
+    }
+
+    /// Converts the [DynPin] into an output.
+    pub fn into_output(self) -> Self {
+        self.set_input(false, None);
+        self.config_output(OutputConfig::Low);
+
+        self.with_mode(DynMode::Output)
+    }
+
+    /// Converts the [DynPin] into an open-drain output, released high.
+    ///
+    /// See [Gpio::into_open_drain_output], the pull configuration is kept.
+    pub fn into_open_drain(self) -> Self {
+        self.set_input(true, None);
+        self.config_output(OutputConfig::Neutral);
+
+        let pad = u32::from(self.pad);
+        if pad < FMUX_PADS {
+            modify_sys_fmux(SYS_DOUT, pad, SYS_DOUT_MASK, OutputDrive::Low.into());
+        }
+
+        self.with_mode(DynMode::OpenDrain)
+    }
+
+    /// Gets the [Pad] of the [DynPin].
+    pub const fn pad(&self) -> Pad {
+        self.pad
+    }
+
+    /// Gets the [DynMode] of the [DynPin].
+    pub const fn mode(&self) -> DynMode {
+        self.mode
+    }
+
+    /// Gets whether the input pin, or the line of an open-drain output, is set.
+    ///
+    /// Returns an error if the [DynPin] is not an input or an open-drain output.
+    pub fn bit_is_set(&self) -> Result<bool> {
+        self.check_mode(&[DynMode::Input, DynMode::OpenDrain])?;
+
+        Ok(sys_input(self.pad.into()))
+    }
+
+    /// Sets whether the output pin is driven high, or an open-drain output released high.
+    ///
+    /// Returns an error if the [DynPin] is not an output.
+    pub fn set_pin(&mut self, high: bool) -> Result<()> {
+        self.check_mode(&[DynMode::Output, DynMode::OpenDrain])?;
+
+        let pad = u32::from(self.pad);
+        if pad >= FMUX_PADS {
+            return Ok(());
+        }
+
+        if self.mode == DynMode::OpenDrain {
+            let config = if high {
+                OutputConfig::Neutral
+            } else {
+                OutputConfig::Low
+            };
+            modify_sys_fmux(SYS_DOEN, pad, SYS_DOEN_MASK, config.into());
+        } else {
+            modify_sys_fmux(SYS_DOUT, pad, SYS_DOUT_MASK, OutputDrive::from(high).into());
+        }
+
+        Ok(())
+    }
+
+    /// Gets whether the output pin is driven high, or an open-drain output released high.
+    ///
+    /// Returns an error if the [DynPin] is not an output.
+    pub fn is_pin_set(&self) -> Result<bool> {
+        self.check_mode(&[DynMode::Output, DynMode::OpenDrain])?;
+
+        let pad = u32::from(self.pad);
+
+        Ok(if self.mode == DynMode::OpenDrain {
+            pad >= FMUX_PADS || read_sys_fmux(SYS_DOEN, pad, SYS_DOEN_MASK) != GpenFunction::ENABLE
+        } else {
+            pad < FMUX_PADS && read_sys_fmux(SYS_DOUT, pad, SYS_DOUT_MASK) == GpoFunction::HIGH
+        })
+    }
+
+    const fn with_mode(mut self, mode: DynMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    // Sets the input enable, and the pull resistors if `pull` is set, of the pad configuration.
+    //
+    // Pads without a pad configuration register are left untouched.
+    fn set_input(&self, enable: bool, pull: Option<Pull>) {
+        if let Ok(cfg) = PadConfig::read(self.pad) {
+            let cfg = cfg.with_input_enable(enable);
+            let cfg = pull.map_or(cfg, |pull| cfg.with_pull(pull));
+
+            cfg.apply(self.pad).ok();
+        }
+    }
+
+    // Sets the DOEN byte of the pad, pads outside the function multiplexer are left untouched.
+    fn config_output(&self, config: OutputConfig) {
+        let pad = u32::from(self.pad);
+
+        if pad < FMUX_PADS {
+            modify_sys_fmux(SYS_DOEN, pad, SYS_DOEN_MASK, config.into());
+        }
+    }
+
+    fn check_mode(&self, modes: &[DynMode]) -> Result<()> {
+        if modes.contains(&self.mode) {
+            Ok(())
+        } else {
+            Err(Error::InvalidMode(self.mode))
+        }
+    }
+}
+
+// Checks that a pad is one of the SYS GPIO pads 0-63.
+const fn check_pad(pad: Pad) -> Result<()> {
+    let n = pad as u32;
+
+    if n < FMUX_PADS {
+        Ok(())
+    } else {
+        Err(Error::InvalidPad(n))
+    }
+}
+
+impl<'g, GPIO: GpioCfg, ENABLED, DIRECTION: PinDirection, MODE: PinMode>
+    Gpio<'g, GPIO, ENABLED, DIRECTION, MODE>
+{
+    /// Converts the [Gpio] into a [DynPin], keeping its configuration.
+    pub fn into_dynamic(self) -> DynPin<'g> {
+        let mode = if MODE::OPEN_DRAIN {
+            DynMode::OpenDrain
+        } else {
+            DIRECTION::DYN_MODE
+        };
+
+        DynPin::new(Pad::from(GPIO::pad()), mode)
+    }
+}
+
+impl<'g> ErrorType for DynPin<'g> {
+    type Error = Error;
+}
+
+impl<'g> InputPin for DynPin<'g> {
+    fn is_low(&mut self) -> Result<bool> {
+        self.is_high().map(|v| !v)
+    }
+
+    fn is_high(&mut self) -> Result<bool> {
+        self.bit_is_set()
+    }
+}
+
+impl<'g> OutputPin for DynPin<'g> {
+    fn set_low(&mut self) -> Result<()> {
+        self.set_pin(false)
+    }
+
+    fn set_high(&mut self) -> Result<()> {
+        self.set_pin(true)
+    }
+}
+
+impl<'g> StatefulOutputPin for DynPin<'g> {
+    fn is_set_high(&mut self) -> Result<bool> {
+        self.is_pin_set()
+    }
+
+    fn is_set_low(&mut self) -> Result<bool> {
+        self.is_pin_set().map(|v| !v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dyn_mode() {
+        assert_eq!(<Nop as PinDirection>::DYN_MODE, DynMode::Disabled);
+        assert_eq!(<Input as PinDirection>::DYN_MODE, DynMode::Input);
+        assert_eq!(<Output as PinDirection>::DYN_MODE, DynMode::Output);
+        assert_eq!(
+            <Alternate<Uart0Tx> as PinDirection>::DYN_MODE,
+            DynMode::Alternate
+        );
+    }
+
+    #[test]
+    fn test_check_pad() {
+        assert_eq!(check_pad(Pad::Gpio0), Ok(()));
+        assert_eq!(check_pad(Pad::Gpio63), Ok(()));
+        assert_eq!(
+            check_pad(Pad::Sd0Clk),
+            Err(Error::InvalidPad(u32::from(Pad::Sd0Clk)))
+        );
+        assert_eq!(
+            check_pad(Pad::QspiSclk),
+            Err(Error::InvalidPad(u32::from(Pad::QspiSclk)))
+        );
+    }
+
+    #[test]
+    fn test_check_mode() {
+        let mut pin = DynPin::new(Pad::Gpio40, DynMode::Input);
+
+        assert_eq!(pin.check_mode(&[DynMode::Input]), Ok(()));
+        assert_eq!(pin.set_high(), Err(Error::InvalidMode(DynMode::Input)));
+        assert_eq!(pin.is_set_high(), Err(Error::InvalidMode(DynMode::Input)));
+
+        let mut pin = DynPin::new(Pad::Gpio40, DynMode::Disabled);
+
+        assert_eq!(pin.is_high(), Err(Error::InvalidMode(DynMode::Disabled)));
+
+        let pin = DynPin::new(Pad::Gpio40, DynMode::OpenDrain);
+
+        assert_eq!(
+            pin.check_mode(&[DynMode::Output, DynMode::OpenDrain]),
+            Ok(())
+        );
+        assert_eq!(
+            pin.check_mode(&[DynMode::Output]),
+            Err(Error::InvalidMode(DynMode::OpenDrain))
+        );
+    }
+}
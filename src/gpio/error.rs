@@ -3,6 +3,8 @@ use core::fmt;
 use embedded_hal::digital::Error as GpioError;
 pub use embedded_hal::digital::ErrorKind;
 
+use super::DynMode;
+
 /// Convenience [`Result`](core::result::Result) alias for JH71xx GPIO module.
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -11,6 +13,7 @@ pub type Result<T> = core::result::Result<T, Error>;
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Error {
     InvalidPad(u32),
+    InvalidMode(DynMode),
 }
 
 impl GpioError for Error {
@@ -23,6 +26,7 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::InvalidPad(err) => write!(f, "invalid pad number: {err}"),
+            Self::InvalidMode(err) => write!(f, "invalid pin mode: {err:?}"),
         }
     }
 }
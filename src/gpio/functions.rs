@@ -1,8 +1,58 @@
 //! GPIO function multiplexer (FMUX)
+//!
+//! Register layout follows the Linux [`pinctrl-starfive-jh7110-sys`](https://github.com/torvalds/linux/blob/master/drivers/pinctrl/starfive/pinctrl-starfive-jh7110-sys.c) driver.
 
+use crate::pac;
+
+/// SYS GPO enable (DOEN) register index, one byte per pad.
+pub(crate) const SYS_DOEN: usize = 0x000 >> 2;
+/// SYS GPO output (DOUT) register index, one byte per pad.
+pub(crate) const SYS_DOUT: usize = 0x040 >> 2;
+/// SYS GPI register index, one byte per GPI function.
+pub(crate) const SYS_GPI: usize = 0x080 >> 2;
+
+/// Mask of the DOUT function byte.
+pub(crate) const SYS_DOUT_MASK: u8 = 0x7f;
+/// Mask of the DOEN function byte.
+pub(crate) const SYS_DOEN_MASK: u8 = 0x3f;
+/// Mask of the GPI pad byte.
+pub(crate) const SYS_GPI_MASK: u8 = 0x7f;
+
+/// Number of GPIO pads routed through the SYS function multiplexer.
+pub const FMUX_PADS: u32 = 64;
+/// Offset of the pad number in the GPI registers, values `0` and `1` select constant low and high.
+pub const GPI_PAD_OFFSET: u8 = 2;
+
+/// Represents a peripheral signal routed through the GPIO function multiplexer.
+///
+/// [GROUP](Self::GROUP) and [INDEX](Self::INDEX) select the primary signal, the `DOUT`, `DOEN` and
+/// `GPI` values routed for a pad default to:
+///
+/// - [GpioGroup::Gpo]: the output signal, with the output driver always enabled
+/// - [GpioGroup::Gpen]: a constant low output, enabled by the signal (open-drain)
+/// - [GpioGroup::Gpi]: the input signal, with the output driver disabled
 pub trait Function {
+    /// Signal group of the primary signal.
     const GROUP: GpioGroup;
+    /// Index of the primary signal in its group.
     const INDEX: u8;
+
+    /// GPO (DOUT) function routed to the pad.
+    const DOUT: u8 = match Self::GROUP {
+        GpioGroup::Gpo | GpioGroup::AonGpo => Self::INDEX,
+        _ => GpoFunction::LOW,
+    };
+    /// GPEN (DOEN) function routed to the pad.
+    const DOEN: u8 = match Self::GROUP {
+        GpioGroup::Gpo | GpioGroup::AonGpo => GpenFunction::ENABLE,
+        GpioGroup::Gpen | GpioGroup::AonGpen => Self::INDEX,
+        _ => GpenFunction::DISABLE,
+    };
+    /// GPI function routed from the pad, if any.
+    const GPI: Option<u8> = match Self::GROUP {
+        GpioGroup::Gpi | GpioGroup::AonGpi => Some(Self::INDEX),
+        _ => None,
+    };
 }
 
 /// Represents the GPIO signal group for configurable functions.
@@ -23,6 +73,11 @@ impl GpioGroup {
     pub const fn new() -> Self {
         Self::Gpo
     }
+
+    /// Gets whether the [GpioGroup] is routed to the AON pads.
+    pub const fn is_aon(&self) -> bool {
+        matches!(self, Self::AonGpo | Self::AonGpen | Self::AonGpi)
+    }
 }
 
 /// Configurable GPO function indices.
@@ -31,6 +86,8 @@ impl GpioGroup {
 pub struct GpoFunction;
 
 impl GpoFunction {
+    pub const LOW: u8 = 0;
+    pub const HIGH: u8 = 1;
     pub const U0_WAVE511_O_UART_TXSOUT: u8 = 2;
     pub const U0_CAN_CTRL_STBY: u8 = 3;
     pub const U0_CAN_CTRL_TST_NEXT_BIT: u8 = 4;
@@ -145,6 +202,8 @@ impl GpoFunction {
 pub struct GpenFunction;
 
 impl GpenFunction {
+    pub const ENABLE: u8 = 0;
+    pub const DISABLE: u8 = 1;
     pub const U0_DOM_VOUT_TOP_U0_HDMI_TX_PIN_CEC_SDA_OEN: u8 = 2;
     pub const U0_DOM_VOUT_TOP_U0_HDMI_TX_PIN_DDC_SCL_OEN: u8 = 3;
     pub const U0_DOM_VOUT_TOP_U0_HDMI_TX_PIN_DDC_SDA_OEN: u8 = 4;
@@ -195,7 +254,7 @@ impl GpenFunction {
     pub const U6_SSP_SPI_NSSPOE: u8 = 49;
 }
 
-/// Configurable GPEN function indices.
+/// Configurable GPI function indices.
 ///
 /// GPI function signals can be configured for GPIO pins 2-63 (GPIO0-GPIO1 are reserved).
 pub struct GpiFunction;
@@ -296,7 +355,7 @@ impl GpiFunction {
 
 /// Configurable AON GPO function indices.
 ///
-/// AON GPO (Always-on DOUT) function signals can be configured for GPIO pins 0-63.
+/// AON GPO (Always-on DOUT) function signals can be configured for the AON pads, see [AonPad](super::AonPad).
 pub struct AonGpoFunction;
 
 impl AonGpoFunction {
@@ -312,7 +371,7 @@ impl AonGpoFunction {
 
 /// Configurable AON GPEN function indices.
 ///
-/// AON GPEN (Always-on DOEN) function signals can be configured for GPIO pins 0-63.
+/// AON GPEN (Always-on DOEN) function signals can be configured for the AON pads, see [AonPad](super::AonPad).
 pub struct AonGpenFunction;
 
 impl AonGpenFunction {
@@ -333,3 +392,606 @@ impl AonGpiFunction {
     pub const U0_PMU_IO_EVENT_STUB_GPIO_WAKEUP_2: u8 = 2;
     pub const U0_PMU_IO_EVENT_STUB_GPIO_WAKEUP_3: u8 = 3;
 }
+
+// Gets the register index and bit offset of byte `n` of the FMUX registers at `base`.
+pub(crate) const fn fmux_field(base: usize, n: u32) -> (usize, u32) {
+    (base + (n / 4) as usize, (n % 4) * 8)
+}
+
+// Replaces the masked bits of the byte at `shift` in the FMUX register value `reg`.
+pub(crate) const fn fmux_update(reg: u32, shift: u32, mask: u8, val: u8) -> u32 {
+    let mask = (mask as u32) << shift;
+
+    (reg & !mask) | (((val as u32) << shift) & mask)
+}
+
+// Modifies byte `n` of the SYS FMUX registers at `base`.
+#[inline]
+pub(crate) fn modify_sys_fmux(base: usize, n: u32, mask: u8, val: u8) {
+    let (index, shift) = fmux_field(base, n);
+
+    modify_sys_fmux_reg(index, |r| fmux_update(r, shift, mask, val));
+}
+
+// Modifies the SYS FMUX register at `index`.
+//
+// Each FMUX register holds the bytes of four pads, which may be owned by different contexts, so
+// the read-modify-write runs in a critical section.
+#[inline]
+pub(crate) fn modify_sys_fmux_reg(index: usize, f: impl FnOnce(u32) -> u32) {
+    critical_section::with(|_| {
+        // SAFETY: the FMUX registers are 32-bit words at the start of the SYS pinctrl block. The
+        // critical section keeps other contexts from modifying the register between the read and
+        // the write.
+        unsafe {
+            let reg = (pac::SysPinctrl::ptr() as *mut u32).add(index);
+
+            reg.write_volatile(f(reg.read_volatile()));
+        }
+    })
+}
+
+// Reads byte `n` of the SYS FMUX registers at `base`.
+#[inline]
+pub(crate) fn read_sys_fmux(base: usize, n: u32, mask: u8) -> u8 {
+    let (index, shift) = fmux_field(base, n);
+
+    // SAFETY: the FMUX registers are 32-bit words at the start of the SYS pinctrl block
+    let reg = unsafe {
+        (pac::SysPinctrl::ptr() as *const u32)
+            .add(index)
+            .read_volatile()
+    };
+
+    (reg >> shift) as u8 & mask
+}
+
+/// Routes the DOUT, DOEN and GPI functions of `F` to a GPIO pad.
+///
+/// Pads outside the function multiplexer (see [FMUX_PADS]) are left untouched.
+pub(crate) fn set_sys_function<F: Function>(pad: u32) {
+    if pad >= FMUX_PADS {
+        return;
+    }
+
+    modify_sys_fmux(SYS_DOUT, pad, SYS_DOUT_MASK, F::DOUT);
+    modify_sys_fmux(SYS_DOEN, pad, SYS_DOEN_MASK, F::DOEN);
+
+    if let Some(gpi) = F::GPI {
+        modify_sys_fmux(
+            SYS_GPI,
+            gpi as u32,
+            SYS_GPI_MASK,
+            pad as u8 + GPI_PAD_OFFSET,
+        );
+    }
+}
+
+macro_rules! function {
+    ($(#[$meta:meta])* $name:ident, $group:ident, $index:expr$(, $opt:ident: $val:expr)*) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub struct $name;
+
+        impl Function for $name {
+            const GROUP: GpioGroup = GpioGroup::$group;
+            const INDEX: u8 = $index;
+            $(const $opt: function!(@ty $opt) = $val;)*
+        }
+    };
+    (@ty DOEN) => { u8 };
+    (@ty GPI) => { Option<u8> };
+}
+
+function!(
+    /// UART0 transmit data.
+    Uart0Tx, Gpo, GpoFunction::U0_DW_UART_SOUT
+);
+function!(
+    /// UART0 receive data.
+    Uart0Rx, Gpi, GpiFunction::U0_DW_UART_SIN
+);
+function!(
+    /// UART1 transmit data.
+    Uart1Tx, Gpo, GpoFunction::U1_DW_UART_SOUT
+);
+function!(
+    /// UART1 receive data.
+    Uart1Rx, Gpi, GpiFunction::U1_DW_UART_SIN
+);
+function!(
+    /// UART1 request-to-send.
+    Uart1Rts, Gpo, GpoFunction::U1_DW_UART_RTS_N
+);
+function!(
+    /// UART1 clear-to-send.
+    Uart1Cts, Gpi, GpiFunction::U1_DW_UART_CTS_N
+);
+function!(
+    /// UART2 transmit data.
+    Uart2Tx, Gpo, GpoFunction::U2_DW_UART_SOUT
+);
+function!(
+    /// UART2 receive data.
+    Uart2Rx, Gpi, GpiFunction::U2_DW_UART_SIN
+);
+function!(
+    /// UART2 request-to-send.
+    Uart2Rts, Gpo, GpoFunction::U2_DW_UART_RTS_N
+);
+function!(
+    /// UART2 clear-to-send.
+    Uart2Cts, Gpi, GpiFunction::U2_DW_UART_CTS_N
+);
+function!(
+    /// UART3 transmit data.
+    Uart3Tx, Gpo, GpoFunction::U3_DW_UART_SOUT
+);
+function!(
+    /// UART3 receive data.
+    Uart3Rx, Gpi, GpiFunction::U3_DW_UART_SIN
+);
+function!(
+    /// UART4 transmit data.
+    Uart4Tx, Gpo, GpoFunction::U4_DW_UART_SOUT
+);
+function!(
+    /// UART4 receive data.
+    Uart4Rx, Gpi, GpiFunction::U4_DW_UART_SIN
+);
+function!(
+    /// UART4 request-to-send.
+    Uart4Rts, Gpo, GpoFunction::U4_DW_UART_RTS_N
+);
+function!(
+    /// UART4 clear-to-send.
+    Uart4Cts, Gpi, GpiFunction::U4_DW_UART_CTS_N
+);
+function!(
+    /// UART5 transmit data.
+    Uart5Tx, Gpo, GpoFunction::U5_DW_UART_SOUT
+);
+function!(
+    /// UART5 receive data.
+    Uart5Rx, Gpi, GpiFunction::U5_DW_UART_SIN
+);
+function!(
+    /// UART5 request-to-send.
+    Uart5Rts, Gpo, GpoFunction::U5_DW_UART_RTS_N
+);
+function!(
+    /// UART5 clear-to-send.
+    Uart5Cts, Gpi, GpiFunction::U5_DW_UART_CTS_N
+);
+function!(
+    /// I2C0 clock (open-drain).
+    I2c0Scl,
+    Gpen,
+    GpenFunction::U0_DW_I2C_IC_CLK_OE,
+    GPI: Some(GpiFunction::U0_DW_I2C_IC_CLK_IN_A)
+);
+function!(
+    /// I2C0 data (open-drain).
+    I2c0Sda,
+    Gpen,
+    GpenFunction::U0_DW_I2C_IC_DATA_OE,
+    GPI: Some(GpiFunction::U0_DW_I2C_IC_DATA_IN_A)
+);
+function!(
+    /// I2C1 clock (open-drain).
+    I2c1Scl,
+    Gpen,
+    GpenFunction::U1_DW_I2C_IC_CLK_OE,
+    GPI: Some(GpiFunction::U1_DW_I2C_IC_CLK_IN_A)
+);
+function!(
+    /// I2C1 data (open-drain).
+    I2c1Sda,
+    Gpen,
+    GpenFunction::U1_DW_I2C_IC_DATA_OE,
+    GPI: Some(GpiFunction::U1_DW_I2C_IC_DATA_IN_A)
+);
+function!(
+    /// I2C2 clock (open-drain).
+    I2c2Scl,
+    Gpen,
+    GpenFunction::U2_DW_I2C_IC_CLK_OE,
+    GPI: Some(GpiFunction::U2_DW_I2C_IC_CLK_IN_A)
+);
+function!(
+    /// I2C2 data (open-drain).
+    I2c2Sda,
+    Gpen,
+    GpenFunction::U2_DW_I2C_IC_DATA_OE,
+    GPI: Some(GpiFunction::U2_DW_I2C_IC_DATA_IN_A)
+);
+function!(
+    /// I2C3 clock (open-drain).
+    I2c3Scl,
+    Gpen,
+    GpenFunction::U3_DW_I2C_IC_CLK_OE,
+    GPI: Some(GpiFunction::U3_DW_I2C_IC_CLK_IN_A)
+);
+function!(
+    /// I2C3 data (open-drain).
+    I2c3Sda,
+    Gpen,
+    GpenFunction::U3_DW_I2C_IC_DATA_OE,
+    GPI: Some(GpiFunction::U3_DW_I2C_IC_DATA_IN_A)
+);
+function!(
+    /// I2C4 clock (open-drain).
+    I2c4Scl,
+    Gpen,
+    GpenFunction::U4_DW_I2C_IC_CLK_OE,
+    GPI: Some(GpiFunction::U4_DW_I2C_IC_CLK_IN_A)
+);
+function!(
+    /// I2C4 data (open-drain).
+    I2c4Sda,
+    Gpen,
+    GpenFunction::U4_DW_I2C_IC_DATA_OE,
+    GPI: Some(GpiFunction::U4_DW_I2C_IC_DATA_IN_A)
+);
+function!(
+    /// I2C5 clock (open-drain).
+    I2c5Scl,
+    Gpen,
+    GpenFunction::U5_DW_I2C_IC_CLK_OE,
+    GPI: Some(GpiFunction::U5_DW_I2C_IC_CLK_IN_A)
+);
+function!(
+    /// I2C5 data (open-drain).
+    I2c5Sda,
+    Gpen,
+    GpenFunction::U5_DW_I2C_IC_DATA_OE,
+    GPI: Some(GpiFunction::U5_DW_I2C_IC_DATA_IN_A)
+);
+function!(
+    /// I2C6 clock (open-drain).
+    I2c6Scl,
+    Gpen,
+    GpenFunction::U6_DW_I2C_IC_CLK_OE,
+    GPI: Some(GpiFunction::U6_DW_I2C_IC_CLK_IN_A)
+);
+function!(
+    /// I2C6 data (open-drain).
+    I2c6Sda,
+    Gpen,
+    GpenFunction::U6_DW_I2C_IC_DATA_OE,
+    GPI: Some(GpiFunction::U6_DW_I2C_IC_DATA_IN_A)
+);
+function!(
+    /// SPI0 clock output.
+    Spi0Clk, Gpo, GpoFunction::U0_SSP_SPI_SSPCLKOUT
+);
+function!(
+    /// SPI0 chip select output.
+    Spi0Cs, Gpo, GpoFunction::U0_SSP_SPI_SSPFSSOUT
+);
+function!(
+    /// SPI0 transmit data.
+    Spi0Tx, Gpo, GpoFunction::U0_SSP_SPI_SSPTXD
+);
+function!(
+    /// SPI0 receive data.
+    Spi0Rx, Gpi, GpiFunction::U0_SSP_SPI_SSPRXD
+);
+function!(
+    /// SPI1 clock output.
+    Spi1Clk, Gpo, GpoFunction::U1_SSP_SPI_SSPCLKOUT
+);
+function!(
+    /// SPI1 chip select output.
+    Spi1Cs, Gpo, GpoFunction::U1_SSP_SPI_SSPFSSOUT
+);
+function!(
+    /// SPI1 transmit data.
+    Spi1Tx, Gpo, GpoFunction::U1_SSP_SPI_SSPTXD
+);
+function!(
+    /// SPI1 receive data.
+    Spi1Rx, Gpi, GpiFunction::U1_SSP_SPI_SSPRXD
+);
+function!(
+    /// SPI2 clock output.
+    Spi2Clk, Gpo, GpoFunction::U2_SSP_SPI_SSPCLKOUT
+);
+function!(
+    /// SPI2 chip select output.
+    Spi2Cs, Gpo, GpoFunction::U2_SSP_SPI_SSPFSSOUT
+);
+function!(
+    /// SPI2 transmit data.
+    Spi2Tx, Gpo, GpoFunction::U2_SSP_SPI_SSPTXD
+);
+function!(
+    /// SPI2 receive data.
+    Spi2Rx, Gpi, GpiFunction::U2_SSP_SPI_SSPRXD
+);
+function!(
+    /// SPI3 clock output.
+    Spi3Clk, Gpo, GpoFunction::U3_SSP_SPI_SSPCLKOUT
+);
+function!(
+    /// SPI3 chip select output.
+    Spi3Cs, Gpo, GpoFunction::U3_SSP_SPI_SSPFSSOUT
+);
+function!(
+    /// SPI3 transmit data.
+    Spi3Tx, Gpo, GpoFunction::U3_SSP_SPI_SSPTXD
+);
+function!(
+    /// SPI3 receive data.
+    Spi3Rx, Gpi, GpiFunction::U3_SSP_SPI_SSPRXD
+);
+function!(
+    /// SPI4 clock output.
+    Spi4Clk, Gpo, GpoFunction::U4_SSP_SPI_SSPCLKOUT
+);
+function!(
+    /// SPI4 chip select output.
+    Spi4Cs, Gpo, GpoFunction::U4_SSP_SPI_SSPFSSOUT
+);
+function!(
+    /// SPI4 transmit data.
+    Spi4Tx, Gpo, GpoFunction::U4_SSP_SPI_SSPTXD
+);
+function!(
+    /// SPI4 receive data.
+    Spi4Rx, Gpi, GpiFunction::U4_SSP_SPI_SSPRXD
+);
+function!(
+    /// SPI5 clock output.
+    Spi5Clk, Gpo, GpoFunction::U5_SSP_SPI_SSPCLKOUT
+);
+function!(
+    /// SPI5 chip select output.
+    Spi5Cs, Gpo, GpoFunction::U5_SSP_SPI_SSPFSSOUT
+);
+function!(
+    /// SPI5 transmit data.
+    Spi5Tx, Gpo, GpoFunction::U5_SSP_SPI_SSPTXD
+);
+function!(
+    /// SPI5 receive data.
+    Spi5Rx, Gpi, GpiFunction::U5_SSP_SPI_SSPRXD
+);
+function!(
+    /// SPI6 clock output.
+    Spi6Clk, Gpo, GpoFunction::U6_SSP_SPI_SSPCLKOUT
+);
+function!(
+    /// SPI6 chip select output.
+    Spi6Cs, Gpo, GpoFunction::U6_SSP_SPI_SSPFSSOUT
+);
+function!(
+    /// SPI6 transmit data.
+    Spi6Tx, Gpo, GpoFunction::U6_SSP_SPI_SSPTXD
+);
+function!(
+    /// SPI6 receive data.
+    Spi6Rx, Gpi, GpiFunction::U6_SSP_SPI_SSPRXD
+);
+function!(
+    /// PWM channel 0, enabled by the PWM output enable.
+    Pwm0,
+    Gpo,
+    GpoFunction::U0_PWM_8CH_PTC_PWM_0,
+    DOEN: GpenFunction::U0_PWM_8CH_PTC_OE_N_0
+);
+function!(
+    /// PWM channel 1, enabled by the PWM output enable.
+    Pwm1,
+    Gpo,
+    GpoFunction::U0_PWM_8CH_PTC_PWM_1,
+    DOEN: GpenFunction::U0_PWM_8CH_PTC_OE_N_1
+);
+function!(
+    /// PWM channel 2, enabled by the PWM output enable.
+    Pwm2,
+    Gpo,
+    GpoFunction::U0_PWM_8CH_PTC_PWM_2,
+    DOEN: GpenFunction::U0_PWM_8CH_PTC_OE_N_2
+);
+function!(
+    /// PWM channel 3, enabled by the PWM output enable.
+    Pwm3,
+    Gpo,
+    GpoFunction::U0_PWM_8CH_PTC_PWM_3,
+    DOEN: GpenFunction::U0_PWM_8CH_PTC_OE_N_3
+);
+function!(
+    /// SDIO0 (eMMC) card reset.
+    Sdio0Rst, Gpo, GpoFunction::U0_DW_SDIO_RST_N
+);
+function!(
+    /// SDIO1 card clock.
+    Sdio1Clk, Gpo, GpoFunction::U1_DW_SDIO_CCLK_OUT
+);
+function!(
+    /// SDIO1 command, enabled by the SDIO command output enable.
+    Sdio1Cmd,
+    Gpo,
+    GpoFunction::U1_DW_SDIO_CCMD_OUT,
+    DOEN: GpenFunction::U1_DW_SDIO_CCMD_OUT_EN,
+    GPI: Some(GpiFunction::U1_DW_SDIO_CCMD_IN)
+);
+function!(
+    /// SDIO1 data 0, enabled by the SDIO data output enable.
+    Sdio1Data0,
+    Gpo,
+    GpoFunction::U1_DW_SDIO_CDATA_OUT_0,
+    DOEN: GpenFunction::U1_DW_SDIO_CDATA_OUT_EN_0,
+    GPI: Some(GpiFunction::U1_DW_SDIO_CDATA_IN_0)
+);
+function!(
+    /// SDIO1 data 1, enabled by the SDIO data output enable.
+    Sdio1Data1,
+    Gpo,
+    GpoFunction::U1_DW_SDIO_CDATA_OUT_1,
+    DOEN: GpenFunction::U1_DW_SDIO_CDATA_OUT_EN_1,
+    GPI: Some(GpiFunction::U1_DW_SDIO_CDATA_IN_1)
+);
+function!(
+    /// SDIO1 data 2, enabled by the SDIO data output enable.
+    Sdio1Data2,
+    Gpo,
+    GpoFunction::U1_DW_SDIO_CDATA_OUT_2,
+    DOEN: GpenFunction::U1_DW_SDIO_CDATA_OUT_EN_2,
+    GPI: Some(GpiFunction::U1_DW_SDIO_CDATA_IN_2)
+);
+function!(
+    /// SDIO1 data 3, enabled by the SDIO data output enable.
+    Sdio1Data3,
+    Gpo,
+    GpoFunction::U1_DW_SDIO_CDATA_OUT_3,
+    DOEN: GpenFunction::U1_DW_SDIO_CDATA_OUT_EN_3,
+    GPI: Some(GpiFunction::U1_DW_SDIO_CDATA_IN_3)
+);
+function!(
+    /// PWM channel 4, on the AON pads.
+    AonPwm4,
+    AonGpo,
+    AonGpoFunction::U0_PWM_8CH_PTC_PWM_4,
+    DOEN: AonGpenFunction::U0_PWM_8CH_PTC_OE_N_4
+);
+function!(
+    /// PWM channel 5, on the AON pads.
+    AonPwm5,
+    AonGpo,
+    AonGpoFunction::U0_PWM_8CH_PTC_PWM_5,
+    DOEN: AonGpenFunction::U0_PWM_8CH_PTC_OE_N_5
+);
+function!(
+    /// PWM channel 6, on the AON pads.
+    AonPwm6,
+    AonGpo,
+    AonGpoFunction::U0_PWM_8CH_PTC_PWM_6,
+    DOEN: AonGpenFunction::U0_PWM_8CH_PTC_OE_N_6
+);
+function!(
+    /// PWM channel 7, on the AON pads.
+    AonPwm7,
+    AonGpo,
+    AonGpoFunction::U0_PWM_8CH_PTC_PWM_7,
+    DOEN: AonGpenFunction::U0_PWM_8CH_PTC_OE_N_7
+);
+function!(
+    /// 32.768 kHz clock output, on the AON pads.
+    AonClk32kOut, AonGpo, AonGpoFunction::U0_AON_CRG_CLK_32K_OUT
+);
+function!(
+    /// PMU GPIO wakeup event 0, on the AON pads.
+    PmuWakeup0, AonGpi, AonGpiFunction::U0_PMU_IO_EVENT_STUB_GPIO_WAKEUP_0
+);
+function!(
+    /// PMU GPIO wakeup event 1, on the AON pads.
+    PmuWakeup1, AonGpi, AonGpiFunction::U0_PMU_IO_EVENT_STUB_GPIO_WAKEUP_1
+);
+function!(
+    /// PMU GPIO wakeup event 2, on the AON pads.
+    PmuWakeup2, AonGpi, AonGpiFunction::U0_PMU_IO_EVENT_STUB_GPIO_WAKEUP_2
+);
+function!(
+    /// PMU GPIO wakeup event 3, on the AON pads.
+    PmuWakeup3, AonGpi, AonGpiFunction::U0_PMU_IO_EVENT_STUB_GPIO_WAKEUP_3
+);
+
+#[cfg(test)]
+mod tests {
+    use super::super::AON_GPEN_DISABLE;
+    use super::*;
+
+    #[test]
+    fn test_fmux_field() {
+        assert_eq!(fmux_field(SYS_DOEN, 0), (0, 0));
+        assert_eq!(fmux_field(SYS_DOEN, 5), (1, 8));
+        assert_eq!(fmux_field(SYS_DOUT, 63), (31, 24));
+        assert_eq!(fmux_field(SYS_GPI, 90), (54, 16));
+    }
+
+    #[test]
+    fn test_fmux_pac_layout() {
+        // PAC `gpo_doenN`/`gpo_doutN` registers hold the `doen`/`dout` fields of pads 4N..4N+3,
+        // one byte each, starting at offsets 0x00 and 0x40
+        for pad in 0..FMUX_PADS {
+            let (doen, doen_shift) = fmux_field(SYS_DOEN, pad);
+            let (dout, dout_shift) = fmux_field(SYS_DOUT, pad);
+
+            assert_eq!(doen, (pad / 4) as usize);
+            assert_eq!(dout, 0x10 + (pad / 4) as usize);
+            assert_eq!(doen_shift, (pad % 4) * 8);
+            assert_eq!(dout_shift, doen_shift);
+
+            assert_eq!(doen * 4 + (doen_shift / 8) as usize, pad as usize);
+            assert_eq!(dout * 4 + (dout_shift / 8) as usize, 0x40 + pad as usize);
+        }
+
+        // `gpo_doen15.doen62`, `gpo_dout1.dout4`, `gpi0.gpi2`
+        assert_eq!(fmux_field(SYS_DOEN, 62), (15, 16));
+        assert_eq!(fmux_field(SYS_DOUT, 4), (0x44 >> 2, 0));
+        assert_eq!(fmux_field(SYS_GPI, 2), (0x80 >> 2, 16));
+
+        // SAFETY: the register block only holds integer registers, all zero is a valid value
+        let rb: pac::sys_pinctrl::RegisterBlock = unsafe { core::mem::zeroed() };
+        let field = |base: usize, n: u32| fmux_field(base, n).0 * 4;
+
+        fn offset<T>(rb: &pac::sys_pinctrl::RegisterBlock, reg: &T) -> usize {
+            reg as *const T as usize - rb as *const _ as usize
+        }
+
+        let doen = rb.gpo_doen();
+        assert_eq!(offset(&rb, doen.gpo_doen0()), field(SYS_DOEN, 0));
+        assert_eq!(offset(&rb, doen.gpo_doen1()), field(SYS_DOEN, 5));
+        assert_eq!(offset(&rb, doen.gpo_doen15()), field(SYS_DOEN, 62));
+
+        let dout = rb.gpo_dout();
+        assert_eq!(offset(&rb, dout.gpo_dout0()), field(SYS_DOUT, 0));
+        assert_eq!(offset(&rb, dout.gpo_dout1()), field(SYS_DOUT, 4));
+        assert_eq!(offset(&rb, dout.gpo_dout15()), field(SYS_DOUT, 63));
+
+        let gpi = rb.gpi();
+        assert_eq!(offset(&rb, gpi.gpi0()), field(SYS_GPI, 2));
+        assert_eq!(offset(&rb, gpi.gpi1()), field(SYS_GPI, 4));
+    }
+
+    #[test]
+    fn test_fmux_update() {
+        assert_eq!(fmux_update(0, 0, SYS_DOEN_MASK, 0b01), 0x0000_0001);
+        assert_eq!(fmux_update(0xffff_ffff, 8, SYS_DOEN_MASK, 0), 0xffff_c0ff);
+        assert_eq!(
+            fmux_update(0x1234_5678, 24, SYS_DOUT_MASK, 0xff),
+            0x7f34_5678
+        );
+        assert_eq!(fmux_update(0x1234_5678, 16, SYS_GPI_MASK, 2), 0x1202_5678);
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(Uart0Tx::DOUT, GpoFunction::U0_DW_UART_SOUT);
+        assert_eq!(Uart0Tx::DOEN, GpenFunction::ENABLE);
+        assert_eq!(Uart0Tx::GPI, None);
+
+        assert_eq!(Uart0Rx::DOUT, GpoFunction::LOW);
+        assert_eq!(Uart0Rx::DOEN, GpenFunction::DISABLE);
+        assert_eq!(Uart0Rx::GPI, Some(GpiFunction::U0_DW_UART_SIN));
+
+        assert_eq!(I2c0Sda::DOUT, GpoFunction::LOW);
+        assert_eq!(I2c0Sda::DOEN, GpenFunction::U0_DW_I2C_IC_DATA_OE);
+        assert_eq!(I2c0Sda::GPI, Some(GpiFunction::U0_DW_I2C_IC_DATA_IN_A));
+
+        assert_eq!(Pwm3::DOUT, GpoFunction::U0_PWM_8CH_PTC_PWM_3);
+        assert_eq!(Pwm3::DOEN, GpenFunction::U0_PWM_8CH_PTC_OE_N_3);
+        assert_eq!(Pwm3::GPI, None);
+
+        assert_eq!(Sdio1Data2::DOUT, GpoFunction::U1_DW_SDIO_CDATA_OUT_2);
+        assert_eq!(Sdio1Data2::DOEN, GpenFunction::U1_DW_SDIO_CDATA_OUT_EN_2);
+        assert_eq!(Sdio1Data2::GPI, Some(GpiFunction::U1_DW_SDIO_CDATA_IN_2));
+
+        assert_eq!(AonPwm7::DOUT, AonGpoFunction::U0_PWM_8CH_PTC_PWM_7);
+        assert_eq!(AonPwm7::DOEN, AonGpenFunction::U0_PWM_8CH_PTC_OE_N_7);
+        assert_eq!(PmuWakeup2::DOEN, AON_GPEN_DISABLE);
+        assert_eq!(PmuWakeup2::GPI, Some(2));
+    }
+}
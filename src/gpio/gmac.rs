@@ -0,0 +1,246 @@
+//! GMAC RGMII pads: GMAC0 in the AON pinctrl block, GMAC1 in the SYS pinctrl block.
+//!
+//! The GMAC pads are not routed through the function multiplexer, and their configuration
+//! registers do not follow the layout of the other pads (see [PadConfig](super::PadConfig)).
+//!
+//! Each GMAC has one register per signal, in the order of [GmacSignal]:
+//!
+//! - GMAC0: AON pinctrl `0x58-0x8c`
+//! - GMAC1: SYS pinctrl `0x24c-0x280`
+//!
+//! Only the drive strength field (bits `[1:0]`) is known, it is the field written by the StarFive
+//! U-Boot VisionFive 2 SPL to raise the TX pads to level `1`. The remaining bits are preserved on
+//! write.
+//!
+//! ```no_run
+//! use jh71xx_hal::gpio::{GmacPad, GmacPadConfig, GmacSignal};
+//!
+//! let tx = [
+//!     GmacSignal::Txd0,
+//!     GmacSignal::Txd1,
+//!     GmacSignal::Txd2,
+//!     GmacSignal::Txd3,
+//!     GmacSignal::Txen,
+//! ];
+//!
+//! let cfg = GmacPadConfig::new().with_drive_strength(1);
+//!
+//! for signal in tx {
+//!     cfg.apply(GmacPad::Gmac0(signal));
+//!     cfg.apply(GmacPad::Gmac1(signal));
+//! }
+//! ```
+
+use super::{Error, Pad, Result, PAD_GMAC1_MDC, PAD_GMAC1_TXC};
+use crate::pac;
+
+/// AON pad configuration register index of the GMAC0 MDC pad.
+pub(crate) const AON_GMAC0_PADCFG: usize = 0x58 >> 2;
+/// SYS pad configuration register index of the GMAC1 MDC pad.
+pub(crate) const SYS_GMAC1_PADCFG: usize = 0x24c >> 2;
+/// GMAC pad configuration drive strength mask.
+pub(crate) const GMAC_PADCFG_DS_MASK: u32 = 0b11;
+
+/// Number of pads per GMAC.
+pub const GMAC_PADS: usize = 14;
+
+/// GMAC RGMII signals, one pad each.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GmacSignal {
+    Mdc = 0,
+    Mdio = 1,
+    Rxd0 = 2,
+    Rxd1 = 3,
+    Rxd2 = 4,
+    Rxd3 = 5,
+    Rxdv = 6,
+    Rxc = 7,
+    Txd0 = 8,
+    Txd1 = 9,
+    Txd2 = 10,
+    Txd3 = 11,
+    Txen = 12,
+    Txc = 13,
+}
+
+impl GmacSignal {
+    /// Gets the GMAC signal `n`, in register order.
+    pub const fn new(n: u32) -> Result<Self> {
+        match n {
+            0 => Ok(Self::Mdc),
+            1 => Ok(Self::Mdio),
+            2 => Ok(Self::Rxd0),
+            3 => Ok(Self::Rxd1),
+            4 => Ok(Self::Rxd2),
+            5 => Ok(Self::Rxd3),
+            6 => Ok(Self::Rxdv),
+            7 => Ok(Self::Rxc),
+            8 => Ok(Self::Txd0),
+            9 => Ok(Self::Txd1),
+            10 => Ok(Self::Txd2),
+            11 => Ok(Self::Txd3),
+            12 => Ok(Self::Txen),
+            13 => Ok(Self::Txc),
+            _ => Err(Error::InvalidPad(n)),
+        }
+    }
+}
+
+/// Represents a GMAC RGMII pad.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GmacPad {
+    /// GMAC0 pad, in the AON pinctrl block.
+    Gmac0(GmacSignal),
+    /// GMAC1 pad, in the SYS pinctrl block.
+    Gmac1(GmacSignal),
+}
+
+impl GmacPad {
+    /// Gets the pad configuration register index, in the pinctrl block of the pad.
+    pub(crate) const fn padcfg_index(self) -> usize {
+        match self {
+            Self::Gmac0(signal) => AON_GMAC0_PADCFG + signal as usize,
+            Self::Gmac1(signal) => SYS_GMAC1_PADCFG + signal as usize,
+        }
+    }
+
+    fn padcfg(self) -> *mut u32 {
+        let base = match self {
+            Self::Gmac0(_) => pac::AonPinctrl::ptr() as *mut u32,
+            Self::Gmac1(_) => pac::SysPinctrl::ptr() as *mut u32,
+        };
+
+        // SAFETY: the index is the pad configuration register of the pad in its pinctrl block
+        unsafe { base.add(self.padcfg_index()) }
+    }
+}
+
+impl TryFrom<Pad> for GmacPad {
+    type Error = Error;
+
+    fn try_from(val: Pad) -> Result<Self> {
+        match u32::from(val) {
+            n @ PAD_GMAC1_MDC..=PAD_GMAC1_TXC => {
+                GmacSignal::new(n - PAD_GMAC1_MDC).map(Self::Gmac1)
+            }
+            n => Err(Error::InvalidPad(n)),
+        }
+    }
+}
+
+/// Represents the electrical configuration of a GMAC pad.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GmacPadConfig {
+    drive_strength: u8,
+}
+
+impl GmacPadConfig {
+    /// Maximum drive strength level.
+    pub const MAX_DRIVE_STRENGTH: u8 = GMAC_PADCFG_DS_MASK as u8;
+
+    /// Creates a new [GmacPadConfig].
+    pub const fn new() -> Self {
+        Self { drive_strength: 0 }
+    }
+
+    /// Gets the drive strength level, higher is stronger.
+    pub const fn drive_strength(&self) -> u8 {
+        self.drive_strength
+    }
+
+    /// Builder function that sets the drive strength level.
+    ///
+    /// Levels above [MAX_DRIVE_STRENGTH](Self::MAX_DRIVE_STRENGTH) are clamped.
+    pub const fn with_drive_strength(mut self, val: u8) -> Self {
+        self.drive_strength = if val > Self::MAX_DRIVE_STRENGTH {
+            Self::MAX_DRIVE_STRENGTH
+        } else {
+            val
+        };
+        self
+    }
+
+    /// Merges the [GmacPadConfig] into a pad configuration register value.
+    pub const fn merge_bits(&self, bits: u32) -> u32 {
+        (bits & !GMAC_PADCFG_DS_MASK) | (self.drive_strength as u32 & GMAC_PADCFG_DS_MASK)
+    }
+
+    /// Creates a [GmacPadConfig] from a pad configuration register value.
+    pub const fn from_bits(bits: u32) -> Self {
+        Self {
+            drive_strength: (bits & GMAC_PADCFG_DS_MASK) as u8,
+        }
+    }
+
+    /// Reads the [GmacPadConfig] of a GMAC pad.
+    pub fn read(pad: GmacPad) -> Self {
+        // SAFETY: `padcfg` is the pad configuration register of `pad`
+        Self::from_bits(unsafe { pad.padcfg().read_volatile() })
+    }
+
+    /// Writes the [GmacPadConfig] to a GMAC pad, preserving the unknown register bits.
+    pub fn apply(&self, pad: GmacPad) {
+        let reg = pad.padcfg();
+
+        // SAFETY: `reg` is the pad configuration register of `pad`
+        unsafe { reg.write_volatile(self.merge_bits(reg.read_volatile())) };
+    }
+
+    /// Writes the [GmacPadConfig] to a list of GMAC pads.
+    pub fn apply_all(&self, pads: &[GmacPad]) {
+        pads.iter().for_each(|&pad| self.apply(pad));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gmac_padcfg_index() {
+        assert_eq!(GmacPad::Gmac0(GmacSignal::Mdc).padcfg_index(), 0x58 >> 2);
+        assert_eq!(GmacPad::Gmac0(GmacSignal::Txd0).padcfg_index(), 0x78 >> 2);
+        assert_eq!(GmacPad::Gmac0(GmacSignal::Txc).padcfg_index(), 0x8c >> 2);
+        assert_eq!(GmacPad::Gmac1(GmacSignal::Mdc).padcfg_index(), 0x24c >> 2);
+        assert_eq!(GmacPad::Gmac1(GmacSignal::Txd0).padcfg_index(), 0x26c >> 2);
+        assert_eq!(GmacPad::Gmac1(GmacSignal::Txc).padcfg_index(), 0x280 >> 2);
+    }
+
+    #[test]
+    fn test_gmac_pad() {
+        for n in 0..GMAC_PADS as u32 {
+            let signal = GmacSignal::new(n).unwrap();
+            let pad = Pad::from(PAD_GMAC1_MDC + n);
+
+            assert_eq!(signal as u32, n);
+            assert_eq!(GmacPad::try_from(pad), Ok(GmacPad::Gmac1(signal)));
+        }
+
+        assert_eq!(
+            GmacSignal::new(GMAC_PADS as u32),
+            Err(Error::InvalidPad(GMAC_PADS as u32))
+        );
+        assert_eq!(
+            GmacPad::try_from(Pad::Sd0Strb),
+            Err(Error::InvalidPad(u32::from(Pad::Sd0Strb)))
+        );
+        assert_eq!(
+            GmacPad::try_from(Pad::QspiSclk),
+            Err(Error::InvalidPad(u32::from(Pad::QspiSclk)))
+        );
+    }
+
+    #[test]
+    fn test_gmac_pad_config_bits() {
+        let cfg = GmacPadConfig::new().with_drive_strength(1);
+
+        assert_eq!(cfg.merge_bits(0), 0b01);
+        assert_eq!(cfg.merge_bits(0xff), 0xfd);
+        assert_eq!(GmacPadConfig::from_bits(0xfd), cfg);
+        assert_eq!(
+            GmacPadConfig::new().with_drive_strength(7).drive_strength(),
+            GmacPadConfig::MAX_DRIVE_STRENGTH
+        );
+    }
+}
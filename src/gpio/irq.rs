@@ -0,0 +1,411 @@
+//! GPIO interrupts.
+//!
+//! Register layout follows the Linux [`pinctrl-starfive-jh7110`](https://github.com/torvalds/linux/blob/master/drivers/pinctrl/starfive/pinctrl-starfive-jh7110.c) driver.
+//!
+//! All GPIO pads of a pinctrl block share one PLIC interrupt: [SYS_GPIO_IRQ] for GPIO 0-63, and
+//! [AON_GPIO_IRQ] for the AON pads. The handler finds the pads that fired with [dispatch] and
+//! [dispatch_aon].
+//!
+//! The interrupt registers hold one bit per pad, and are updated in a critical section, so the
+//! handler and task code can configure different pads of the same register.
+
+use critical_section::CriticalSection;
+
+use super::{AonPad, Pad};
+use crate::pac;
+
+/// PLIC interrupt number of the SYS GPIO pads.
+pub const SYS_GPIO_IRQ: u16 = 86;
+/// PLIC interrupt number of the AON GPIO pads.
+pub const AON_GPIO_IRQ: u16 = 85;
+
+/// Number of SYS GPIO pads with interrupt support.
+pub const SYS_IRQ_PADS: u32 = 64;
+
+/// SYS GPIO input register index, one bit per pad for GPIO 0-31 and 32-63.
+pub(crate) const SYS_GPIO_IN: usize = 0x118 >> 2;
+
+/// Register indices of a GPIO interrupt block, each register holds one bit per pad.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct IrqRegs {
+    /// Global interrupt enable.
+    pub en: usize,
+    /// Interrupt sense: edge (1) or level (0) triggered.
+    pub is: usize,
+    /// Interrupt clear, for edge triggered interrupts.
+    pub ic: usize,
+    /// Both edges trigger the interrupt.
+    pub ibe: usize,
+    /// Interrupt event: rising edge or high level (1), falling edge or low level (0).
+    pub iev: usize,
+    /// Interrupt enable (unmask).
+    pub ie: usize,
+    /// Raw interrupt status.
+    pub ris: usize,
+    /// Masked interrupt status.
+    pub mis: usize,
+}
+
+/// SYS GPIO interrupt registers, two words per register for GPIO 0-31 and 32-63.
+pub(crate) const SYS_IRQ_REGS: IrqRegs = IrqRegs {
+    en: 0x0dc >> 2,
+    is: 0x0e0 >> 2,
+    ic: 0x0e8 >> 2,
+    ibe: 0x0f0 >> 2,
+    iev: 0x0f8 >> 2,
+    ie: 0x100 >> 2,
+    ris: 0x108 >> 2,
+    mis: 0x110 >> 2,
+};
+
+/// AON GPIO interrupt registers.
+pub(crate) const AON_IRQ_REGS: IrqRegs = IrqRegs {
+    en: 0x0c >> 2,
+    is: 0x10 >> 2,
+    ic: 0x14 >> 2,
+    ibe: 0x18 >> 2,
+    iev: 0x1c >> 2,
+    ie: 0x20 >> 2,
+    ris: 0x24 >> 2,
+    mis: 0x28 >> 2,
+};
+
+/// Represents the GPIO interrupt trigger.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Edge {
+    /// Triggers on rising edges.
+    #[default]
+    Rising,
+    /// Triggers on falling edges.
+    Falling,
+    /// Triggers on both rising and falling edges.
+    Both,
+    /// Triggers while the pin is high.
+    High,
+    /// Triggers while the pin is low.
+    Low,
+}
+
+impl Edge {
+    /// Creates a new [Edge].
+    pub const fn new() -> Self {
+        Self::Rising
+    }
+
+    /// Gets whether the [Edge] is edge triggered (`IS`), as opposed to level triggered.
+    pub const fn is_edge(&self) -> bool {
+        matches!(self, Self::Rising | Self::Falling | Self::Both)
+    }
+
+    /// Gets whether the [Edge] triggers on both edges (`IBE`).
+    pub const fn is_both(&self) -> bool {
+        matches!(self, Self::Both)
+    }
+
+    /// Gets whether the [Edge] triggers on a rising edge or high level (`IEV`).
+    pub const fn is_rising(&self) -> bool {
+        matches!(self, Self::Rising | Self::High)
+    }
+}
+
+// Gets the register index and bit mask of `n` for a one bit per pad register at `base`.
+pub(crate) const fn irq_field(base: usize, n: u32) -> (usize, u32) {
+    (base + (n / 32) as usize, 1 << (n % 32))
+}
+
+// Represents a pinctrl block with GPIO interrupts.
+#[derive(Clone, Copy)]
+pub(crate) struct IrqBlock {
+    base: *mut u32,
+    regs: IrqRegs,
+}
+
+impl IrqBlock {
+    pub(crate) fn sys() -> Self {
+        Self {
+            base: pac::SysPinctrl::ptr() as *mut u32,
+            regs: SYS_IRQ_REGS,
+        }
+    }
+
+    pub(crate) fn aon() -> Self {
+        Self {
+            base: pac::AonPinctrl::ptr() as *mut u32,
+            regs: AON_IRQ_REGS,
+        }
+    }
+
+    // Points the block at host memory, for tests.
+    #[cfg(test)]
+    pub(crate) const fn from_ptr(base: *mut u32, regs: IrqRegs) -> Self {
+        Self { base, regs }
+    }
+
+    fn read(&self, index: usize) -> u32 {
+        // SAFETY: `index` is one of the interrupt registers of the pinctrl block
+        unsafe { self.base.add(index).read_volatile() }
+    }
+
+    fn write(&self, index: usize, val: u32) {
+        // SAFETY: `index` is one of the interrupt registers of the pinctrl block
+        unsafe { self.base.add(index).write_volatile(val) }
+    }
+
+    // Takes the critical section token: the registers are shared by 32 pads.
+    fn set_bit(&self, _cs: CriticalSection, base: usize, n: u32, set: bool) {
+        let (index, mask) = irq_field(base, n);
+        let val = self.read(index);
+
+        self.write(index, if set { val | mask } else { val & !mask });
+    }
+
+    fn bit(&self, base: usize, n: u32) -> bool {
+        let (index, mask) = irq_field(base, n);
+        self.read(index) & mask != 0
+    }
+
+    pub(crate) fn listen(&self, n: u32, edge: Edge) {
+        let regs = self.regs;
+
+        critical_section::with(|cs| {
+            self.set_bit(cs, regs.ie, n, false);
+
+            self.set_bit(cs, regs.is, n, edge.is_edge());
+            self.set_bit(cs, regs.ibe, n, edge.is_both());
+            self.set_bit(cs, regs.iev, n, edge.is_rising());
+
+            self.clear_in(cs, n);
+            self.write(regs.en, 1);
+            self.set_bit(cs, regs.ie, n, true);
+        })
+    }
+
+    pub(crate) fn unlisten(&self, n: u32) {
+        critical_section::with(|cs| {
+            self.set_bit(cs, self.regs.ie, n, false);
+            self.clear_in(cs, n);
+        })
+    }
+
+    pub(crate) fn is_pending(&self, n: u32) -> bool {
+        self.bit(self.regs.mis, n)
+    }
+
+    pub(crate) fn is_enabled(&self, n: u32) -> bool {
+        self.bit(self.regs.ie, n)
+    }
+
+    // Masks the interrupt, keeping its trigger configuration.
+    pub(crate) fn disable(&self, n: u32) {
+        critical_section::with(|cs| self.set_bit(cs, self.regs.ie, n, false))
+    }
+
+    pub(crate) fn clear(&self, n: u32) {
+        critical_section::with(|cs| self.clear_in(cs, n))
+    }
+
+    fn clear_in(&self, cs: CriticalSection, n: u32) {
+        // edge flags are cleared on the rising edge of the clear bit
+        self.set_bit(cs, self.regs.ic, n, false);
+        self.set_bit(cs, self.regs.ic, n, true);
+    }
+
+    // Gets the input level of a pad of the SYS block, pads without an input register read low.
+    pub(crate) fn input(&self, n: u32) -> bool {
+        n < SYS_IRQ_PADS && self.bit(SYS_GPIO_IN, n)
+    }
+
+    // Gets the pending interrupts of the SYS block, one bit per pad.
+    pub(crate) fn pending(&self) -> u64 {
+        let mis = self.regs.mis;
+
+        (self.read(mis) as u64) | ((self.read(mis + 1) as u64) << 32)
+    }
+}
+
+/// Enables the interrupt of a SYS GPIO pad, triggered by `edge`.
+///
+/// Pads without interrupt support (see [SYS_IRQ_PADS]) are left untouched.
+pub fn listen(pad: Pad, edge: Edge) {
+    let n = u32::from(pad);
+
+    if n < SYS_IRQ_PADS {
+        IrqBlock::sys().listen(n, edge);
+    }
+}
+
+/// Disables the interrupt of a SYS GPIO pad.
+pub fn unlisten(pad: Pad) {
+    let n = u32::from(pad);
+
+    if n < SYS_IRQ_PADS {
+        IrqBlock::sys().unlisten(n);
+    }
+}
+
+/// Gets whether the interrupt of a SYS GPIO pad is pending.
+pub fn is_interrupt_pending(pad: Pad) -> bool {
+    let n = u32::from(pad);
+
+    n < SYS_IRQ_PADS && IrqBlock::sys().is_pending(n)
+}
+
+/// Clears the pending edge interrupt of a SYS GPIO pad.
+///
+/// Level interrupts stay pending until the level changes.
+pub fn clear_interrupt(pad: Pad) {
+    let n = u32::from(pad);
+
+    if n < SYS_IRQ_PADS {
+        IrqBlock::sys().clear(n);
+    }
+}
+
+// Gets the input level of a SYS GPIO pad, pads without an input register read low.
+pub(crate) fn sys_input(n: u32) -> bool {
+    IrqBlock::sys().input(n)
+}
+
+// Gets the input levels of the SYS GPIO pads, one bit per pad.
+pub(crate) fn sys_inputs() -> u64 {
+    let block = IrqBlock::sys();
+
+    (block.read(SYS_GPIO_IN) as u64) | ((block.read(SYS_GPIO_IN + 1) as u64) << 32)
+}
+
+/// Gets the pending interrupts of the SYS GPIO pads, one bit per pad.
+pub fn pending_interrupts() -> u64 {
+    IrqBlock::sys().pending()
+}
+
+/// Calls `f` for each SYS GPIO pad with a pending interrupt, lowest pad first.
+///
+/// The edge interrupt of each pad is cleared before calling `f`. Returns the number of pads.
+///
+/// # Example
+///
+/// ```no_run
+/// use jh71xx_hal::gpio;
+///
+/// // from the SYS GPIO interrupt handler
+/// gpio::dispatch(|pad| {
+///     if pad == gpio::Pad::Gpio38 {
+///         // handle the button press
+///     }
+/// });
+/// ```
+pub fn dispatch(mut f: impl FnMut(Pad)) -> usize {
+    let block = IrqBlock::sys();
+
+    for_each_bit(pending_interrupts(), |n| {
+        block.clear(n);
+        f(Pad::from(n));
+    })
+}
+
+/// Enables the interrupt of an AON GPIO pad, triggered by `edge`.
+pub fn listen_aon(pad: AonPad, edge: Edge) {
+    IrqBlock::aon().listen(pad.into(), edge);
+}
+
+/// Disables the interrupt of an AON GPIO pad.
+pub fn unlisten_aon(pad: AonPad) {
+    IrqBlock::aon().unlisten(pad.into());
+}
+
+/// Gets whether the interrupt of an AON GPIO pad is pending.
+pub fn is_aon_interrupt_pending(pad: AonPad) -> bool {
+    IrqBlock::aon().is_pending(pad.into())
+}
+
+/// Clears the pending edge interrupt of an AON GPIO pad.
+pub fn clear_aon_interrupt(pad: AonPad) {
+    IrqBlock::aon().clear(pad.into());
+}
+
+/// Gets the pending interrupts of the AON GPIO pads, one bit per pad.
+pub fn aon_pending_interrupts() -> u8 {
+    let block = IrqBlock::aon();
+    (block.read(block.regs.mis) & 0xf) as u8
+}
+
+/// Calls `f` for each AON GPIO pad with a pending interrupt, see [dispatch].
+pub fn dispatch_aon(mut f: impl FnMut(AonPad)) -> usize {
+    let block = IrqBlock::aon();
+
+    for_each_bit(aon_pending_interrupts() as u64, |n| {
+        block.clear(n);
+        if let Ok(pad) = AonPad::new(n) {
+            f(pad);
+        }
+    })
+}
+
+// Calls `f` with the index of each set bit of `bits`, lowest first, and returns the count.
+pub(crate) fn for_each_bit(mut bits: u64, mut f: impl FnMut(u32)) -> usize {
+    let mut count = 0;
+
+    while bits != 0 {
+        f(bits.trailing_zeros());
+        bits &= bits - 1;
+        count += 1;
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge() {
+        let cfg = |e: Edge| (e.is_edge(), e.is_both(), e.is_rising());
+
+        assert_eq!(cfg(Edge::Rising), (true, false, true));
+        assert_eq!(cfg(Edge::Falling), (true, false, false));
+        assert_eq!(cfg(Edge::Both), (true, true, false));
+        assert_eq!(cfg(Edge::High), (false, false, true));
+        assert_eq!(cfg(Edge::Low), (false, false, false));
+    }
+
+    #[test]
+    fn test_irq_field() {
+        assert_eq!(irq_field(SYS_IRQ_REGS.ie, 0), (0x100 >> 2, 1));
+        assert_eq!(irq_field(SYS_IRQ_REGS.ie, 31), (0x100 >> 2, 1 << 31));
+        assert_eq!(irq_field(SYS_IRQ_REGS.ie, 32), (0x104 >> 2, 1));
+        assert_eq!(irq_field(SYS_IRQ_REGS.mis, 63), (0x114 >> 2, 1 << 31));
+        assert_eq!(irq_field(AON_IRQ_REGS.ic, 3), (0x14 >> 2, 1 << 3));
+    }
+
+    #[test]
+    fn test_sys_input() {
+        let mut regs = [0u32; SYS_GPIO_IN + 2];
+        regs[SYS_GPIO_IN] = 1;
+        regs[SYS_GPIO_IN + 1] = 1 << 31;
+
+        let block = IrqBlock::from_ptr(regs.as_mut_ptr(), SYS_IRQ_REGS);
+
+        assert!(block.input(u32::from(Pad::Gpio0)));
+        assert!(!block.input(u32::from(Pad::Gpio31)));
+        assert!(!block.input(u32::from(Pad::Gpio62)));
+        assert!(block.input(u32::from(Pad::Gpio63)));
+        assert!(!block.input(SYS_IRQ_PADS));
+    }
+
+    #[test]
+    fn test_for_each_bit() {
+        let mut bits = [0u32; 4];
+        let mut i = 0;
+
+        let count = for_each_bit((1 << 63) | (1 << 32) | 0b101, |n| {
+            bits[i] = n;
+            i += 1;
+        });
+
+        assert_eq!(count, 4);
+        assert_eq!(bits, [0, 2, 32, 63]);
+        assert_eq!(for_each_bit(0, |_| panic!()), 0);
+    }
+}
@@ -0,0 +1,266 @@
+//! Multi-pin GPIO ports.
+//!
+//! A [GpioPort] owns a set of [DynPin]s on GPIO 0-63, and reads or writes them as one `u64`
+//! with one bit per pad.
+//!
+//! Writes use one read-modify-write per FMUX register: pads `4n..4n+3` share a register and
+//! change together, pads in different registers change one register after the other. Reads use
+//! the two input registers.
+//!
+//! ```no_run
+//! use jh71xx_hal::{gpio, pac};
+//!
+//! let dp = pac::Peripherals::take().unwrap();
+//! let padcfg = dp.sys_pinctrl.padcfg();
+//!
+//! let mut bus = gpio::GpioPort::new([
+//!     gpio::get_gpio(padcfg.gpio44()).into_enabled_output().into_dynamic(),
+//!     gpio::get_gpio(padcfg.gpio45()).into_enabled_output().into_dynamic(),
+//!     gpio::get_gpio(padcfg.gpio46()).into_enabled_output().into_dynamic(),
+//!     gpio::get_gpio(padcfg.gpio47()).into_enabled_output().into_dynamic(),
+//! ])
+//! .unwrap();
+//!
+//! // data bit `i` drives the pin at index `i`
+//! bus.write_word(0b1010);
+//! ```
+
+use super::*;
+
+/// Represents a set of SYS GPIO pins read and written together.
+pub struct GpioPort<'g, const N: usize> {
+    pins: [DynPin<'g>; N],
+    mask: u64,
+    output_mask: u64,
+    open_drain_mask: u64,
+}
+
+impl<'g, const N: usize> GpioPort<'g, N> {
+    /// Creates a new [GpioPort] from input, output or open-drain pins on GPIO 0-63.
+    ///
+    /// Returns an error for pads outside GPIO 0-63, pads used twice, and disabled or alternate
+    /// function pins.
+    pub fn new(pins: [DynPin<'g>; N]) -> Result<Self> {
+        let mut mask = 0u64;
+        let mut output_mask = 0u64;
+        let mut open_drain_mask = 0u64;
+
+        for pin in pins.iter() {
+            let pad = u32::from(pin.pad());
+            let bit = 1u64 << (pad % FMUX_PADS);
+
+            if pad >= FMUX_PADS || mask & bit != 0 {
+                return Err(Error::InvalidPad(pad));
+            }
+
+            match pin.mode() {
+                DynMode::Input => (),
+                DynMode::Output => output_mask |= bit,
+                DynMode::OpenDrain => open_drain_mask |= bit,
+                mode => return Err(Error::InvalidMode(mode)),
+            }
+
+            mask |= bit;
+        }
+
+        Ok(Self {
+            pins,
+            mask,
+            output_mask,
+            open_drain_mask,
+        })
+    }
+
+    /// Gets the pads of the [GpioPort], one bit per pad.
+    pub const fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    /// Gets the pins of the [GpioPort].
+    pub fn pins(&self) -> &[DynPin<'g>] {
+        self.pins.as_ref()
+    }
+
+    /// Releases the pins of the [GpioPort].
+    pub fn release(self) -> [DynPin<'g>; N] {
+        self.pins
+    }
+
+    /// Reads the levels of the port pads, one bit per pad.
+    pub fn read(&self) -> u64 {
+        sys_inputs() & self.mask
+    }
+
+    /// Writes the port output pads selected by `mask`, one bit per pad.
+    ///
+    /// Open-drain pins are released for set bits, and driven low for cleared bits. Input pins and
+    /// pads outside the port are left untouched.
+    pub fn write(&mut self, mask: u64, value: u64) {
+        write_fmux(
+            SYS_DOUT,
+            SYS_DOUT_MASK,
+            mask & self.output_mask,
+            value,
+            [GpoFunction::LOW, GpoFunction::HIGH],
+        );
+
+        write_fmux(
+            SYS_DOEN,
+            SYS_DOEN_MASK,
+            mask & self.open_drain_mask,
+            value,
+            [OutputConfig::Low.into(), OutputConfig::Neutral.into()],
+        );
+    }
+
+    /// Reads the port pads as a data word, bit `i` is the level of the pin at index `i`.
+    pub fn read_word(&self) -> u64 {
+        gather_word(&self.pins, self.read())
+    }
+
+    /// Writes a data word to the port pads, bit `i` drives the pin at index `i`.
+    pub fn write_word(&mut self, word: u64) {
+        self.write(self.mask, spread_word(&self.pins, word));
+    }
+}
+
+// Writes the FMUX bytes of the pads set in `mask` to `vals[0]` or `vals[1]` following `value`,
+// with one read-modify-write per register.
+fn write_fmux(base: usize, field_mask: u8, mask: u64, value: u64, vals: [u8; 2]) {
+    let mut pending = mask;
+
+    while pending != 0 {
+        let first = pending.trailing_zeros() & !3;
+        let (index, _) = fmux_field(base, first);
+
+        modify_sys_fmux_reg(index, |r| {
+            fmux_port_update(r, first, mask, value, field_mask, vals)
+        });
+
+        pending &= !(0xf << first);
+    }
+}
+
+// Updates the FMUX register value of pads `first..first + 4` set in `mask`.
+const fn fmux_port_update(
+    mut reg: u32,
+    first: u32,
+    mask: u64,
+    value: u64,
+    field_mask: u8,
+    vals: [u8; 2],
+) -> u32 {
+    let mut i = 0;
+
+    while i < 4 {
+        let bit = 1u64 << (first + i);
+
+        if mask & bit != 0 {
+            let val = vals[(value & bit != 0) as usize];
+            reg = fmux_update(reg, i * 8, field_mask, val);
+        }
+
+        i += 1;
+    }
+
+    reg
+}
+
+// Maps bit `i` of `word` onto the pad of `pins[i]`.
+fn spread_word(pins: &[DynPin], word: u64) -> u64 {
+    pins.iter()
+        .enumerate()
+        .filter(|(i, _)| word & (1 << i) != 0)
+        .fold(0, |acc, (_, pin)| acc | (1 << u32::from(pin.pad())))
+}
+
+// Maps the pad of `pins[i]` in `bits` onto bit `i`.
+fn gather_word(pins: &[DynPin], bits: u64) -> u64 {
+    pins.iter()
+        .enumerate()
+        .filter(|(_, pin)| bits & (1 << u32::from(pin.pad())) != 0)
+        .fold(0, |acc, (i, _)| acc | (1 << i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pin(pad: Pad, mode: DynMode) -> DynPin<'static> {
+        DynPin::new(pad, mode)
+    }
+
+    #[test]
+    fn test_port_new() {
+        let port = GpioPort::new([
+            pin(Pad::Gpio44, DynMode::Output),
+            pin(Pad::Gpio3, DynMode::OpenDrain),
+            pin(Pad::Gpio63, DynMode::Input),
+        ])
+        .unwrap();
+
+        assert_eq!(port.mask(), (1 << 44) | (1 << 3) | (1 << 63));
+        assert_eq!(port.output_mask, 1 << 44);
+        assert_eq!(port.open_drain_mask, 1 << 3);
+
+        let dup = [
+            pin(Pad::Gpio5, DynMode::Output),
+            pin(Pad::Gpio5, DynMode::Input),
+        ];
+        assert!(matches!(GpioPort::new(dup), Err(Error::InvalidPad(5))));
+
+        let sd = [pin(Pad::Sd0Clk, DynMode::Output)];
+        assert!(matches!(GpioPort::new(sd), Err(Error::InvalidPad(64))));
+
+        let alt = [pin(Pad::Gpio5, DynMode::Alternate)];
+        assert!(matches!(
+            GpioPort::new(alt),
+            Err(Error::InvalidMode(DynMode::Alternate))
+        ));
+    }
+
+    #[test]
+    fn test_fmux_port_update() {
+        let vals = [GpoFunction::LOW, GpoFunction::HIGH];
+
+        assert_eq!(
+            fmux_port_update(
+                0x1414_1414,
+                4,
+                0b1111 << 4,
+                0b0101 << 4,
+                SYS_DOUT_MASK,
+                vals
+            ),
+            0x0001_0001
+        );
+        assert_eq!(
+            fmux_port_update(
+                0x1414_1414,
+                4,
+                0b0110 << 4,
+                0b1110 << 4,
+                SYS_DOUT_MASK,
+                vals
+            ),
+            0x1401_0114
+        );
+        assert_eq!(
+            fmux_port_update(0xffff_ffff, 60, 1 << 63, 0, SYS_DOEN_MASK, [0, 1]),
+            0xc0ff_ffff
+        );
+    }
+
+    #[test]
+    fn test_word() {
+        let pins = [
+            pin(Pad::Gpio44, DynMode::Output),
+            pin(Pad::Gpio3, DynMode::Output),
+            pin(Pad::Gpio63, DynMode::Output),
+        ];
+
+        assert_eq!(spread_word(&pins, 0b101), (1 << 44) | (1 << 63));
+        assert_eq!(spread_word(&pins, 0b010), 1 << 3);
+        assert_eq!(gather_word(&pins, (1 << 3) | (1 << 63) | 1), 0b110);
+    }
+}
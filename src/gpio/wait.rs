@@ -0,0 +1,206 @@
+//! Async GPIO inputs.
+//!
+//! Each wait enables the edge or level interrupt of its pad, and stores the task [Waker] in a
+//! per-pad table. The [SYS_GPIO_IRQ] handler calls [wake_pending], which masks the pads that fired
+//! and wakes the waiting tasks.
+//!
+//! Only GPIO 0-63 support interrupts (see [SYS_IRQ_PADS]), waiting on other pads returns
+//! [Error::InvalidPad].
+
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use critical_section::Mutex;
+use embedded_hal_async::digital::Wait;
+
+use super::*;
+
+type WakerSlot = Mutex<RefCell<Option<Waker>>>;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_SLOT: WakerSlot = Mutex::new(RefCell::new(None));
+
+static SYS_WAKERS: [WakerSlot; SYS_IRQ_PADS as usize] = [EMPTY_SLOT; SYS_IRQ_PADS as usize];
+
+/// Wakes the tasks waiting on SYS GPIO pads with a pending interrupt.
+///
+/// Call from the [SYS_GPIO_IRQ] handler. The interrupt of each pad is masked until the waiting
+/// task completes, so [wake_pending] and [dispatch] should not be used for the same pads.
+///
+/// Returns the number of pads.
+///
+/// # Example
+///
+/// ```no_run
+/// use jh71xx_hal::gpio;
+///
+/// // from the SYS GPIO interrupt handler
+/// gpio::wake_pending();
+/// ```
+pub fn wake_pending() -> usize {
+    wake_block(IrqBlock::sys())
+}
+
+fn wake_block(block: IrqBlock) -> usize {
+    for_each_bit(block.pending(), |n| {
+        // level interrupts fire until the level changes, keep them masked
+        block.disable(n);
+        block.clear(n);
+
+        if let Some(waker) =
+            critical_section::with(|cs| SYS_WAKERS[n as usize].borrow_ref_mut(cs).take())
+        {
+            waker.wake();
+        }
+    })
+}
+
+// Completes once the interrupt of a SYS GPIO pad fired.
+struct InputFuture {
+    block: IrqBlock,
+    n: u32,
+}
+
+impl InputFuture {
+    fn new(block: IrqBlock, pad: u32, edge: Edge) -> Result<Self> {
+        if pad < SYS_IRQ_PADS {
+            block.listen(pad, edge);
+            Ok(Self { block, n: pad })
+        } else {
+            Err(Error::InvalidPad(pad))
+        }
+    }
+}
+
+impl Future for InputFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        critical_section::with(|cs| {
+            let mut slot = SYS_WAKERS[self.n as usize].borrow_ref_mut(cs);
+
+            match slot.as_ref() {
+                Some(waker) if waker.will_wake(cx.waker()) => (),
+                _ => *slot = Some(cx.waker().clone()),
+            }
+        });
+
+        // the waker is stored before checking, so an interrupt in between still wakes the task
+        if self.block.is_enabled(self.n) {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+impl Drop for InputFuture {
+    fn drop(&mut self) {
+        self.block.unlisten(self.n);
+        critical_section::with(|cs| SYS_WAKERS[self.n as usize].borrow_ref_mut(cs).take());
+    }
+}
+
+impl<'g, GPIO: GpioCfg, MODE> Gpio<'g, GPIO, Enabled, Input, MODE> {
+    async fn wait_for(&mut self, edge: Edge) -> Result<()> {
+        InputFuture::new(IrqBlock::sys(), GPIO::pad(), edge)?.await;
+        Ok(())
+    }
+}
+
+impl<'g, GPIO: GpioCfg, MODE> Wait for Gpio<'g, GPIO, Enabled, Input, MODE> {
+    async fn wait_for_high(&mut self) -> Result<()> {
+        if self.bit_is_set() {
+            Ok(())
+        } else {
+            self.wait_for(Edge::High).await
+        }
+    }
+
+    async fn wait_for_low(&mut self) -> Result<()> {
+        if self.bit_is_set() {
+            self.wait_for(Edge::Low).await
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<()> {
+        self.wait_for(Edge::Rising).await
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<()> {
+        self.wait_for(Edge::Falling).await
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<()> {
+        self.wait_for(Edge::Both).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use core::task::{RawWaker, RawWakerVTable};
+
+    use super::*;
+
+    static WAKES: AtomicUsize = AtomicUsize::new(0);
+
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |p| RawWaker::new(p, &VTABLE),
+        |_| {
+            WAKES.fetch_add(1, Ordering::SeqCst);
+        },
+        |_| {
+            WAKES.fetch_add(1, Ordering::SeqCst);
+        },
+        |_| (),
+    );
+
+    #[test]
+    fn test_input_future() {
+        // SYS interrupt registers end with MIS at 0x110..0x118
+        let mut regs = [0u32; 0x118 >> 2];
+        let base = regs.as_mut_ptr();
+        let block = IrqBlock::from_ptr(base, SYS_IRQ_REGS);
+        let reg = |index: usize| unsafe { base.add(index).read() };
+
+        // SAFETY: the vtable functions ignore the data pointer
+        let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(matches!(
+            InputFuture::new(block, SYS_IRQ_PADS, Edge::High),
+            Err(Error::InvalidPad(64))
+        ));
+
+        let mut fut = InputFuture::new(block, 40, Edge::High).unwrap();
+        let (ie, mask) = irq_field(SYS_IRQ_REGS.ie, 40);
+
+        assert_eq!(reg(ie), mask);
+        assert_eq!(reg(irq_field(SYS_IRQ_REGS.iev, 40).0), mask);
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Pending);
+
+        // no interrupt pending yet
+        assert_eq!(wake_block(block), 0);
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Pending);
+
+        // the interrupt fires: masked, cleared and woken
+        let wakes = WAKES.load(Ordering::SeqCst);
+        unsafe { base.add(irq_field(SYS_IRQ_REGS.mis, 40).0).write(mask) };
+
+        assert_eq!(wake_block(block), 1);
+        assert_eq!(WAKES.load(Ordering::SeqCst), wakes + 1);
+        assert_eq!(reg(ie), 0);
+        assert_eq!(reg(irq_field(SYS_IRQ_REGS.ic, 40).0), mask);
+        assert_eq!(Pin::new(&mut fut).poll(&mut cx), Poll::Ready(()));
+
+        drop(fut);
+        assert!(critical_section::with(|cs| SYS_WAKERS[40]
+            .borrow_ref(cs)
+            .is_none()));
+    }
+}
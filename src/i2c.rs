@@ -28,6 +28,7 @@ use core::cmp;
 use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::{self, I2c as I2cHal, Operation, SevenBitAddress, TenBitAddress};
 
+use crate::gpio::{AlternatePin, GpioCfg, IntoAlternate};
 use crate::{bitflag_is_set, delay::u74_mdelay};
 
 mod constants;
@@ -62,8 +63,11 @@ bitflags! {
 bitflag_is_set!(Status);
 
 /// I2C host
-pub struct I2c<I2C: I2cPeripheral> {
+///
+/// `PINS` are the pins owned by the [I2c], see [I2c::with_pins].
+pub struct I2c<I2C: I2cPeripheral, PINS = ()> {
     i2c: I2C,
+    pins: PINS,
     status: Status,
     rx_fifo_depth: u32,
     tx_fifo_depth: u32,
@@ -87,6 +91,12 @@ pub struct I2c<I2C: I2cPeripheral> {
     msg_err: i32,
 }
 
+/// Pins owned by an [I2c] created with [I2c::with_pins]: SDA and SCL.
+pub type I2cPinSet<'g, I2C, SDA, SCL> = (
+    AlternatePin<'g, SDA, <I2C as I2cPins>::Sda>,
+    AlternatePin<'g, SCL, <I2C as I2cPins>::Scl>,
+);
+
 impl<I2C: I2cPeripheral> I2c<I2C> {
     /// Creates a new [I2c].
     ///
@@ -98,8 +108,48 @@ impl<I2C: I2cPeripheral> I2c<I2C> {
     /// let _i2c0 = i2c::I2c::new(dp.i2c0);
     /// ```
     pub fn new(i2c: I2C) -> Self {
+        Self::new_inner(i2c, ())
+    }
+}
+
+impl<I2C: I2cPins> I2c<I2C> {
+    /// Creates a new [I2c] owning its SDA and SCL pins.
+    ///
+    /// The pins are routed to the SDA and SCL signals of the peripheral, and are returned by
+    /// [release](I2c::release).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use jh71xx_hal::{gpio, pac, i2c};
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let padcfg = dp.sys_pinctrl.padcfg();
+    ///
+    /// let sda = gpio::get_gpio(padcfg.gpio58());
+    /// let scl = gpio::get_gpio(padcfg.gpio57());
+    ///
+    /// let i2c0 = i2c::I2c::with_pins(dp.i2c0, sda, scl);
+    /// let (_i2c0, (_sda, _scl)) = i2c0.release();
+    /// ```
+    pub fn with_pins<'g, SDA: GpioCfg, SCL: GpioCfg>(
+        i2c: I2C,
+        sda: impl IntoAlternate<'g, SDA>,
+        scl: impl IntoAlternate<'g, SCL>,
+    ) -> I2c<I2C, I2cPinSet<'g, I2C, SDA, SCL>> {
+        let pins = (
+            sda.into_alternate::<I2C::Sda>(),
+            scl.into_alternate::<I2C::Scl>(),
+        );
+
+        I2c::new_inner(i2c, pins)
+    }
+}
+
+impl<I2C: I2cPeripheral, PINS> I2c<I2C, PINS> {
+    fn new_inner(i2c: I2C, pins: PINS) -> Self {
         Self {
             i2c,
+            pins,
             status: Status::default(),
             rx_fifo_depth: 0,
             tx_fifo_depth: 0,
@@ -124,6 +174,11 @@ impl<I2C: I2cPeripheral> I2c<I2C> {
         }
     }
 
+    /// Releases the I2C peripheral and the pins owned by the [I2c].
+    pub fn release(self) -> (I2C, PINS) {
+        (self.i2c, self.pins)
+    }
+
     /// Gets the [Status].
     pub const fn status(&self) -> Status {
         self.status
@@ -167,6 +222,45 @@ impl<I2C: I2cPeripheral> I2c<I2C> {
         };
     }
 
+    /// Computes the SCL high/low period counts for the configured [I2cTimings].
+    ///
+    /// `ic_clk_hz` is the I2C reference clock rate (in Hertz), usually taken from the frozen clock tree:
+    /// [`ClockRates::i2c_apb_hz`](crate::clocks::ClockRates::i2c_apb_hz).
+    ///
+    /// Counts are written to the peripheral by [I2c::init_master].
+    pub fn set_scl_counts(&mut self, ic_clk_hz: u64) {
+        let ic_clk = ic_clk_hz / 1000;
+
+        let sda_fall_ns = match self.timings.sda_fall_ns() {
+            0 => DEFAULT_FALL_NS,
+            ns => ns,
+        };
+        let scl_fall_ns = match self.timings.scl_fall_ns() {
+            0 => DEFAULT_FALL_NS,
+            ns => ns,
+        };
+
+        // tHIGH = 4.0 us, tLOW = 4.7 us
+        self.ss_hcnt = scl_hcnt(ic_clk, 4000, sda_fall_ns);
+        self.ss_lcnt = scl_lcnt(ic_clk, 4700, scl_fall_ns);
+
+        if self.timings.bus_freq_hz() == I2cSpeedMode::FastPlus {
+            // tHIGH = 260 ns, tLOW = 500 ns
+            self.fs_hcnt = scl_hcnt(ic_clk, 260, sda_fall_ns);
+            self.fs_lcnt = scl_lcnt(ic_clk, 500, scl_fall_ns);
+        } else {
+            // tHIGH = 0.6 us, tLOW = 1.3 us
+            self.fs_hcnt = scl_hcnt(ic_clk, 600, sda_fall_ns);
+            self.fs_lcnt = scl_lcnt(ic_clk, 1300, scl_fall_ns);
+        }
+
+        if self.timings.bus_freq_hz() == I2cSpeedMode::High {
+            // tHIGH = 160 ns, tLOW = 320 ns
+            self.hs_hcnt = scl_hcnt(ic_clk, 160, sda_fall_ns);
+            self.hs_lcnt = scl_lcnt(ic_clk, 320, scl_fall_ns);
+        }
+    }
+
     fn read_poll_timeout(
         &mut self,
         poll_fn: impl Fn(&mut dyn I2cPeripheral) -> bool,
@@ -427,11 +521,11 @@ impl<I2C: I2cPeripheral> I2c<I2C> {
     }
 }
 
-impl<I2C: I2cPeripheral> i2c::ErrorType for I2c<I2C> {
+impl<I2C: I2cPeripheral, PINS> i2c::ErrorType for I2c<I2C, PINS> {
     type Error = Error;
 }
 
-impl<I2C: I2cPeripheral> I2cHal<SevenBitAddress> for I2c<I2C> {
+impl<I2C: I2cPeripheral, PINS> I2cHal<SevenBitAddress> for I2c<I2C, PINS> {
     fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<()> {
         let tar = I2cTar::from(address as u32);
         self.xfer_init(tar);
@@ -455,7 +549,7 @@ impl<I2C: I2cPeripheral> I2cHal<SevenBitAddress> for I2c<I2C> {
     }
 }
 
-impl<I2C: I2cPeripheral> I2cHal<TenBitAddress> for I2c<I2C> {
+impl<I2C: I2cPeripheral, PINS> I2cHal<TenBitAddress> for I2c<I2C, PINS> {
     fn transaction(&mut self, address: u16, operations: &mut [Operation<'_>]) -> Result<()> {
         let tar = I2cTar::from(address as u32) | I2cTar::MODE_10BIT;
         self.xfer_init(tar);
@@ -1,6 +1,7 @@
 pub use crate::pac::{I2c0, I2c1, I2c2, I2c3, I2c4, I2c5, I2c6};
 
 use super::registers::*;
+use crate::gpio::{self, Function};
 
 /// Generic access for Synopsis Designware I2C peripherals.
 // FIXME: add `modify_*` methods to only modify set bitfields.
@@ -371,3 +372,28 @@ impl_i2c_peripheral!(I2c3);
 impl_i2c_peripheral!(I2c4);
 impl_i2c_peripheral!(I2c5);
 impl_i2c_peripheral!(I2c6);
+
+/// Pad signals of an [I2cPeripheral], routed by [I2c::with_pins](super::I2c::with_pins).
+pub trait I2cPins: I2cPeripheral {
+    /// Data signal.
+    type Sda: Function;
+    /// Clock signal.
+    type Scl: Function;
+}
+
+macro_rules! impl_i2c_pins {
+    ($i2c:ident, $sda:ident, $scl:ident) => {
+        impl I2cPins for $i2c {
+            type Sda = gpio::$sda;
+            type Scl = gpio::$scl;
+        }
+    };
+}
+
+impl_i2c_pins!(I2c0, I2c0Sda, I2c0Scl);
+impl_i2c_pins!(I2c1, I2c1Sda, I2c1Scl);
+impl_i2c_pins!(I2c2, I2c2Sda, I2c2Scl);
+impl_i2c_pins!(I2c3, I2c3Sda, I2c3Scl);
+impl_i2c_pins!(I2c4, I2c4Sda, I2c4Scl);
+impl_i2c_pins!(I2c5, I2c5Sda, I2c5Scl);
+impl_i2c_pins!(I2c6, I2c6Sda, I2c6Scl);
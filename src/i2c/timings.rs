@@ -1,5 +1,40 @@
 use super::I2cSpeedMode;
 
+/// Default SCL/SDA falling time (in nanoseconds) when none is configured.
+pub const DEFAULT_FALL_NS: u32 = 300;
+
+/// Computes the SCL high period count for a given symbol time.
+///
+/// Uses the Designware default condition: `IC_[FS]S_SCL_HCNT + 3 >= IC_CLK * (tHD;STA + tf)`.
+///
+/// Parameters:
+///
+/// - `ic_clk_khz`: I2C reference clock rate (in kilohertz)
+/// - `t_symbol_ns`: tHIGH period of the speed mode (in nanoseconds)
+/// - `tf_ns`: SDA falling time (in nanoseconds)
+pub const fn scl_hcnt(ic_clk_khz: u64, t_symbol_ns: u32, tf_ns: u32) -> u32 {
+    let cycles = div_round_closest(ic_clk_khz * (t_symbol_ns as u64 + tf_ns as u64), 1_000_000);
+    cycles.saturating_sub(3) as u32
+}
+
+/// Computes the SCL low period count for a given symbol time.
+///
+/// `IC_[FS]S_SCL_LCNT + 1 >= IC_CLK * (tLOW + tf)`
+///
+/// Parameters:
+///
+/// - `ic_clk_khz`: I2C reference clock rate (in kilohertz)
+/// - `t_low_ns`: tLOW period of the speed mode (in nanoseconds)
+/// - `tf_ns`: SCL falling time (in nanoseconds)
+pub const fn scl_lcnt(ic_clk_khz: u64, t_low_ns: u32, tf_ns: u32) -> u32 {
+    let cycles = div_round_closest(ic_clk_khz * (t_low_ns as u64 + tf_ns as u64), 1_000_000);
+    cycles.saturating_sub(1) as u32
+}
+
+const fn div_round_closest(num: u64, den: u64) -> u64 {
+    (num + den / 2) / den
+}
+
 /// I2C timing information
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
@@ -157,3 +192,23 @@ impl I2cTimings {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scl_counts() {
+        // 100 MHz reference clock, standard mode with default falling times
+        assert_eq!(scl_hcnt(100_000, 4000, DEFAULT_FALL_NS), 427);
+        assert_eq!(scl_lcnt(100_000, 4700, DEFAULT_FALL_NS), 499);
+
+        // 49.5 MHz APB clock, fast mode
+        assert_eq!(scl_hcnt(49_500, 600, DEFAULT_FALL_NS), 42);
+        assert_eq!(scl_lcnt(49_500, 1300, DEFAULT_FALL_NS), 78);
+
+        // counts never underflow
+        assert_eq!(scl_hcnt(0, 600, DEFAULT_FALL_NS), 0);
+        assert_eq!(scl_lcnt(0, 1300, DEFAULT_FALL_NS), 0);
+    }
+}
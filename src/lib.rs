@@ -20,6 +20,7 @@ extern crate embedded_io as io;
 
 pub extern crate jh7110_pac as pac;
 
+pub mod board;
 pub mod clocks;
 #[cfg(feature = "rt")]
 pub mod critical_section;
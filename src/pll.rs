@@ -1,16 +1,42 @@
 //! PLL initialization and configuration.
 //!
 //! Based on the [`oreboot`](https://github.com/oreboot/oreboot/blob/main/src/mainboard/starfive/visionfive2/bt0/src/pll.rs) implementation.
+//!
+//! # Register map
+//!
+//! The PLLs are configured by the SYS syscon `SYS_SYSCFG_6..=13` registers (`0x18..=0x34`), with
+//! the fields of the PAC `sys_syscon` register block:
+//!
+//! | PLL  | `DACPD`, `DSMPD` | `FBDIV`        | `FRAC`, `POSTDIV1`, `PD` | `PREDIV`       |
+//! |------|------------------|----------------|--------------------------|----------------|
+//! | PLL0 | `0x18` (CFG6)    | `0x1c` (CFG7)  | `0x20` (CFG8)            | `0x24` (CFG9)  |
+//! | PLL1 | `0x24` (CFG9)    | `0x24` (CFG9)  | `0x28` (CFG10)           | `0x2c` (CFG11) |
+//! | PLL2 | `0x2c` (CFG11)   | `0x2c` (CFG11) | `0x30` (CFG12)           | `0x34` (CFG13) |
+//!
+//! The same offsets are used by the Linux
+//! [`clk-starfive-jh7110-pll`](https://github.com/torvalds/linux/blob/master/drivers/clk/starfive/clk-starfive-jh7110-pll.c)
+//! driver. Neither describes a lock or status field for the PLLs, so PLL lock cannot be polled,
+//! see [Pll::settle].
+
+use embedded_hal::delay::DelayNs;
 
-use crate::pac;
+use crate::clocks::{PllRates, OSC_HZ};
+use crate::{delay, pac};
 
 mod dacpd;
 mod dsmpd;
+mod error;
 mod freq;
+mod id;
 
 pub use dacpd::*;
 pub use dsmpd::*;
+pub use error::*;
 pub use freq::*;
+pub use id::*;
+
+/// Time (in microseconds) for a PLL to lock once powered up.
+pub const PLL_LOCK_US: u32 = 100;
 
 /// Contains configuration registers for SoC PLL peripherals.
 pub struct Pll {
@@ -43,6 +69,7 @@ impl Pll {
     /// let mut pll = pll::Pll::new(dp.sys_syscon);
     ///
     /// pll.set_pll0(pll::Freq::pll0_1ghz());
+    /// pll.settle(pll::PllId::Pll0).unwrap();
     /// ```
     pub fn set_pll0(&mut self, f: Freq) -> &mut Self {
         // Turn-off PD by setting the bit.
@@ -67,6 +94,7 @@ impl Pll {
         // all postdiv values for all PLLs and config to be 1, effectively dropping
         // to 0 here.
         self.syscon.sys_syscfg8().modify(|_, w| {
+            w.pll0_frac().variant(f.frac);
             w.pll0_postdiv1().variant(f.postdiv1 >> 1);
             // Turn on PD by clearing the bit
             w.pll0_pd().on()
@@ -86,6 +114,7 @@ impl Pll {
     /// let mut pll = pll::Pll::new(dp.sys_syscon);
     ///
     /// pll.set_pll1(pll::Freq::pll1_ddr2133_1066mhz());
+    /// pll.settle(pll::PllId::Pll1).unwrap();
     /// ```
     pub fn set_pll1(&mut self, f: Freq) -> &mut Self {
         // Turn-off PD by setting the bit.
@@ -110,6 +139,7 @@ impl Pll {
         // all postdiv values for all PLLs and config to be 1, effectively dropping
         // to 0 here.
         self.syscon.sys_syscfg10().modify(|_, w| {
+            w.pll1_frac().variant(f.frac);
             w.pll1_postdiv1().variant(f.postdiv1 >> 1);
             // Turn on PD by clearing the bit
             w.pll1_pd().on()
@@ -129,6 +159,7 @@ impl Pll {
     /// let mut pll = pll::Pll::new(dp.sys_syscon);
     ///
     /// pll.set_pll2(pll::Freq::pll2_1188mhz());
+    /// pll.settle(pll::PllId::Pll2).unwrap();
     /// ```
     pub fn set_pll2(&mut self, f: Freq) -> &mut Self {
         self.syscon.sys_syscfg12().modify(|_, w| w.pll2_pd().off());
@@ -152,6 +183,7 @@ impl Pll {
         // all postdiv values for all PLLs and config to be 1, effectively dropping
         // to 0 here.
         self.syscon.sys_syscfg12().modify(|_, w| {
+            w.pll2_frac().variant(f.frac);
             w.pll2_postdiv1().variant(f.postdiv1 >> 1);
             // Turn on PD by clearing the bit
             w.pll2_pd().on()
@@ -160,6 +192,121 @@ impl Pll {
         self
     }
 
+    /// Reads the current PLL0 [Freq] settings.
+    pub fn pll0_freq(&self) -> Freq {
+        let cfg6 = self.syscon.sys_syscfg6().read();
+        let cfg8 = self.syscon.sys_syscfg8().read();
+
+        Freq {
+            prediv: self.syscon.sys_syscfg9().read().pll0_prediv().bits(),
+            fbdiv: self.syscon.sys_syscfg7().read().pll0_fbdiv().bits(),
+            postdiv1: 1 << cfg8.pll0_postdiv1().bits(),
+            frac: cfg8.pll0_frac().bits(),
+            dacpd: cfg6.pll0_dacpd().variant().into(),
+            dsmpd: cfg6.pll0_dsmpd().variant().into(),
+        }
+    }
+
+    /// Reads the current PLL1 [Freq] settings.
+    pub fn pll1_freq(&self) -> Freq {
+        let cfg9 = self.syscon.sys_syscfg9().read();
+        let cfg10 = self.syscon.sys_syscfg10().read();
+
+        Freq {
+            prediv: self.syscon.sys_syscfg11().read().pll1_prediv().bits(),
+            fbdiv: cfg9.pll1_fbdiv().bits(),
+            postdiv1: 1 << cfg10.pll1_postdiv1().bits(),
+            frac: cfg10.pll1_frac().bits(),
+            dacpd: cfg9.pll1_dacpd().variant().into(),
+            dsmpd: cfg9.pll1_dsmpd().variant().into(),
+        }
+    }
+
+    /// Reads the current PLL2 [Freq] settings.
+    pub fn pll2_freq(&self) -> Freq {
+        let cfg11 = self.syscon.sys_syscfg11().read();
+        let cfg12 = self.syscon.sys_syscfg12().read();
+
+        Freq {
+            prediv: self.syscon.sys_syscfg13().read().pll2_prediv().bits(),
+            fbdiv: cfg11.pll2_fbdiv().bits(),
+            postdiv1: 1 << cfg12.pll2_postdiv1().bits(),
+            frac: cfg12.pll2_frac().bits(),
+            dacpd: cfg11.pll2_dacpd().variant().into(),
+            dsmpd: cfg11.pll2_dsmpd().variant().into(),
+        }
+    }
+
+    /// Reads the current [Freq] settings of a PLL.
+    pub fn freq(&self, id: PllId) -> Freq {
+        match id {
+            PllId::Pll0 => self.pll0_freq(),
+            PllId::Pll1 => self.pll1_freq(),
+            PllId::Pll2 => self.pll2_freq(),
+        }
+    }
+
+    /// Gets whether a PLL is powered down.
+    pub fn is_powered_down(&self, id: PllId) -> bool {
+        match id {
+            PllId::Pll0 => self.syscon.sys_syscfg8().read().pll0_pd().is_off(),
+            PllId::Pll1 => self.syscon.sys_syscfg10().read().pll1_pd().is_off(),
+            PllId::Pll2 => self.syscon.sys_syscfg12().read().pll2_pd().is_off(),
+        }
+    }
+
+    /// Computes the current output frequency (in Hertz) of a PLL.
+    ///
+    /// Returns zero for a powered down PLL.
+    pub fn output_hz(&self, id: PllId) -> u64 {
+        if self.is_powered_down(id) {
+            0
+        } else {
+            self.freq(id).output_hz(OSC_HZ)
+        }
+    }
+
+    /// Reads the current output frequencies of all PLLs, e.g. to [freeze](crate::clocks::Clocks::freeze) the clock tree.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{clocks, pac, pll};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let pll = pll::Pll::new(dp.sys_syscon);
+    /// let clocks = clocks::Clocks::new(dp.aoncrg, dp.ispcrg, dp.stgcrg, dp.syscrg, dp.voutcrg);
+    ///
+    /// let _rates = clocks.freeze(pll.rates());
+    /// ```
+    pub fn rates(&self) -> PllRates {
+        PllRates::new(
+            self.output_hz(PllId::Pll0),
+            self.output_hz(PllId::Pll1),
+            self.output_hz(PllId::Pll2),
+        )
+    }
+
+    /// Waits [PLL_LOCK_US] microseconds for a PLL to settle after it was programmed.
+    ///
+    /// **NOTE**: the PLLs have no lock indication in the SYS syscon registers (see the
+    /// [register map](crate::pll#register-map)), so lock is not verified. The wait follows the
+    /// lock time of the PLL, as in the vendor boot code.
+    ///
+    /// Call after each of [set_pll0](Self::set_pll0), [set_pll1](Self::set_pll1) and
+    /// [set_pll2](Self::set_pll2), before switching a clock to the PLL.
+    ///
+    /// Returns an error if the PLL is powered down.
+    pub fn settle(&self, id: PllId) -> Result<()> {
+        if self.is_powered_down(id) {
+            return Err(Error::PoweredDown(id));
+        }
+
+        delay::u74_mdelay().delay_us(PLL_LOCK_US);
+
+        Ok(())
+    }
+
     /// Releases ownership of the [SysSyscon](pac::SysSyscon) peripheral to the caller.
     ///
     /// Gives ownership of the peripheral back to the caller, before calling [Pll] destructor.
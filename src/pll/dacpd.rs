@@ -34,3 +34,30 @@ impl From<Dacpd> for pac::sys_syscon::sys_syscfg11::Pll2Dacpd {
         }
     }
 }
+
+impl From<pac::sys_syscon::sys_syscfg6::Pll0Dacpd> for Dacpd {
+    fn from(val: pac::sys_syscon::sys_syscfg6::Pll0Dacpd) -> Self {
+        match val {
+            pac::sys_syscon::sys_syscfg6::Pll0Dacpd::Off => Self::Off,
+            pac::sys_syscon::sys_syscfg6::Pll0Dacpd::On => Self::On,
+        }
+    }
+}
+
+impl From<pac::sys_syscon::sys_syscfg9::Pll1Dacpd> for Dacpd {
+    fn from(val: pac::sys_syscon::sys_syscfg9::Pll1Dacpd) -> Self {
+        match val {
+            pac::sys_syscon::sys_syscfg9::Pll1Dacpd::Off => Self::Off,
+            pac::sys_syscon::sys_syscfg9::Pll1Dacpd::On => Self::On,
+        }
+    }
+}
+
+impl From<pac::sys_syscon::sys_syscfg11::Pll2Dacpd> for Dacpd {
+    fn from(val: pac::sys_syscon::sys_syscfg11::Pll2Dacpd) -> Self {
+        match val {
+            pac::sys_syscon::sys_syscfg11::Pll2Dacpd::Off => Self::Off,
+            pac::sys_syscon::sys_syscfg11::Pll2Dacpd::On => Self::On,
+        }
+    }
+}
@@ -34,3 +34,30 @@ impl From<Dsmpd> for pac::sys_syscon::sys_syscfg11::Pll2Dsmpd {
         }
     }
 }
+
+impl From<pac::sys_syscon::sys_syscfg6::Pll0Dsmpd> for Dsmpd {
+    fn from(val: pac::sys_syscon::sys_syscfg6::Pll0Dsmpd) -> Self {
+        match val {
+            pac::sys_syscon::sys_syscfg6::Pll0Dsmpd::Off => Self::Off,
+            pac::sys_syscon::sys_syscfg6::Pll0Dsmpd::On => Self::On,
+        }
+    }
+}
+
+impl From<pac::sys_syscon::sys_syscfg9::Pll1Dsmpd> for Dsmpd {
+    fn from(val: pac::sys_syscon::sys_syscfg9::Pll1Dsmpd) -> Self {
+        match val {
+            pac::sys_syscon::sys_syscfg9::Pll1Dsmpd::Off => Self::Off,
+            pac::sys_syscon::sys_syscfg9::Pll1Dsmpd::On => Self::On,
+        }
+    }
+}
+
+impl From<pac::sys_syscon::sys_syscfg11::Pll2Dsmpd> for Dsmpd {
+    fn from(val: pac::sys_syscon::sys_syscfg11::Pll2Dsmpd) -> Self {
+        match val {
+            pac::sys_syscon::sys_syscfg11::Pll2Dsmpd::Off => Self::Off,
+            pac::sys_syscon::sys_syscfg11::Pll2Dsmpd::On => Self::On,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use core::fmt;
+
+use super::PllId;
+
+/// Convenience [`Result`](core::result::Result) alias for JH71xx PLL module.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// PLL configuration errors.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Output frequency (in Hertz) cannot be reached within the PLL limits.
+    InvalidRate(u64),
+    /// PLL is powered down.
+    PoweredDown(PllId),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRate(err) => write!(f, "invalid PLL output rate: {err} Hz"),
+            Self::PoweredDown(err) => write!(f, "{err:?} is powered down"),
+        }
+    }
+}
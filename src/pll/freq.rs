@@ -1,10 +1,33 @@
-use super::{Dacpd, Dsmpd};
+use super::{Dacpd, Dsmpd, Error, Result};
+
+/// Minimum PLL pre-divider value.
+pub const PREDIV_MIN: u8 = 1;
+/// Maximum PLL pre-divider value.
+pub const PREDIV_MAX: u8 = 63;
+/// Minimum PLL feedback divider value.
+pub const FBDIV_MIN: u16 = 16;
+/// Maximum PLL feedback divider value.
+pub const FBDIV_MAX: u16 = 4095;
+/// Supported PLL post-divider values.
+pub const POSTDIV1_VALUES: [u8; 3] = [1, 2, 4];
+/// Number of fractional bits of the feedback divider.
+pub const FRAC_BITS: u32 = 24;
+/// Bit mask of the fractional part of the feedback divider.
+pub const FRAC_MASK: u32 = (1 << FRAC_BITS) - 1;
+/// Minimum PLL phase detector frequency (in Hertz): `ref_hz / prediv`.
+pub const PFD_MIN_HZ: u64 = 1_000_000;
+/// Minimum PLL VCO frequency (in Hertz): 375 MHz
+pub const VCO_MIN_HZ: u64 = 375_000_000;
+/// Maximum PLL VCO frequency (in Hertz): 3 GHz
+pub const VCO_MAX_HZ: u64 = 3_000_000_000;
 
 /// Represents PLL frequency settings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Freq {
     pub prediv: u8,
     pub fbdiv: u16,
     pub postdiv1: u8,
+    pub frac: u32,
     pub dacpd: Dacpd,
     pub dsmpd: Dsmpd,
 }
@@ -16,6 +39,7 @@ impl Freq {
             prediv: 1,
             fbdiv: 1,
             postdiv1: 1,
+            frac: 0,
             dacpd: Dacpd::On,
             dsmpd: Dsmpd::On,
         }
@@ -27,6 +51,7 @@ impl Freq {
             prediv: 3,
             fbdiv: 125,
             postdiv1: 1,
+            frac: 0,
             dacpd: Dacpd::On,
             dsmpd: Dsmpd::On,
         }
@@ -38,6 +63,7 @@ impl Freq {
             prediv: 12,
             fbdiv: 533,
             postdiv1: 1,
+            frac: 0,
             dacpd: Dacpd::On,
             dsmpd: Dsmpd::On,
         }
@@ -49,6 +75,7 @@ impl Freq {
             prediv: 12,
             fbdiv: 533,
             postdiv1: 1,
+            frac: 0,
             dacpd: Dacpd::Off,
             dsmpd: Dsmpd::Off,
         }
@@ -60,10 +87,141 @@ impl Freq {
             prediv: 2,
             fbdiv: 99,
             postdiv1: 1,
+            frac: 0,
             dacpd: Dacpd::On,
             dsmpd: Dsmpd::On,
         }
     }
+
+    /// Gets whether the fractional part of the feedback divider is used.
+    pub const fn is_fractional(&self) -> bool {
+        matches!(self.dsmpd, Dsmpd::Off)
+    }
+
+    // Gets the feedback divider in 2^-24 units.
+    const fn fb(&self) -> u128 {
+        let frac = if self.is_fractional() {
+            (self.frac & FRAC_MASK) as u128
+        } else {
+            0
+        };
+
+        ((self.fbdiv as u128) << FRAC_BITS) | frac
+    }
+
+    /// Computes the PLL VCO frequency (in Hertz) from the reference clock frequency `ref_hz`.
+    ///
+    /// `FVCO = ref_hz * (fbdiv + frac / 2^24) / prediv`
+    ///
+    /// The fractional part is only used in fractional mode, see [is_fractional](Self::is_fractional).
+    ///
+    /// Returns zero for a zero `prediv`.
+    pub const fn vco_hz(&self, ref_hz: u64) -> u64 {
+        let fb = self.fb();
+
+        match (ref_hz as u128 * fb).checked_div((self.prediv as u128) << FRAC_BITS) {
+            Some(hz) => hz as u64,
+            None => 0,
+        }
+    }
+
+    /// Computes the PLL output frequency (in Hertz) from the reference clock frequency `ref_hz`.
+    ///
+    /// `FOUT = ref_hz * (fbdiv + frac / 2^24) / prediv / postdiv1`
+    ///
+    /// Returns zero for a zero `prediv` or `postdiv1`.
+    pub const fn output_hz(&self, ref_hz: u64) -> u64 {
+        let fb = self.fb();
+        let div = (self.prediv as u128 * self.postdiv1 as u128) << FRAC_BITS;
+
+        match (ref_hz as u128 * fb).checked_div(div) {
+            Some(hz) => hz as u64,
+            None => 0,
+        }
+    }
+
+    /// Finds the [Freq] settings giving the output frequency closest to `target_hz`.
+    ///
+    /// Searches `prediv`, `fbdiv` and `postdiv1` within the PLL limits, preferring integer mode
+    /// when it reaches `target_hz` exactly, and fractional mode otherwise.
+    ///
+    /// Returns the settings, and the achieved output frequency (in Hertz).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jh71xx_hal::{clocks, pll};
+    ///
+    /// let (freq, hz) = pll::Freq::from_target_hz(1_500_000_000, clocks::OSC_HZ).unwrap();
+    ///
+    /// assert_eq!((freq.prediv, freq.fbdiv), (2, 125));
+    /// assert_eq!(hz, 1_500_000_000);
+    /// ```
+    pub fn from_target_hz(target_hz: u64, ref_hz: u64) -> Result<(Self, u64)> {
+        if target_hz == 0 || ref_hz == 0 {
+            return Err(Error::InvalidRate(target_hz));
+        }
+
+        let best = |best: Option<(Self, u64)>, cand: (Self, u64)| match best {
+            Some((_, hz)) if hz.abs_diff(target_hz) <= cand.1.abs_diff(target_hz) => best,
+            _ => Some(cand),
+        };
+
+        let integer = Self::candidates(target_hz, ref_hz, false).fold(None, best);
+
+        match integer {
+            Some((_, hz)) if hz == target_hz => integer,
+            _ => Self::candidates(target_hz, ref_hz, true).fold(integer, best),
+        }
+        .ok_or(Error::InvalidRate(target_hz))
+    }
+
+    // Iterates over valid settings approaching `target_hz`, one per `postdiv1` and `prediv` pair.
+    fn candidates(
+        target_hz: u64,
+        ref_hz: u64,
+        fractional: bool,
+    ) -> impl Iterator<Item = (Self, u64)> {
+        POSTDIV1_VALUES.into_iter().flat_map(move |postdiv1| {
+            (PREDIV_MIN..=PREDIV_MAX)
+                .filter(move |&prediv| ref_hz / prediv as u64 >= PFD_MIN_HZ)
+                .filter_map(move |prediv| {
+                    // feedback divider in 2^-24 units, rounded to closest
+                    let num = (target_hz as u128 * postdiv1 as u128 * prediv as u128) << FRAC_BITS;
+                    let fb = (num + ref_hz as u128 / 2) / ref_hz as u128;
+                    let fb = if fractional {
+                        fb
+                    } else {
+                        (fb + (1 << (FRAC_BITS - 1))) & !(FRAC_MASK as u128)
+                    };
+
+                    let fbdiv = (fb >> FRAC_BITS) as u64;
+                    if !(FBDIV_MIN as u64..=FBDIV_MAX as u64).contains(&fbdiv) {
+                        return None;
+                    }
+
+                    let (dacpd, dsmpd) = if fractional {
+                        (Dacpd::Off, Dsmpd::Off)
+                    } else {
+                        (Dacpd::On, Dsmpd::On)
+                    };
+
+                    let freq = Self {
+                        prediv,
+                        fbdiv: fbdiv as u16,
+                        postdiv1,
+                        frac: (fb as u32) & FRAC_MASK,
+                        dacpd,
+                        dsmpd,
+                    };
+
+                    let vco_hz = freq.vco_hz(ref_hz);
+                    (VCO_MIN_HZ..=VCO_MAX_HZ)
+                        .contains(&vco_hz)
+                        .then(|| (freq, freq.output_hz(ref_hz)))
+                })
+        })
+    }
 }
 
 impl Default for Freq {
@@ -71,3 +229,80 @@ impl Default for Freq {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OSC_HZ: u64 = 24_000_000;
+
+    #[test]
+    fn test_output_hz() {
+        assert_eq!(Freq::pll0_1ghz().output_hz(OSC_HZ), 1_000_000_000);
+        assert_eq!(
+            Freq::pll1_ddr2133_1066mhz().output_hz(OSC_HZ),
+            1_066_000_000
+        );
+        assert_eq!(Freq::pll2_1188mhz().output_hz(OSC_HZ), 1_188_000_000);
+
+        let invalid = Freq {
+            prediv: 0,
+            ..Freq::new()
+        };
+        assert_eq!(invalid.output_hz(OSC_HZ), 0);
+    }
+
+    #[test]
+    fn test_from_target_hz_integer() {
+        // presets are found again, with the smallest pre-divider
+        [
+            Freq::pll0_1ghz(),
+            Freq::pll1_ddr2133_1066mhz(),
+            Freq::pll2_1188mhz(),
+        ]
+        .into_iter()
+        .for_each(|preset| {
+            let hz = preset.output_hz(OSC_HZ);
+            assert_eq!(Freq::from_target_hz(hz, OSC_HZ), Ok((preset, hz)));
+        });
+
+        let (freq, hz) = Freq::from_target_hz(1_250_000_000, OSC_HZ).unwrap();
+        assert_eq!((freq.prediv, freq.fbdiv, freq.postdiv1), (12, 625, 1));
+        assert_eq!(hz, 1_250_000_000);
+
+        // below the VCO range, the post-divider takes over
+        let (freq, hz) = Freq::from_target_hz(200_000_000, OSC_HZ).unwrap();
+        assert_eq!((freq.prediv, freq.fbdiv, freq.postdiv1), (3, 50, 2));
+        assert_eq!(hz, 200_000_000);
+        assert!(!freq.is_fractional());
+    }
+
+    #[test]
+    fn test_from_target_hz_fractional() {
+        // 4x 148.5 MHz (1080p60 pixel clock) is reachable in integer mode
+        let target = 594_000_000;
+        let (freq, hz) = Freq::from_target_hz(target, OSC_HZ).unwrap();
+        assert_eq!(hz, target);
+        assert!(!freq.is_fractional());
+
+        // 1,234,567,890 Hz has no exact integer setting
+        let target = 1_234_567_890;
+        let (freq_frac, hz) = Freq::from_target_hz(target, OSC_HZ).unwrap();
+        assert!(freq_frac.is_fractional());
+        assert!(hz.abs_diff(target) <= 1, "{hz}");
+        assert!((VCO_MIN_HZ..=VCO_MAX_HZ).contains(&freq_frac.vco_hz(OSC_HZ)));
+    }
+
+    #[test]
+    fn test_from_target_hz_invalid() {
+        assert_eq!(Freq::from_target_hz(0, OSC_HZ), Err(Error::InvalidRate(0)));
+        assert_eq!(
+            Freq::from_target_hz(10_000_000_000, OSC_HZ),
+            Err(Error::InvalidRate(10_000_000_000))
+        );
+        assert_eq!(
+            Freq::from_target_hz(1_000_000, OSC_HZ),
+            Err(Error::InvalidRate(1_000_000))
+        );
+    }
+}
@@ -0,0 +1,11 @@
+/// Identifies one of the SoC PLLs.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PllId {
+    /// PLL0: CPU root, peripheral root.
+    Pll0,
+    /// PLL1: DDR bus.
+    Pll1,
+    /// PLL2: bus root, GPU, ISP, display.
+    Pll2,
+}
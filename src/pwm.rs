@@ -17,6 +17,8 @@
 
 use embedded_hal::pwm::{ErrorType, SetDutyCycle};
 
+use crate::gpio::{AlternatePin, GpioCfg, IntoAlternate};
+
 mod error;
 mod peripheral;
 
@@ -24,8 +26,11 @@ pub use error::*;
 pub use peripheral::*;
 
 /// Represents the PWM PTC peripheral on JH71xx-based SoCs.
-pub struct Pwm<PWM: PwmPeripheral> {
+///
+/// `PINS` are the pins owned by the [Pwm], see [Pwm::with_pin].
+pub struct Pwm<PWM: PwmPeripheral, PINS = ()> {
     periph: PWM,
+    pins: PINS,
 }
 
 impl<PWM: PwmPeripheral> Pwm<PWM> {
@@ -38,11 +43,46 @@ impl<PWM: PwmPeripheral> Pwm<PWM> {
     /// let dp = pac::Peripherals::take().unwrap();
     /// let _pwm = pwm::Pwm::new(dp.pwm);
     /// ```
-    pub fn new(mut periph: PWM) -> Self {
+    pub fn new(periph: PWM) -> Self {
+        Self::new_inner(periph, ())
+    }
+}
+
+impl<PWM: PwmPins> Pwm<PWM> {
+    /// Creates a new [Pwm] owning its output pin.
+    ///
+    /// The pin is routed to the output signal of the PWM channel, and is returned by
+    /// [release](Pwm::release).
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jh71xx_hal::{gpio, pac, pwm};
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let out = gpio::get_gpio(dp.sys_pinctrl.padcfg().gpio46());
+    ///
+    /// let pwm = pwm::Pwm::with_pin(dp.pwm, out);
+    /// let (_pwm, _out) = pwm.release();
+    /// ```
+    pub fn with_pin<'g, OUT: GpioCfg>(
+        periph: PWM,
+        out: impl IntoAlternate<'g, OUT>,
+    ) -> Pwm<PWM, AlternatePin<'g, OUT, PWM::Out>> {
+        Pwm::new_inner(periph, out.into_alternate::<PWM::Out>())
+    }
+}
+
+impl<PWM: PwmPeripheral, PINS> Pwm<PWM, PINS> {
+    fn new_inner(mut periph: PWM, pins: PINS) -> Self {
         if periph.period() > MAX_PERIOD {
             periph.set_period(MAX_PERIOD);
         }
-        Self { periph }
+        Self { periph, pins }
+    }
+
+    /// Releases the PWM peripheral and the pins owned by the [Pwm].
+    pub fn release(self) -> (PWM, PINS) {
+        (self.periph, self.pins)
     }
 
     /// Gets the period of the [Pwm] peripheral.
@@ -106,11 +146,11 @@ impl<PWM: PwmPeripheral> Pwm<PWM> {
     }
 }
 
-impl<PWM: PwmPeripheral> ErrorType for Pwm<PWM> {
+impl<PWM: PwmPeripheral, PINS> ErrorType for Pwm<PWM, PINS> {
     type Error = Error;
 }
 
-impl<PWM: PwmPeripheral> SetDutyCycle for Pwm<PWM> {
+impl<PWM: PwmPeripheral, PINS> SetDutyCycle for Pwm<PWM, PINS> {
     fn max_duty_cycle(&self) -> u16 {
         (self.periph.period() & 0xffff) as u16
     }
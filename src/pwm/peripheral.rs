@@ -1,5 +1,7 @@
 use pac::{Pwm0, Pwm1, Pwm2, Pwm3};
 
+use crate::gpio::{self, Function};
+
 /// Max period length configurable by the HAL.
 pub const MAX_PERIOD: u32 = u16::MAX as u32;
 
@@ -68,3 +70,22 @@ impl_pwm_peripheral!(Pwm0);
 impl_pwm_peripheral!(Pwm1);
 impl_pwm_peripheral!(Pwm2);
 impl_pwm_peripheral!(Pwm3);
+
+/// Pad signal of a [PwmPeripheral], routed by [Pwm::with_pin](super::Pwm::with_pin).
+pub trait PwmPins: PwmPeripheral {
+    /// Output signal.
+    type Out: Function;
+}
+
+macro_rules! impl_pwm_pins {
+    ($pwm:ident, $out:ident) => {
+        impl PwmPins for $pwm {
+            type Out = gpio::$out;
+        }
+    };
+}
+
+impl_pwm_pins!(Pwm0, Pwm0);
+impl_pwm_pins!(Pwm1, Pwm1);
+impl_pwm_pins!(Pwm2, Pwm2);
+impl_pwm_pins!(Pwm3, Pwm3);
@@ -60,6 +60,8 @@
 
 use embedded_hal::spi::{ErrorType, SpiBus};
 
+use crate::gpio::{AlternatePin, GpioCfg, IntoAlternate};
+
 mod error;
 mod peripheral;
 
@@ -67,11 +69,29 @@ pub use error::*;
 pub use peripheral::*;
 
 /// Represents an SPI peripheral on a JH71xx-based SoC.
+///
+/// `PINS` are the pins owned by the [Spi], see [Spi::with_pins].
 #[repr(C)]
-pub struct Spi<SPI: SpiPeripheral, const WORD: u8> {
+pub struct Spi<SPI: SpiPeripheral, const WORD: u8, PINS = ()> {
     periph: SPI,
+    pins: PINS,
 }
 
+/// Pins owned by an [Spi] created with [Spi::with_pins]: SCK, MOSI, MISO and CS.
+pub type SpiPinSet<'g, SPI, SCK, MOSI, MISO, CS> = (
+    AlternatePin<'g, SCK, <SPI as SpiPins>::Sck>,
+    AlternatePin<'g, MOSI, <SPI as SpiPins>::Mosi>,
+    AlternatePin<'g, MISO, <SPI as SpiPins>::Miso>,
+    AlternatePin<'g, CS, <SPI as SpiPins>::Cs>,
+);
+
+/// Pins owned by an [Spi] created with [Spi::with_pins_no_cs]: SCK, MOSI and MISO.
+pub type SpiBusPinSet<'g, SPI, SCK, MOSI, MISO> = (
+    AlternatePin<'g, SCK, <SPI as SpiPins>::Sck>,
+    AlternatePin<'g, MOSI, <SPI as SpiPins>::Mosi>,
+    AlternatePin<'g, MISO, <SPI as SpiPins>::Miso>,
+);
+
 impl<SPI: SpiPeripheral, const WORD: u8> Spi<SPI, WORD> {
     /// Creates a new [Spi] from an SPI peripheral.
     ///
@@ -86,17 +106,8 @@ impl<SPI: SpiPeripheral, const WORD: u8> Spi<SPI, WORD> {
     /// let dp = pac::Peripherals::take().unwrap();
     /// let _spi = spi::Spi::<pac::Spi0, 8>::new(dp.spi0);
     /// ```
-    pub fn new(mut periph: SPI) -> Result<Self> {
-        let data_size = DataSize::from(WORD);
-        match data_size {
-            DataSize::Eight | DataSize::Sixteen => {
-                periph.set_dss(data_size);
-                periph.set_ms(ModeSelect::Master);
-                periph.set_frf(FrameFormat::Spi);
-                Ok(Self { periph })
-            }
-            _ => Err(Error::DataSize(data_size)),
-        }
+    pub fn new(periph: SPI) -> Result<Self> {
+        Self::new_inner(periph, ())
     }
 
     /// Splits the [Spi] back into the inner peripheral type.
@@ -105,11 +116,121 @@ impl<SPI: SpiPeripheral, const WORD: u8> Spi<SPI, WORD> {
     }
 }
 
-impl<SPI: SpiPeripheral, const WORD: u8> ErrorType for Spi<SPI, WORD> {
+impl<SPI: SpiPins, const WORD: u8> Spi<SPI, WORD> {
+    /// Creates a new [Spi] owning its SCK, MOSI, MISO and CS pins.
+    ///
+    /// The pins are routed to the signals of the peripheral, and are returned by
+    /// [release](Spi::release).
+    ///
+    /// The `WORD` size is checked before the pins are routed, so the pins are left untouched on
+    /// error.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jh71xx_hal::{gpio, pac, spi};
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let padcfg = dp.sys_pinctrl.padcfg();
+    ///
+    /// let sck = gpio::get_gpio(padcfg.gpio48());
+    /// let mosi = gpio::get_gpio(padcfg.gpio52());
+    /// let miso = gpio::get_gpio(padcfg.gpio53());
+    /// let cs = gpio::get_gpio(padcfg.gpio49());
+    ///
+    /// let spi0 = spi::Spi::<pac::Spi0, 8>::with_pins(dp.spi0, sck, mosi, miso, cs).unwrap();
+    /// let (_spi0, _pins) = spi0.release();
+    /// ```
+    pub fn with_pins<'g, SCK: GpioCfg, MOSI: GpioCfg, MISO: GpioCfg, CS: GpioCfg>(
+        periph: SPI,
+        sck: impl IntoAlternate<'g, SCK>,
+        mosi: impl IntoAlternate<'g, MOSI>,
+        miso: impl IntoAlternate<'g, MISO>,
+        cs: impl IntoAlternate<'g, CS>,
+    ) -> Result<Spi<SPI, WORD, SpiPinSet<'g, SPI, SCK, MOSI, MISO, CS>>> {
+        Self::data_size()?;
+
+        let pins = (
+            sck.into_alternate::<SPI::Sck>(),
+            mosi.into_alternate::<SPI::Mosi>(),
+            miso.into_alternate::<SPI::Miso>(),
+            cs.into_alternate::<SPI::Cs>(),
+        );
+
+        Spi::new_inner(periph, pins)
+    }
+
+    /// Creates a new [Spi] owning its SCK, MOSI and MISO pins, without a hardware chip select.
+    ///
+    /// Use this when the chip select is driven as a regular GPIO output, e.g. by an
+    /// [`SpiDevice`](embedded_hal::spi::SpiDevice) implementation sharing the bus.
+    ///
+    /// The `WORD` size is checked before the pins are routed, so the pins are left untouched on
+    /// error.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jh71xx_hal::{gpio, pac, spi};
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let padcfg = dp.sys_pinctrl.padcfg();
+    ///
+    /// let sck = gpio::get_gpio(padcfg.gpio48());
+    /// let mosi = gpio::get_gpio(padcfg.gpio52());
+    /// let miso = gpio::get_gpio(padcfg.gpio53());
+    ///
+    /// let spi0 = spi::Spi::<pac::Spi0, 8>::with_pins_no_cs(dp.spi0, sck, mosi, miso).unwrap();
+    /// let (_spi0, (_sck, _mosi, _miso)) = spi0.release();
+    /// ```
+    pub fn with_pins_no_cs<'g, SCK: GpioCfg, MOSI: GpioCfg, MISO: GpioCfg>(
+        periph: SPI,
+        sck: impl IntoAlternate<'g, SCK>,
+        mosi: impl IntoAlternate<'g, MOSI>,
+        miso: impl IntoAlternate<'g, MISO>,
+    ) -> Result<Spi<SPI, WORD, SpiBusPinSet<'g, SPI, SCK, MOSI, MISO>>> {
+        Self::data_size()?;
+
+        let pins = (
+            sck.into_alternate::<SPI::Sck>(),
+            mosi.into_alternate::<SPI::Mosi>(),
+            miso.into_alternate::<SPI::Miso>(),
+        );
+
+        Spi::new_inner(periph, pins)
+    }
+}
+
+impl<SPI: SpiPeripheral, const WORD: u8, PINS> Spi<SPI, WORD, PINS> {
+    fn new_inner(mut periph: SPI, pins: PINS) -> Result<Self> {
+        let data_size = Self::data_size()?;
+
+        periph.set_dss(data_size);
+        periph.set_ms(ModeSelect::Master);
+        periph.set_frf(FrameFormat::Spi);
+
+        Ok(Self { periph, pins })
+    }
+
+    // Gets the [DataSize] of `WORD`, only 8-bit and 16-bit transfers are supported.
+    //
+    // NOTE: the [DataSize] register encoding is the number of bits minus one.
+    fn data_size() -> Result<DataSize> {
+        match DataSize::from(WORD.wrapping_sub(1)) {
+            data_size @ (DataSize::Eight | DataSize::Sixteen) => Ok(data_size),
+            data_size => Err(Error::DataSize(data_size)),
+        }
+    }
+
+    /// Releases the SPI peripheral and the pins owned by the [Spi].
+    pub fn release(self) -> (SPI, PINS) {
+        (self.periph, self.pins)
+    }
+}
+
+impl<SPI: SpiPeripheral, const WORD: u8, PINS> ErrorType for Spi<SPI, WORD, PINS> {
     type Error = Error;
 }
 
-impl<SPI: SpiPeripheral> SpiBus<u8> for Spi<SPI, 8> {
+impl<SPI: SpiPeripheral, PINS> SpiBus<u8> for Spi<SPI, 8, PINS> {
     fn read(&mut self, words: &mut [u8]) -> Result<()> {
         for word in words.iter_mut() {
             // Spin until receive FIFO is full
@@ -179,7 +300,7 @@ impl<SPI: SpiPeripheral> SpiBus<u8> for Spi<SPI, 8> {
     }
 }
 
-impl<SPI: SpiPeripheral> SpiBus<u16> for Spi<SPI, 16> {
+impl<SPI: SpiPeripheral, PINS> SpiBus<u16> for Spi<SPI, 16, PINS> {
     fn read(&mut self, words: &mut [u16]) -> Result<()> {
         for word in words.iter_mut() {
             // Spin until receive FIFO is full
@@ -247,18 +368,20 @@ impl<SPI: SpiPeripheral> SpiBus<u16> for Spi<SPI, 16> {
     }
 }
 
-impl<SPI: SpiPeripheral> TryFrom<Spi<SPI, 8>> for Spi<SPI, 16> {
+impl<SPI: SpiPeripheral, PINS> TryFrom<Spi<SPI, 8, PINS>> for Spi<SPI, 16, PINS> {
     type Error = Error;
 
-    fn try_from(val: Spi<SPI, 8>) -> Result<Self> {
-        Self::new(val.split())
+    fn try_from(val: Spi<SPI, 8, PINS>) -> Result<Self> {
+        let (periph, pins) = val.release();
+        Self::new_inner(periph, pins)
     }
 }
 
-impl<SPI: SpiPeripheral> TryFrom<Spi<SPI, 16>> for Spi<SPI, 8> {
+impl<SPI: SpiPeripheral, PINS> TryFrom<Spi<SPI, 16, PINS>> for Spi<SPI, 8, PINS> {
     type Error = Error;
 
-    fn try_from(val: Spi<SPI, 16>) -> Result<Self> {
-        Self::new(val.split())
+    fn try_from(val: Spi<SPI, 16, PINS>) -> Result<Self> {
+        let (periph, pins) = val.release();
+        Self::new_inner(periph, pins)
     }
 }
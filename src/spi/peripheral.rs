@@ -2,6 +2,8 @@ use core::fmt;
 
 use pac::{Spi0, Spi1, Spi2, Spi3, Spi4, Spi5, Spi6};
 
+use crate::gpio::{self, Function};
+
 /// Represents the data word size (in bits) of the FIFO buffers.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -560,3 +562,35 @@ impl_spi_peripheral!(Spi3);
 impl_spi_peripheral!(Spi4);
 impl_spi_peripheral!(Spi5);
 impl_spi_peripheral!(Spi6);
+
+/// Pad signals of an [SpiPeripheral], routed by [Spi::with_pins](super::Spi::with_pins) and
+/// [Spi::with_pins_no_cs](super::Spi::with_pins_no_cs).
+pub trait SpiPins: SpiPeripheral {
+    /// Clock output signal.
+    type Sck: Function;
+    /// Transmit data signal.
+    type Mosi: Function;
+    /// Receive data signal.
+    type Miso: Function;
+    /// Chip select output signal.
+    type Cs: Function;
+}
+
+macro_rules! impl_spi_pins {
+    ($spi:ident, $sck:ident, $mosi:ident, $miso:ident, $cs:ident) => {
+        impl SpiPins for $spi {
+            type Sck = gpio::$sck;
+            type Mosi = gpio::$mosi;
+            type Miso = gpio::$miso;
+            type Cs = gpio::$cs;
+        }
+    };
+}
+
+impl_spi_pins!(Spi0, Spi0Clk, Spi0Tx, Spi0Rx, Spi0Cs);
+impl_spi_pins!(Spi1, Spi1Clk, Spi1Tx, Spi1Rx, Spi1Cs);
+impl_spi_pins!(Spi2, Spi2Clk, Spi2Tx, Spi2Rx, Spi2Cs);
+impl_spi_pins!(Spi3, Spi3Clk, Spi3Tx, Spi3Rx, Spi3Cs);
+impl_spi_pins!(Spi4, Spi4Clk, Spi4Tx, Spi4Rx, Spi4Cs);
+impl_spi_pins!(Spi5, Spi5Clk, Spi5Tx, Spi5Rx, Spi5Cs);
+impl_spi_pins!(Spi6, Spi6Clk, Spi6Tx, Spi6Rx, Spi6Cs);
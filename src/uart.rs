@@ -1,6 +1,8 @@
 //! UART peripheral access
 
-use core::marker::PhantomData;
+use core::{fmt, marker::PhantomData};
+
+use crate::gpio::{AlternatePin, GpioCfg, IntoAlternate};
 
 mod config;
 mod error;
@@ -10,9 +12,14 @@ pub use config::*;
 pub use error::*;
 pub use serial::*;
 
-/// Clock used by Dw_apb_uart: 50 MHz
+/// APB0 bus clock: 50 MHz
+///
+/// This is the register interface clock, the baud rate is derived from the core clock, see
+/// [CLK_OSC].
 pub const APB0: usize = 50_000_000;
 /// Core clock oscillator: 24 MHz
+///
+/// Reset parent of the UART0-2 core clocks, and the default [Config] clock rate.
 pub const CLK_OSC: usize = 24_000_000;
 
 /// Transaction timeout in microseconds.
@@ -105,14 +112,24 @@ impl<T: Serial> UartRx<T> {
 /// Represents a UART peripheral.
 ///
 /// Based on the implementation in [`esp-hal`](https://github.com/esp-rs/esp-hal).
+///
+/// `PINS` are the pins owned by the [Uart], see [Uart::with_pins].
 #[repr(C)]
-pub struct Uart<UART: Serial> {
+pub struct Uart<UART: Serial, PINS = ()> {
+    uart: UART,
     tx: UartTx<UART>,
     rx: UartRx<UART>,
     timeout: u64,
     config: Config,
+    pins: PINS,
 }
 
+/// Pins owned by a [Uart] created with [Uart::with_pins]: TX and RX.
+pub type UartPinSet<'g, UART, TX, RX> = (
+    AlternatePin<'g, TX, <UART as UartPins>::Tx>,
+    AlternatePin<'g, RX, <UART as UartPins>::Rx>,
+);
+
 impl<UART: Serial> Uart<UART> {
     /// Creates a new [Uart].
     ///
@@ -153,25 +170,84 @@ impl<UART: Serial> Uart<UART> {
     ///         stop: uart::Stop::One,
     ///         parity: uart::Parity::None,
     ///         baud_rate: uart::BaudRate::B115200,
-    ///         // default APB0 clock frequency
-    ///         clk_hz: 50_000_000,
+    ///         // UART0 core clock, on the oscillator
+    ///         clk_hz: 24_000_000,
     ///     },
     /// );
     /// ```
-    pub fn new_with_config(mut uart: UART, timeout: u64, config: Config) -> Self {
+    pub fn new_with_config(uart: UART, timeout: u64, config: Config) -> Self {
+        Self::new_inner(uart, timeout, config, ())
+    }
+
+    /// Splits the [Uart] into a transmitter and receiver
+    pub fn split(self) -> (UartTx<UART>, UartRx<UART>) {
+        (self.tx, self.rx)
+    }
+}
+
+impl<UART: UartPins> Uart<UART> {
+    /// Creates a new [Uart] owning its TX and RX pins.
+    ///
+    /// The pins are routed to the TX and RX signals of the peripheral, and are returned by
+    /// [release](Uart::release).
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// # use jh71xx_hal::{gpio, pac, uart};
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let padcfg = dp.sys_pinctrl.padcfg();
+    ///
+    /// let tx = gpio::get_gpio(padcfg.gpio5());
+    /// let rx = gpio::get_gpio(padcfg.gpio6());
+    ///
+    /// let uart0 = uart::Uart::with_pins(dp.uart0, tx, rx);
+    /// let (_uart0, (_tx, _rx)) = uart0.release();
+    /// ```
+    pub fn with_pins<'g, TX: GpioCfg, RX: GpioCfg>(
+        uart: UART,
+        tx: impl IntoAlternate<'g, TX>,
+        rx: impl IntoAlternate<'g, RX>,
+    ) -> Uart<UART, UartPinSet<'g, UART, TX, RX>> {
+        Self::with_pins_and_config(uart, tx, rx, TIMEOUT_US, Config::new())
+    }
+
+    /// Creates a new [Uart] owning its TX and RX pins, from a custom configuration.
+    ///
+    /// See [with_pins](Self::with_pins) and [new_with_config](Self::new_with_config).
+    pub fn with_pins_and_config<'g, TX: GpioCfg, RX: GpioCfg>(
+        uart: UART,
+        tx: impl IntoAlternate<'g, TX>,
+        rx: impl IntoAlternate<'g, RX>,
+        timeout: u64,
+        config: Config,
+    ) -> Uart<UART, UartPinSet<'g, UART, TX, RX>> {
+        let pins = (
+            tx.into_alternate::<UART::Tx>(),
+            rx.into_alternate::<UART::Rx>(),
+        );
+
+        Uart::new_inner(uart, timeout, config, pins)
+    }
+}
+
+impl<UART: Serial, PINS> Uart<UART, PINS> {
+    fn new_inner(mut uart: UART, timeout: u64, config: Config, pins: PINS) -> Self {
         uart.setup(config).ok();
 
         Self {
+            uart,
             tx: UartTx::new_inner(),
             rx: UartRx::new_inner(),
             timeout,
             config,
+            pins,
         }
     }
 
-    /// Splits the [Uart] into a transmitter and receiver
-    pub fn split(self) -> (UartTx<UART>, UartRx<UART>) {
-        (self.tx, self.rx)
+    /// Releases the UART peripheral and the pins owned by the [Uart].
+    pub fn release(self) -> (UART, PINS) {
+        (self.uart, self.pins)
     }
 
     /// Read a byte from the UART FIFO.
@@ -223,7 +299,7 @@ impl<UART: Serial> Uart<UART> {
     }
 }
 
-impl<UART: Serial> io::ErrorType for Uart<UART> {
+impl<UART: Serial, PINS> io::ErrorType for Uart<UART, PINS> {
     type Error = Error;
 }
 
@@ -235,7 +311,7 @@ impl<UART: Serial> io::ErrorType for UartTx<UART> {
     type Error = Error;
 }
 
-impl<UART: Serial> embedded_hal_nb::serial::ErrorType for Uart<UART> {
+impl<UART: Serial, PINS> embedded_hal_nb::serial::ErrorType for Uart<UART, PINS> {
     type Error = Error;
 }
 
@@ -247,7 +323,7 @@ impl<UART: Serial> embedded_hal_nb::serial::ErrorType for UartTx<UART> {
     type Error = Error;
 }
 
-impl<UART: Serial> io::Read for Uart<UART> {
+impl<UART: Serial, PINS> io::Read for Uart<UART, PINS> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         self.rx.read_bytes(buf)
     }
@@ -259,7 +335,7 @@ impl<UART: Serial> io::Read for UartRx<UART> {
     }
 }
 
-impl<UART: Serial> io::Write for Uart<UART> {
+impl<UART: Serial, PINS> io::Write for Uart<UART, PINS> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
         self.tx.write_bytes(buf)
     }
@@ -281,7 +357,24 @@ impl<UART: Serial> io::Write for UartTx<UART> {
     }
 }
 
-impl<UART: Serial> embedded_hal_nb::serial::Read for Uart<UART> {
+impl<UART: Serial, PINS> fmt::Write for Uart<UART, PINS> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.tx
+            .write_bytes(s.as_bytes())
+            .map(|_| ())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+impl<UART: Serial> fmt::Write for UartTx<UART> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes())
+            .map(|_| ())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+impl<UART: Serial, PINS> embedded_hal_nb::serial::Read for Uart<UART, PINS> {
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
         self.rx.read_byte()
     }
@@ -293,7 +386,7 @@ impl<UART: Serial> embedded_hal_nb::serial::Read for UartRx<UART> {
     }
 }
 
-impl<UART: Serial> embedded_hal_nb::serial::Write for Uart<UART> {
+impl<UART: Serial, PINS> embedded_hal_nb::serial::Write for Uart<UART, PINS> {
     fn write(&mut self, val: u8) -> nb::Result<(), Self::Error> {
         self.tx.write_byte(val)
     }
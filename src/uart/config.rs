@@ -1,4 +1,5 @@
-use super::APB0;
+use super::CLK_OSC;
+use crate::clocks::{self, ClockRates};
 
 /// Fixed divisor constant multiplier.
 ///
@@ -117,15 +118,47 @@ pub struct Config {
 
 impl Config {
     /// Creates a new [Config].
+    ///
+    /// The core clock rate defaults to the oscillator ([CLK_OSC]), the reset parent of the UART0-2
+    /// core clocks. Use [from_clock_rates](Self::from_clock_rates) to take it from the clock tree.
     pub const fn new() -> Self {
         Self {
             data_len: DataLength::new(),
             stop: Stop::new(),
             parity: Parity::new(),
             baud_rate: BaudRate::new(),
-            clk_hz: APB0,
+            clk_hz: CLK_OSC,
         }
     }
+
+    /// Creates a new [Config] with the core clock rate of the UART instance `n`, taken from the
+    /// frozen clock tree.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use jh71xx_hal::{clocks, pac, pll, uart};
+    ///
+    /// let dp = pac::Peripherals::take().unwrap();
+    /// let clocks = clocks::Clocks::new(dp.aoncrg, dp.ispcrg, dp.stgcrg, dp.syscrg, dp.voutcrg);
+    /// let pll = pll::Pll::new(dp.sys_syscon);
+    ///
+    /// let rates = clocks.freeze(pll.rates());
+    /// let config = uart::Config::from_clock_rates(&rates, 0).unwrap();
+    ///
+    /// let _uart0 = uart::Uart::new_with_config(dp.uart0, uart::TIMEOUT_US, config);
+    /// ```
+    pub fn from_clock_rates(rates: &ClockRates, n: usize) -> clocks::Result<Self> {
+        rates
+            .uart_core_hz(n)
+            .map(|hz| Self::new().with_clk_hz(hz as usize))
+    }
+
+    /// Builder function that sets the UART core clock rate (in Hertz), overriding the default.
+    pub const fn with_clk_hz(mut self, clk_hz: usize) -> Self {
+        self.clk_hz = clk_hz;
+        self
+    }
 }
 
 impl Default for Config {
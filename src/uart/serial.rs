@@ -1,6 +1,7 @@
 use crate::pac::{Uart0, Uart1, Uart2, Uart3, Uart4, Uart5};
 
 use super::{Config, Error, Result};
+use crate::gpio::{self, Function};
 
 /// Traits for access to a UART peripheral.
 ///
@@ -124,3 +125,27 @@ impl_uart!(Uart2);
 impl_uart!(Uart3);
 impl_uart!(Uart4);
 impl_uart!(Uart5);
+
+/// Pad signals of a [Serial] peripheral, routed by [Uart::with_pins](super::Uart::with_pins).
+pub trait UartPins: Serial {
+    /// Transmit data signal.
+    type Tx: Function;
+    /// Receive data signal.
+    type Rx: Function;
+}
+
+macro_rules! impl_uart_pins {
+    ($uart:ident, $tx:ident, $rx:ident) => {
+        impl UartPins for $uart {
+            type Tx = gpio::$tx;
+            type Rx = gpio::$rx;
+        }
+    };
+}
+
+impl_uart_pins!(Uart0, Uart0Tx, Uart0Rx);
+impl_uart_pins!(Uart1, Uart1Tx, Uart1Rx);
+impl_uart_pins!(Uart2, Uart2Tx, Uart2Rx);
+impl_uart_pins!(Uart3, Uart3Tx, Uart3Rx);
+impl_uart_pins!(Uart4, Uart4Tx, Uart4Rx);
+impl_uart_pins!(Uart5, Uart5Tx, Uart5Rx);